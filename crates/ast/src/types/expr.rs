@@ -19,9 +19,8 @@ pub enum ExprKind {
 	Literal(LiteralKind, Symbol),
 	/// A path (`path::to::model::Type`).
 	Path(Path),
-	// TODO: discuss whether this is a bad idea, not sure to implement it
-	/// A template for status codes (e.g., `~2xx`).
-	Template(()),
+	/// A template for status codes (e.g., `~2xx`), without the tilde.
+	Template(Symbol),
 
 	// -- Composables --
 	/// An array (`[a, b, c, d]`)
@@ -43,13 +42,15 @@ pub struct Path {
 	pub span: Span,
 }
 
-/// A segment of a path: an identifier.
+/// A segment of a path: an identifier, with type arguments for generic models.
 ///
-/// E.g., `user` or `Type`.
+/// E.g., `user`, `Type` or `Page<Pet>`.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PathSegment {
 	pub ident: Ident,
 	pub id: NodeId,
+	/// Type arguments, only given to the last segment
+	pub args: ThinVec<P<Ty>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -90,4 +91,16 @@ pub enum TyKind {
 	/// A model defined inlined
 	/// e.g. `{ error string }`
 	InlineModel(ThinVec<P<FieldDef>>),
+
+	/// An object with arbitrary keys
+	/// e.g. `{[string]: int}`
+	Map(P<Ty>, P<Ty>),
+
+	/// A type that also accepts `null`
+	/// e.g. `string?`
+	Nullable(P<Ty>),
+
+	/// A value of any of the types
+	/// e.g. `Pet | Error`
+	Union(ThinVec<P<Ty>>),
 }
@@ -1,4 +1,4 @@
-use crate::types::{AttrVec, Expr, NodeId, P, PropertyDef, Ty};
+use crate::types::{AttrVec, Expr, NodeId, P, Path, PropertyDef, Ty};
 use dapic_session::{Ident, Span};
 use thin_vec::ThinVec;
 
@@ -21,20 +21,59 @@ pub enum ItemKind {
 	Path(PathItem),
 	Model(Model),
 	Enum(Enum),
+	Union(Union),
+	TypeAlias(TypeAlias),
 	Query(Query),
 	Headers(Headers),
 	Verb(Verb),
 	StatusCode(StatusCode),
 	Body(Body),
 	Params(Params),
+	Response(Response),
+	Use(Use),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Auth {
-	Use,
-	Define {
-		// TODO
-	},
+	/// `auth Name;` or `auth Name ["scope"];`, secures the enclosing items
+	/// with a scheme defined elsewhere.
+	Use { scopes: Option<P<Expr>> },
+	/// `auth Name { <properties> <flows> }`
+	Define(AuthDef),
+}
+
+/// Definition of a security scheme.
+///
+/// ```text
+/// auth PetstoreAuth {
+///     type "oauth2"
+///     flow implicit {
+///         authorizationUrl "https://petstore.swagger.io/oauth/authorize"
+///         scopes {
+///             ## Modify pets in your account
+///             "write:pets"
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthDef {
+	/// e.g. `type "http"` or `scheme "bearer"`
+	pub properties: ThinVec<P<PropertyDef>>,
+	/// `OAuth2` flows
+	pub flows: ThinVec<AuthFlow>,
+}
+
+/// `flow <kind> { <properties> scopes { <scopes> } }`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthFlow {
+	pub kind: Ident,
+	/// e.g. `tokenUrl "https://example.com/token"`
+	pub properties: ThinVec<P<PropertyDef>>,
+	/// String literals, documented with their outer attributes
+	pub scopes: ThinVec<P<Expr>>,
+
+	pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -56,13 +95,8 @@ pub struct Verb {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct StatusCode {
-	// TODO: change for more advanced expression
-	// e.g.
-	// ```dapi
-	// code 200 {}
-	// code 5xx {}
-	// code IM_A_TEAPOT {}
-	// ```
+	/// Either a number (`200`), a template (`~5xx`), a status name
+	/// (`IM_A_TEAPOT`) or `default`.
 	pub code: P<Expr>,
 	pub items: ThinVec<P<Item>>,
 }
@@ -73,21 +107,73 @@ pub struct Metadata {
 	pub fields: ThinVec<P<PropertyDef>>,
 }
 
+/// `response Name { <items> }`, a response shared by several verbs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+	pub items: ThinVec<P<Item>>,
+}
+
+/// Use of a block defined once in a scope, e.g. `headers Name;`. The name is
+/// the ident of the item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Use {
+	/// `headers Name;`
+	Headers,
+	/// `query Name;`
+	Query,
+	/// `params Name;`
+	Params,
+	/// `code 404 Name;`
+	Response { code: P<Expr> },
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Headers {
 	pub headers: ThinVec<P<FieldDef>>,
 }
 
+/// `model Pet : Base, Timestamps { <fields> }` or `model Page<T> { <fields> }`
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Model {
+	/// Type parameters of a generic model
+	pub generics: ThinVec<Ident>,
+	/// Models whose fields are inherited, in order
+	pub bases: ThinVec<Path>,
 	pub fields: ThinVec<P<FieldDef>>,
 }
 
+/// `enum Code : int { Ok 200 }`, variants are strings unless a type is given
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Enum {
+	/// Type of the variant values
+	pub ty: Option<P<Ty>>,
 	pub variants: ThinVec<P<PropertyDef>>,
 }
 
+/// A tagged union, the variants are models told apart by the value of their
+/// discriminator field.
+///
+/// ```text
+/// union Pet on petType {
+///     cat Cat
+///     dog Dog
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Union {
+	/// Name of the field shared by every variant
+	pub discriminator: Ident,
+	/// The name of a variant is the value of its discriminator
+	pub variants: ThinVec<P<FieldDef>>,
+}
+
+/// `type UserId = string |@format: "uuid"|`, the attributes after the type
+/// belong to the alias.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeAlias {
+	pub ty: P<Ty>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Query {
 	pub fields: ThinVec<P<FieldDef>>,
@@ -125,7 +211,11 @@ pub enum ScopeKind {
 pub struct FieldDef {
 	pub attrs: AttrVec,
 	pub ident: Ident,
+	/// Whether the field may be absent, e.g. `name? string`
+	pub optional: bool,
 	pub ty: P<Ty>,
+	/// Value used when the field is absent, e.g. `limit int = 20`
+	pub default: Option<P<Expr>>,
 
 	pub id: NodeId,
 	pub span: Span,
@@ -56,9 +56,10 @@ pub trait Visitor: Sized {
 pub mod noop {
 	use super::{Visitor, ns};
 	use crate::types::{
-		AttrKind, Attribute, Auth, Body, Enum, Expr, ExprKind, FieldDef, Headers, Item, ItemKind,
-		MetaAttr, Metadata, Model, NormalAttr, Params, Path, PathItem, PathSegment, PropertyDef,
-		Query, Root, ScopeKind, StatusCode, Ty, TyKind, Verb,
+		AttrKind, Attribute, Auth, AuthDef, AuthFlow, Body, Enum, Expr, ExprKind, FieldDef,
+		Headers, Item, ItemKind, MetaAttr, Metadata, Model, NormalAttr, Params, Path, PathItem,
+		PathSegment, PropertyDef, Query, Response, Root, ScopeKind, StatusCode, Ty, TyKind,
+		TypeAlias, Union, Use, Verb,
 	};
 	use dapic_session::Ident;
 
@@ -83,9 +84,10 @@ pub mod noop {
 	}
 
 	pub fn visit_path<V: Visitor>(v: &mut V, Path { segments, span }: &Path) {
-		for PathSegment { ident, id } in segments {
+		for PathSegment { ident, id, args } in segments {
 			v.visit_ident(ident);
 			v.visit_id(id);
+			ns::visit_thin_vec(args, |ty| v.visit_ty(ty));
 		}
 		v.visit_span(span);
 	}
@@ -115,7 +117,7 @@ pub mod noop {
 			}
 			ExprKind::Path(path) => v.visit_path(path),
 			// Noop
-			ExprKind::Literal(_, _) | ExprKind::Template(()) => {}
+			ExprKind::Literal(_, _) | ExprKind::Template(_) => {}
 		}
 	}
 
@@ -124,10 +126,14 @@ pub mod noop {
 		v.visit_span(span);
 
 		match kind {
-			TyKind::Array(ty) | TyKind::Paren(ty) => v.visit_ty(ty),
+			TyKind::Array(ty) | TyKind::Paren(ty) | TyKind::Nullable(ty) => v.visit_ty(ty),
 			TyKind::InlineModel(fields) => ns::visit_thin_vec(fields, |fd| v.visit_field_def(fd)),
+			TyKind::Map(key, value) => {
+				v.visit_ty(key);
+				v.visit_ty(value);
+			}
 			TyKind::Path(path) => v.visit_path(path),
-			TyKind::Tuple(tys) => ns::visit_thin_vec(tys, |ty| v.visit_ty(ty)),
+			TyKind::Tuple(tys) | TyKind::Union(tys) => ns::visit_thin_vec(tys, |ty| v.visit_ty(ty)),
 		}
 	}
 
@@ -137,8 +143,10 @@ pub mod noop {
 			attrs,
 			id,
 			ident,
+			optional: _,
 			span,
 			ty,
+			default,
 		}: &FieldDef,
 	) {
 		ns::visit_attrs(v, attrs);
@@ -146,6 +154,9 @@ pub mod noop {
 		v.visit_ident(ident);
 		v.visit_span(span);
 		v.visit_ty(ty);
+		if let Some(default) = default {
+			v.visit_expr(default);
+		}
 	}
 
 	pub fn visit_property_def<V: Visitor>(
@@ -182,19 +193,55 @@ pub mod noop {
 
 		match kind {
 			ItemKind::Auth(auth) => match auth {
-				Auth::Define {} | Auth::Use => {}
+				Auth::Use { scopes } => {
+					if let Some(scopes) = scopes {
+						v.visit_expr(scopes);
+					}
+				}
+				Auth::Define(AuthDef { properties, flows }) => {
+					ns::visit_thin_vec(properties, |pd| v.visit_property_def(pd));
+					ns::visit_thin_vec(flows, |flow| {
+						let AuthFlow {
+							kind,
+							properties,
+							scopes,
+							span,
+						} = flow;
+
+						v.visit_ident(kind);
+						ns::visit_thin_vec(properties, |pd| v.visit_property_def(pd));
+						ns::visit_thin_vec(scopes, |expr| v.visit_expr(expr));
+						v.visit_span(span);
+					});
+				}
 			},
-			ItemKind::Body(Body { ty }) => v.visit_ty(ty),
-			ItemKind::Enum(Enum { variants }) => {
+			ItemKind::Body(Body { ty }) | ItemKind::TypeAlias(TypeAlias { ty }) => v.visit_ty(ty),
+			ItemKind::Enum(Enum { ty, variants }) => {
+				if let Some(ty) = ty {
+					v.visit_ty(ty);
+				}
 				ns::visit_thin_vec(variants, |pd| v.visit_property_def(pd));
 			}
+			ItemKind::Union(Union {
+				discriminator,
+				variants,
+			}) => {
+				v.visit_ident(discriminator);
+				ns::visit_thin_vec(variants, |fd| v.visit_field_def(fd));
+			}
 			ItemKind::Headers(Headers { headers }) => {
 				ns::visit_thin_vec(headers, |fd| v.visit_field_def(fd));
 			}
 			ItemKind::Meta(Metadata { fields }) => {
 				ns::visit_thin_vec(fields, |pd| v.visit_property_def(pd));
 			}
-			ItemKind::Model(Model { fields }) => {
+			ItemKind::Model(Model {
+				generics,
+				bases,
+				fields,
+			}) => {
+				ns::visit_thin_vec(generics, |ident| v.visit_ident(ident));
+				ns::visit_thin_vec(bases, |path| v.visit_path(path));
 				ns::visit_thin_vec(fields, |fd| v.visit_field_def(fd));
 			}
 			ItemKind::Params(Params { properties }) => {
@@ -226,6 +273,13 @@ pub mod noop {
 				ns::visit_thin_vec(items, |item| v.visit_item(item));
 				v.visit_ident(method);
 			}
+			ItemKind::Response(Response { items }) => {
+				ns::visit_thin_vec(items, |item| v.visit_item(item));
+			}
+			ItemKind::Use(use_) => match use_ {
+				Use::Headers | Use::Query | Use::Params => {}
+				Use::Response { code } => v.visit_expr(code),
+			},
 		}
 	}
 
@@ -1,6 +1,10 @@
 use crate::commands::Act;
 use dapic_expand::expand_ast;
-use dapic_generator_openapi::generate_openapi_spec;
+use dapic_generator_html::generate_html;
+use dapic_generator_markdown::generate_markdown;
+use dapic_generator_openapi::{Composition, Options, generate_openapi_spec};
+use dapic_generator_rust::{generate_rust_client, generate_rust_server};
+use dapic_generator_typescript::generate_typescript;
 use dapic_parser::Parser;
 use dapic_session::Session;
 use std::{error::Error, path::PathBuf};
@@ -11,6 +15,30 @@ pub(crate) struct Compile {
 
 	#[clap(long, short)]
 	output: PathBuf,
+
+	/// Format of the generated artefact
+	#[clap(long, value_enum, default_value_t = Target::Openapi)]
+	target: Target,
+
+	/// Copy the fields of base models instead of referencing them with `allOf`
+	#[clap(long)]
+	flatten: bool,
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum Target {
+	/// `OpenAPI` specification in JSON
+	Openapi,
+	/// Markdown API reference
+	Markdown,
+	/// Self-contained interactive HTML page
+	Html,
+	/// TypeScript declarations and `fetch` client
+	Typescript,
+	/// Rust module with `serde` types and a `reqwest` client
+	RustClient,
+	/// Rust module with `serde` types and an `axum` server trait
+	RustServer,
 }
 
 impl Act for Compile {
@@ -40,23 +68,48 @@ impl Act for Compile {
 			session.diagnostics.check_degraded_and_exit();
 
 			// Build HIR
+			let arena = dapic_hir::Arena::new();
 			let hir = session
 				.time("hir_creation")
-				.run(|| dapic_hir::compile_hir(&ast));
+				.run(|| dapic_hir::compile_hir(session, &arena, &ast));
 
 			session.diagnostics.check_degraded_and_exit();
 
 			// AST is not needed anymore
 			session.time("ast_drop").run(|| drop(ast));
 
-			// Generate OpenAPI artefact
-			let spec = session
-				.time("generate_openapi")
-				.run(|| generate_openapi_spec(&hir));
+			let out = match self.target {
+				Target::Openapi => {
+					let options = Options {
+						composition: if self.flatten {
+							Composition::Flatten
+						} else {
+							Composition::AllOf
+						},
+					};
+					let spec = session
+						.time("generate_openapi")
+						.run(|| generate_openapi_spec(&hir, &options, &session.diagnostics));
+
+					dapic_generator_openapi::serde_json::to_string_pretty(&spec)?
+				}
+				Target::Markdown => session
+					.time("generate_markdown")
+					.run(|| generate_markdown(&hir)),
+				Target::Html => session.time("generate_html").run(|| generate_html(&hir)),
+				Target::Typescript => session
+					.time("generate_typescript")
+					.run(|| generate_typescript(&hir)),
+				Target::RustClient => session
+					.time("generate_rust_client")
+					.run(|| generate_rust_client(&hir)),
+				Target::RustServer => session
+					.time("generate_rust_server")
+					.run(|| generate_rust_server(&hir, &session.diagnostics)),
+			};
 
 			// Print the output to file
-			let out = dapic_generator_openapi::serde_json::to_string_pretty(&spec).unwrap();
-			std::fs::write(&self.output, out).unwrap();
+			std::fs::write(&self.output, out)?;
 
 			Ok::<_, Box<dyn Error>>(())
 		})?;
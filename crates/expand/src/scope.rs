@@ -115,7 +115,7 @@ impl ScopeExpander<'_> {
 				import_stack: scope_data.file_path_stack[pos..].to_vec().iter().fold(
 					String::new(),
 					|mut s, p| {
-						write!(s, "{}", p.display()).unwrap();
+						write!(s, "{}", p.display()).expect("writing to a string never fails");
 						s
 					},
 				),
@@ -0,0 +1,27 @@
+//! Declarative API generators shared helpers
+//!
+//! Views of the HIR that several generators lay out the same way, e.g. the
+//! [`sections`] of documentation generators, and the plumbing of writing them.
+
+use std::fmt;
+
+mod section;
+
+pub use section::*;
+
+/// Runs `write` on a new `String` and gives it back.
+///
+/// Generators write with [`fmt::Write`] to share code with the [`fmt`]
+/// machinery, writing to a `String` never fails so the result is dropped.
+pub fn write_string(write: impl FnOnce(&mut String) -> fmt::Result) -> String {
+	let mut out = String::new();
+	let _ = write(&mut out);
+	out
+}
+
+/// Marks the error nodes of the HIR, e.g. [`Res::Err`](dapic_hir::types::Res::Err),
+/// as unreachable: generators only run once lowering reported no error.
+#[track_caller]
+pub fn invalid_hir() -> ! {
+	unreachable!("generators only run on valid HIR")
+}
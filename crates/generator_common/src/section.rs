@@ -0,0 +1,186 @@
+use dapic_hir::types::{DefId, Definition, Endpoint, Item, ItemKind, Root, docs};
+use dapic_session::Symbol;
+
+/// A `scope` with the endpoints and definitions declared directly in it, as
+/// laid out by documentation generators.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section<'tcx> {
+	/// Scopes the section is nested in, empty for the api root.
+	pub mod_path: Vec<Symbol>,
+	pub docs: Option<String>,
+	pub endpoints: Vec<Endpoint<'tcx>>,
+	pub defs: Vec<DefId>,
+}
+
+/// The root section followed by every scope in declaration order, sections
+/// without endpoints nor definitions are skipped.
+#[must_use]
+pub fn sections<'tcx>(root: &Root<'tcx>) -> Vec<Section<'tcx>> {
+	fn collect(
+		items: &[Item<'_>],
+		mod_path: &mut Vec<Symbol>,
+		docs: Option<String>,
+		sections: &mut Vec<Section<'_>>,
+	) {
+		sections.push(Section {
+			mod_path: mod_path.clone(),
+			docs,
+			endpoints: Vec::new(),
+			defs: Vec::new(),
+		});
+
+		for item in items {
+			if let ItemKind::Scope(scope) = &item.kind {
+				mod_path.push(item.ident.symbol);
+				collect(scope.items, mod_path, self::docs(item.attrs), sections);
+				mod_path.pop();
+			}
+		}
+	}
+
+	let mut sections = Vec::new();
+	collect(root.items(), &mut Vec::new(), None, &mut sections);
+
+	for endpoint in root.endpoints() {
+		let mod_path = endpoint
+			.mod_path
+			.iter()
+			.map(|ident| ident.symbol)
+			.collect::<Vec<_>>();
+		if let Some(section) = sections.iter_mut().find(|s| s.mod_path == mod_path) {
+			section.endpoints.push(endpoint);
+		}
+	}
+
+	for (def_id, def) in root.defs() {
+		if !def.is_concrete_type() {
+			continue;
+		}
+
+		let mod_path = def
+			.scopes()
+			.iter()
+			.map(|ident| ident.symbol)
+			.collect::<Vec<_>>();
+		if let Some(section) = sections.iter_mut().find(|s| s.mod_path == mod_path) {
+			section.defs.push(def_id);
+		}
+	}
+
+	sections.retain(|section| !section.endpoints.is_empty() || !section.defs.is_empty());
+	sections
+}
+
+/// Identifier of the section of a documentation page describing the value.
+pub trait Anchor {
+	#[must_use]
+	fn anchor(&self) -> String;
+}
+
+impl Anchor for Section<'_> {
+	/// e.g. `scope-pet.store` or `root`
+	fn anchor(&self) -> String {
+		if self.mod_path.is_empty() {
+			return "root".into();
+		}
+
+		let path = self.mod_path.iter().map(Symbol::as_str).collect::<Vec<_>>();
+		format!("scope-{}", path.join("."))
+	}
+}
+
+impl Anchor for Endpoint<'_> {
+	/// e.g. `op-getPetByPetId`
+	fn anchor(&self) -> String {
+		format!("op-{}", self.operation_id())
+	}
+}
+
+impl Anchor for Definition<'_> {
+	/// e.g. `def-pet.Status`
+	fn anchor(&self) -> String {
+		format!("def-{}", self.qualified_name())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{Anchor, sections};
+	use dapic_ast::visit_mut::MutVisitor;
+	use dapic_expand::NodeExpander;
+	use dapic_hir::{Arena, compile_hir, types::Endpoint};
+	use dapic_parser::Parser;
+	use dapic_session::Session;
+
+	#[test]
+	fn group_sections_by_scope() {
+		let mut session = Session::default();
+		let src = r#"
+			meta { name "Test" version "1" }
+
+			model Error {}
+			model Page<T> { items [T] }
+
+			scope empty {
+				auth Key { type "apiKey" in "header" name "key" }
+			}
+
+			scope pet {
+				##! Everything about pets
+				enum Status { Sold "sold" }
+
+				scope store {
+					path stores { verb GET { code 204 {} } }
+				}
+			}
+			"#;
+
+		let sections = session.enter_source_map_ctx(|session| {
+			let sf = session.source_map.load_anon(src.into());
+			let mut ast = Parser::from_source(&session.parse_sess(), &sf)
+				.parse_root()
+				.expect("source should parse");
+			NodeExpander::default().visit_root(&mut ast);
+
+			let arena = Arena::new();
+			let hir = compile_hir(session, &arena, &ast);
+			assert!(!session.diagnostics.has_errors(), "source should lower");
+
+			sections(&hir)
+				.into_iter()
+				.map(|section| {
+					let defs = section
+						.defs
+						.iter()
+						.map(|&def_id| hir.def(def_id).anchor())
+						.collect::<Vec<_>>();
+					let endpoints = section
+						.endpoints
+						.iter()
+						.map(Endpoint::anchor)
+						.collect::<Vec<_>>();
+					(section.anchor(), section.docs, defs, endpoints)
+				})
+				.collect::<Vec<_>>()
+		});
+
+		assert_eq!(
+			sections,
+			[
+				("root".into(), None, vec!["def-Error".into()], vec![]),
+				(
+					"scope-pet".into(),
+					Some("Everything about pets".into()),
+					vec!["def-pet.Status".into()],
+					vec![]
+				),
+				(
+					"scope-pet.store".into(),
+					None,
+					vec![],
+					vec!["op-getStores".into()]
+				),
+			]
+		);
+	}
+}
@@ -0,0 +1,32 @@
+// Filters the sidebar links with the search field, matching every word of the
+// query against endpoint methods and paths and definition names.
+(() => {
+	const search = document.getElementById("search");
+	const scopes = document.querySelectorAll("#sidebar li.scope");
+
+	search.addEventListener("input", () => {
+		const words = search.value.toLowerCase().split(/\s+/).filter(Boolean);
+
+		for (const scope of scopes) {
+			let matches = 0;
+			for (const entry of scope.querySelectorAll("li[data-search]")) {
+				const text = entry.dataset.search.toLowerCase();
+				const match = words.every((word) => text.includes(word));
+				entry.classList.toggle("hidden", !match);
+				if (match) matches += 1;
+			}
+
+			scope.classList.toggle("hidden", matches === 0);
+			// Show the results of collapsed scopes while searching
+			if (words.length > 0) scope.querySelector("details").open = true;
+		}
+	});
+
+	// Focus the search with `/`
+	document.addEventListener("keydown", (event) => {
+		if (event.key === "/" && document.activeElement !== search) {
+			event.preventDefault();
+			search.focus();
+		}
+	});
+})();
@@ -0,0 +1,172 @@
+//! Declarative API interactive documentation generator
+//!
+//! Entrypoint is [`generate_html`]. Writes a single self-contained page: the
+//! styles and the search script are inlined so that it works offline.
+
+use crate::{
+	page::{write_definition, write_endpoint},
+	text::{escape, write_docs},
+};
+use dapic_generator_common::{Anchor, Section, sections, write_string};
+use dapic_hir::types::{self as hir, docs};
+use dapic_session::Symbol;
+use std::fmt::{self, Write};
+
+mod page;
+mod text;
+
+const STYLE: &str = include_str!("assets/page.css");
+const SCRIPT: &str = include_str!("assets/search.js");
+
+/// Writes the documentation page of the api.
+#[must_use]
+pub fn generate_html(root: &hir::Root<'_>) -> String {
+	write_string(|out| write_page(out, root))
+}
+
+fn write_page(out: &mut String, root: &hir::Root<'_>) -> fmt::Result {
+	let meta = root.meta();
+	let title = meta
+		.name
+		.map_or_else(|| "API reference".into(), |name| escape(name.as_str()));
+	let sections = sections(root);
+
+	writeln!(out, "<!DOCTYPE html>")?;
+	writeln!(out, "<html lang=\"en\">")?;
+	writeln!(out, "<head>")?;
+	writeln!(out, "<meta charset=\"utf-8\">")?;
+	writeln!(
+		out,
+		"<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">"
+	)?;
+	writeln!(out, "<title>{title}</title>")?;
+	writeln!(out, "<style>\n{STYLE}</style>")?;
+	writeln!(out, "</head>")?;
+	writeln!(out, "<body>")?;
+
+	write_sidebar(out, root, &title, &sections)?;
+
+	writeln!(out, "<main>")?;
+	writeln!(out, "<header>")?;
+	writeln!(out, "<h1>{title}</h1>")?;
+	if let Some(version) = meta.version {
+		writeln!(
+			out,
+			"<p class=\"version\">Version {}</p>",
+			escape(version.as_str())
+		)?;
+	}
+	if let Some(docs) = docs(root.attrs) {
+		write_docs(out, &docs)?;
+	}
+	if let Some(description) = meta.description {
+		write_docs(out, description.as_str())?;
+	}
+	writeln!(out, "</header>")?;
+
+	for section in &sections {
+		write_section(out, root, section)?;
+	}
+
+	writeln!(out, "</main>")?;
+	writeln!(out, "<script>\n{SCRIPT}</script>")?;
+	writeln!(out, "</body>")?;
+	writeln!(out, "</html>")
+}
+
+/// Writes the navigation: the search field and a collapsible group per scope
+/// with links to its endpoints and definitions.
+fn write_sidebar(
+	out: &mut String,
+	root: &hir::Root<'_>,
+	title: &str,
+	sections: &[Section<'_>],
+) -> fmt::Result {
+	writeln!(out, "<nav id=\"sidebar\">")?;
+	writeln!(out, "<a class=\"title\" href=\"#\">{title}</a>")?;
+	writeln!(
+		out,
+		"<input id=\"search\" type=\"search\" placeholder=\"Search endpoints and models\" \
+		 autocomplete=\"off\">"
+	)?;
+	writeln!(out, "<ul class=\"scopes\">")?;
+
+	for section in sections {
+		let title = escape(&section_title(section));
+		writeln!(out, "<li class=\"scope\">")?;
+		writeln!(out, "<details open>")?;
+		writeln!(
+			out,
+			"<summary><a href=\"#{}\">{title}</a></summary>",
+			section.anchor()
+		)?;
+		writeln!(out, "<ul>")?;
+		for endpoint in &section.endpoints {
+			let method = escape(endpoint.verb.method.symbol.as_str());
+			let path = escape(&endpoint.path());
+			writeln!(
+				out,
+				"<li data-search=\"{method} {path}\"><a href=\"#{}\"><span class=\"method \
+				 method-{}\">{method}</span> {path}</a></li>",
+				endpoint.anchor(),
+				method.to_lowercase()
+			)?;
+		}
+		for &def_id in &section.defs {
+			let def = root.def(def_id);
+			let name = escape(def.item.ident.symbol.as_str());
+			writeln!(
+				out,
+				"<li data-search=\"{name}\"><a href=\"#{}\"><span class=\"kind\">{}</span> \
+				 {name}</a></li>",
+				def.anchor(),
+				def.kind
+			)?;
+		}
+		writeln!(out, "</ul>")?;
+		writeln!(out, "</details>")?;
+		writeln!(out, "</li>")?;
+	}
+
+	writeln!(out, "</ul>")?;
+	writeln!(out, "</nav>")
+}
+
+fn section_title(section: &Section<'_>) -> String {
+	if section.mod_path.is_empty() {
+		return "Root".into();
+	}
+
+	let path = section
+		.mod_path
+		.iter()
+		.map(Symbol::as_str)
+		.collect::<Vec<_>>();
+	path.join(".")
+}
+
+fn write_section(out: &mut String, root: &hir::Root<'_>, section: &Section<'_>) -> fmt::Result {
+	let anchor = section.anchor();
+
+	writeln!(out, "<section class=\"scope\" id=\"{anchor}\">")?;
+	writeln!(
+		out,
+		"<h2>{} <a class=\"anchor\" href=\"#{anchor}\">#</a></h2>",
+		escape(&section_title(section))
+	)?;
+	if let Some(docs) = &section.docs {
+		write_docs(out, docs)?;
+	}
+
+	for endpoint in &section.endpoints {
+		write_endpoint(out, root, endpoint)?;
+	}
+	for &def_id in &section.defs {
+		write_definition(out, root, def_id)?;
+	}
+
+	writeln!(out, "</section>")
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,282 @@
+use crate::text::{escape, literal_to_html, ty_to_html, write_docs};
+use dapic_generator_common::Anchor;
+use dapic_hir::types::{self as hir, DefId, DefKind, ItemKind, Res, TyKind, description, docs};
+use std::fmt::{self, Write};
+
+/// Writes the article of an endpoint: its documentation, parameters, request
+/// body and responses.
+pub(crate) fn write_endpoint(
+	out: &mut String,
+	root: &hir::Root<'_>,
+	endpoint: &hir::Endpoint<'_>,
+) -> fmt::Result {
+	let anchor = endpoint.anchor();
+	let method = escape(endpoint.verb.method.symbol.as_str());
+
+	writeln!(out, "<article class=\"endpoint\" id=\"{anchor}\">")?;
+	writeln!(
+		out,
+		"<h3><span class=\"method method-{}\">{method}</span> <code>{}</code> <a \
+		 class=\"anchor\" href=\"#{anchor}\">#</a></h3>",
+		method.to_lowercase(),
+		escape(&endpoint.path())
+	)?;
+	if let Some(docs) = docs(endpoint.item.attrs) {
+		write_docs(out, &docs)?;
+	}
+
+	write_fields(out, root, "Path parameters", endpoint.params())?;
+	write_fields(out, root, "Query parameters", endpoint.query())?;
+	write_fields(out, root, "Headers", endpoint.headers())?;
+
+	if endpoint.bodies().next().is_some() {
+		writeln!(out, "<h4>Request body</h4>")?;
+		write_bodies(out, root, endpoint.bodies())?;
+	}
+
+	if endpoint.responses().next().is_some() {
+		writeln!(out, "<h4>Responses</h4>")?;
+		for (item, code) in endpoint.responses() {
+			// Shared responses are documented on their definition
+			let attrs = code
+				.def
+				.map_or(item.attrs, |def_id| root.def(def_id).item.attrs);
+			let description = docs(attrs)
+				.or_else(|| code.status.reason().map(ToOwned::to_owned))
+				.unwrap_or_default();
+
+			writeln!(out, "<div class=\"response\">")?;
+			writeln!(
+				out,
+				"<h5><span class=\"status status-{}\">{}</span></h5>",
+				status_class(code.status),
+				code.status
+			)?;
+			write_docs(out, &description)?;
+			write_fields(out, root, "Headers", code.headers())?;
+			write_bodies(out, root, code.bodies())?;
+			writeln!(out, "</div>")?;
+		}
+	}
+
+	writeln!(out, "</article>")
+}
+
+/// Writes the article of a `model`, `enum`, `union` or type alias.
+pub(crate) fn write_definition(
+	out: &mut String,
+	root: &hir::Root<'_>,
+	def_id: DefId,
+) -> fmt::Result {
+	let def = root.def(def_id);
+	let anchor = def.anchor();
+
+	writeln!(out, "<article class=\"definition\" id=\"{anchor}\">")?;
+	writeln!(
+		out,
+		"<h3><span class=\"kind\">{}</span> <code>{}</code> <a class=\"anchor\" \
+		 href=\"#{anchor}\">#</a></h3>",
+		def.kind,
+		escape(def.item.ident.symbol.as_str())
+	)?;
+	if let Some(description) = description(def.item.attrs) {
+		write_docs(out, &description)?;
+	}
+
+	match &def.item.kind {
+		ItemKind::Model(model) => {
+			if !model.bases.is_empty() {
+				let bases = model
+					.bases
+					.iter()
+					.map(|base| format!("<code>{}</code>", ty_to_html(root, base)))
+					.collect::<Vec<_>>();
+				writeln!(out, "<p>Extends {}.</p>", bases.join(", "))?;
+			}
+			// The model itself is already expanded
+			write_field_tree(out, root, root.all_fields(model), &mut vec![def_id])?;
+		}
+		ItemKind::Enum(enum_) => {
+			writeln!(
+				out,
+				"<p>Enumeration of <code>{}</code> values.</p>",
+				enum_.ty.symbol()
+			)?;
+			writeln!(out, "<table>")?;
+			writeln!(
+				out,
+				"<tr><th>Variant</th><th>Value</th><th>Description</th></tr>"
+			)?;
+			for variant in enum_.variants {
+				writeln!(
+					out,
+					"<tr><td><code>{}</code></td><td><code>{}</code></td><td>{}</td></tr>",
+					escape(variant.ident.symbol.as_str()),
+					literal_to_html(variant.expr).unwrap_or_default(),
+					description(variant.attrs)
+						.as_deref()
+						.map(escape)
+						.unwrap_or_default()
+				)?;
+			}
+			writeln!(out, "</table>")?;
+		}
+		ItemKind::Union(union) => {
+			writeln!(
+				out,
+				"<p>One of the following models, told apart by their <code>{}</code> \
+				 field.</p>",
+				escape(union.discriminator.symbol.as_str())
+			)?;
+			writeln!(out, "<ul class=\"fields\">")?;
+			for variant in union.variants {
+				writeln!(
+					out,
+					"<li><details><summary><code class=\"name\">&quot;{}&quot;</code> <code \
+					 class=\"ty\">{}</code></summary>",
+					escape(variant.ident.symbol.as_str()),
+					ty_to_html(root, variant.ty)
+				)?;
+				if let Some((def_id, model)) = model_of(root, variant.ty) {
+					write_field_tree(out, root, root.all_fields(model), &mut vec![def_id])?;
+				}
+				writeln!(out, "</details></li>")?;
+			}
+			writeln!(out, "</ul>")?;
+		}
+		ItemKind::TypeAlias(alias) => {
+			writeln!(
+				out,
+				"<p>Alias of <code>{}</code>.</p>",
+				ty_to_html(root, alias.ty)
+			)?;
+		}
+		_ => unreachable!("only models, enums, unions and type aliases are documented"),
+	}
+
+	writeln!(out, "</article>")
+}
+
+/// Writes a titled tree of fields, nothing if there is none.
+fn write_fields<'tcx>(
+	out: &mut String,
+	root: &hir::Root<'_>,
+	title: &str,
+	fields: impl IntoIterator<Item = &'tcx hir::FieldDef<'tcx>>,
+) -> fmt::Result {
+	let mut fields = fields.into_iter().peekable();
+	if fields.peek().is_none() {
+		return Ok(());
+	}
+
+	writeln!(out, "<h4>{title}</h4>")?;
+	write_field_tree(out, root, fields, &mut Vec::new())
+}
+
+/// Writes fields as a list, the ones typed with a model can be expanded to
+/// show its fields. `visited` holds the models being expanded, recursive
+/// models are only linked.
+fn write_field_tree<'tcx>(
+	out: &mut String,
+	root: &hir::Root<'_>,
+	fields: impl IntoIterator<Item = &'tcx hir::FieldDef<'tcx>>,
+	visited: &mut Vec<DefId>,
+) -> fmt::Result {
+	writeln!(out, "<ul class=\"fields\">")?;
+
+	for field in fields {
+		let mut summary = format!(
+			"<code class=\"name\">{}</code> <code class=\"ty\">{}</code>",
+			escape(field.ident.symbol.as_str()),
+			ty_to_html(root, field.ty)
+		);
+		if field.required() {
+			summary.push_str(" <span class=\"required\">required</span>");
+		}
+		if let Some(default) = field.default.and_then(literal_to_html) {
+			write!(summary, " <span class=\"default\">= {default}</span>")?;
+		}
+		if let Some(description) = description(field.attrs) {
+			write!(
+				summary,
+				" <span class=\"docs\">{}</span>",
+				escape(&description)
+			)?;
+		}
+
+		match model_of(root, field.ty) {
+			Some((def_id, model)) if !visited.contains(&def_id) => {
+				writeln!(out, "<li><details><summary>{summary}</summary>")?;
+				visited.push(def_id);
+				write_field_tree(out, root, root.all_fields(model), visited)?;
+				visited.pop();
+				writeln!(out, "</details></li>")?;
+			}
+			_ => writeln!(out, "<li>{summary}</li>")?,
+		}
+	}
+
+	writeln!(out, "</ul>")
+}
+
+/// Writes the media types of the bodies, with the tree of their model.
+fn write_bodies<'tcx>(
+	out: &mut String,
+	root: &hir::Root<'_>,
+	bodies: impl Iterator<Item = (&'tcx hir::Item<'tcx>, &'tcx hir::Body<'tcx>)>,
+) -> fmt::Result {
+	for (item, body) in bodies {
+		let media_types = body
+			.media_types
+			.iter()
+			.map(|media_type| format!("<code>{}</code>", escape(media_type.mime())))
+			.collect::<Vec<_>>();
+
+		writeln!(out, "<div class=\"body\">")?;
+		writeln!(
+			out,
+			"<p>{} <code class=\"ty\">{}</code></p>",
+			media_types.join(", "),
+			ty_to_html(root, body.ty)
+		)?;
+		if let Some(docs) = docs(item.attrs) {
+			write_docs(out, &docs)?;
+		}
+		match &body.ty.kind {
+			TyKind::InlineModel(fields) => write_field_tree(out, root, *fields, &mut Vec::new())?,
+			_ => {
+				if let Some((def_id, model)) = model_of(root, body.ty) {
+					writeln!(out, "<details><summary>Fields</summary>")?;
+					write_field_tree(out, root, root.all_fields(model), &mut vec![def_id])?;
+					writeln!(out, "</details>")?;
+				}
+			}
+		}
+		writeln!(out, "</div>")?;
+	}
+
+	Ok(())
+}
+
+/// The model a type is made of, through arrays and nullable types.
+fn model_of<'tcx>(
+	root: &hir::Root<'tcx>,
+	ty: &hir::Ty<'tcx>,
+) -> Option<(DefId, &'tcx hir::Model<'tcx>)> {
+	match ty.kind {
+		TyKind::Path(_, Res::Def(DefKind::Model, def_id)) => match &root.def(def_id).item.kind {
+			ItemKind::Model(model) => Some((def_id, model)),
+			_ => None,
+		},
+		TyKind::Array(ty) | TyKind::Nullable(ty) => model_of(root, ty),
+		_ => None,
+	}
+}
+
+const fn status_class(status: hir::Status) -> &'static str {
+	match status {
+		hir::Status::Code(200..=299) | hir::Status::Range(2) => "success",
+		hir::Status::Code(400..=599) | hir::Status::Range(4 | 5) | hir::Status::Default => "error",
+		_ => "other",
+	}
+}
@@ -0,0 +1,98 @@
+use crate::{SCRIPT, STYLE, generate_html};
+use dapic_ast::visit_mut::MutVisitor;
+use dapic_expand::NodeExpander;
+use dapic_hir::{Arena, compile_hir};
+use dapic_parser::Parser;
+use dapic_session::Session;
+
+/// Lowers `src` and gives its page, without the inlined styles and script.
+fn generate(src: &str) -> String {
+	let mut session = Session::default();
+
+	session.enter_source_map_ctx(|session| {
+		let sf = session.source_map.load_anon(src.into());
+		let mut ast = Parser::from_source(&session.parse_sess(), &sf)
+			.parse_root()
+			.expect("source should parse");
+		NodeExpander::default().visit_root(&mut ast);
+
+		let arena = Arena::new();
+		let hir = compile_hir(session, &arena, &ast);
+		assert!(!session.diagnostics.has_errors(), "source should lower");
+
+		generate_html(&hir).replace(STYLE, "").replace(SCRIPT, "")
+	})
+}
+
+const PETSTORE: &str = r#"
+##! A sample pet store
+meta {
+	name "Petstore"
+	version "1.0.0"
+	description "Adopt & buy <pets>"
+}
+
+## A failure
+model Error {
+	message string
+	code? int32
+}
+
+type Tags = [string] "Labels of a pet"
+
+scope pet {
+	##! Everything about pets
+
+	model Base {
+		id int64
+		kind string
+	}
+
+	## Status of a pet
+	enum Status {
+		## Ready to be adopted
+		Available "available"
+		Sold "sold"
+	}
+
+	model Pet : Base {
+		## Name of the pet
+		name string |@maxLength: 64|
+		nickname? string "Name it answers to"
+		status? Status = "available"
+		tags Tags = []
+		owner? { name string phone? string }
+		ranks {[Status]: int}
+	}
+
+	union Animal on kind { dog Pet cat Pet }
+
+	params PetId { petId int64 }
+	response NotFound { body Error }
+
+	path pets {
+		## Lists the pets
+		verb GET {
+			query { limit? int = 20 }
+			code 200 { body [Pet] }
+			code ~5xx { body Error }
+		}
+
+		path {petId} {
+			params PetId;
+
+			verb PUT {
+				headers { X-Request-Id uuid "Traces the request" }
+				body Pet
+				code 200 { body Animal }
+				code 404 NotFound;
+			}
+		}
+	}
+}
+"#;
+
+#[test]
+fn generate_page() {
+	insta::assert_snapshot!(generate(PETSTORE));
+}
@@ -0,0 +1,94 @@
+use dapic_generator_common::{Anchor, invalid_hir};
+use dapic_hir::types::{self as hir, ExprKind, Res, TyKind};
+use dapic_lexer::rich::LiteralKind;
+use std::fmt::{self, Write};
+
+/// Escapes text for element contents and quoted attribute values.
+pub(crate) fn escape(text: &str) -> String {
+	let mut escaped = String::with_capacity(text.len());
+	for char in text.chars() {
+		match char {
+			'&' => escaped.push_str("&amp;"),
+			'<' => escaped.push_str("&lt;"),
+			'>' => escaped.push_str("&gt;"),
+			'"' => escaped.push_str("&quot;"),
+			'\'' => escaped.push_str("&#39;"),
+			_ => escaped.push(char),
+		}
+	}
+	escaped
+}
+
+/// Writes doc comments as paragraphs, split on blank lines. The text is kept
+/// as written, line breaks included.
+pub(crate) fn write_docs(out: &mut String, docs: &str) -> fmt::Result {
+	for paragraph in docs.split("\n\n").filter(|p| !p.trim().is_empty()) {
+		let lines = paragraph.lines().map(escape).collect::<Vec<_>>();
+		writeln!(out, "<p class=\"docs\">{}</p>", lines.join("<br>"))?;
+	}
+	Ok(())
+}
+
+/// A type in the syntax of the language, definitions link to their section.
+pub(crate) fn ty_to_html(root: &hir::Root<'_>, ty: &hir::Ty<'_>) -> String {
+	match ty.kind {
+		TyKind::Path(_, Res::PrimTy(prim)) => prim.symbol().to_string(),
+		TyKind::Path(_, Res::Def(_, def_id)) => {
+			let def = root.def(def_id);
+			format!(
+				"<a href=\"#{}\">{}</a>",
+				def.anchor(),
+				escape(def.item.ident.symbol.as_str())
+			)
+		}
+		TyKind::Path(_, Res::TyParam) => unreachable!("generic models are not documented"),
+		TyKind::Path(_, Res::Err) => invalid_hir(),
+		TyKind::Array(ty) => format!("[{}]", ty_to_html(root, ty)),
+		TyKind::Tuple(tys) => format!("({})", tys_to_html(root, tys, ", ")),
+		TyKind::InlineModel(fields) => {
+			let fields = fields
+				.iter()
+				.map(|field| {
+					let name = escape(field.ident.symbol.as_str());
+					format!("{name} {}", ty_to_html(root, field.ty))
+				})
+				.collect::<Vec<_>>();
+			format!("{{ {} }}", fields.join(", "))
+		}
+		TyKind::Map(key, value) => format!(
+			"{{[{}]: {}}}",
+			ty_to_html(root, key),
+			ty_to_html(root, value)
+		),
+		TyKind::Nullable(ty) => format!("{}?", ty_to_html(root, ty)),
+		TyKind::Union(tys) => tys_to_html(root, tys, " | "),
+	}
+}
+
+fn tys_to_html(root: &hir::Root<'_>, tys: &[hir::Ty<'_>], separator: &str) -> String {
+	tys.iter()
+		.map(|ty| ty_to_html(root, ty))
+		.collect::<Vec<_>>()
+		.join(separator)
+}
+
+/// A literal value as written in the source, `None` for other expressions.
+pub(crate) fn literal_to_html(expr: &hir::Expr<'_>) -> Option<String> {
+	match expr.kind {
+		ExprKind::Literal(LiteralKind::Str, value) => Some(escape(&format!("\"{value}\""))),
+		ExprKind::Literal(_, value) => Some(escape(value.as_str())),
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::escape;
+
+	#[test]
+	fn escape_text() {
+		assert_eq!(escape("<script>"), "&lt;script&gt;");
+		assert_eq!(escape("\"a\" & 'b'"), "&quot;a&quot; &amp; &#39;b&#39;");
+		assert_eq!(escape("/pet/{petId}"), "/pet/{petId}");
+	}
+}
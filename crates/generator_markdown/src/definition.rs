@@ -0,0 +1,68 @@
+use crate::text::{literal_to_markdown, ty_to_markdown, write_fields, write_header, write_row};
+use dapic_generator_common::Anchor;
+use dapic_hir::types::{self as hir, ItemKind, description};
+use std::fmt::{self, Write};
+
+/// Writes the section of a `model`, `enum`, `union` or type alias.
+pub(crate) fn write_definition(
+	out: &mut String,
+	root: &hir::Root<'_>,
+	def: &hir::Definition<'_>,
+) -> fmt::Result {
+	writeln!(out, "<a id=\"{}\"></a>\n", def.anchor())?;
+	writeln!(out, "### `{}`\n", def.item.ident)?;
+	if let Some(description) = description(def.item.attrs) {
+		writeln!(out, "{description}\n")?;
+	}
+
+	match &def.item.kind {
+		ItemKind::Model(model) => {
+			if !model.bases.is_empty() {
+				let bases = model
+					.bases
+					.iter()
+					.map(|base| ty_to_markdown(root, base))
+					.collect::<Vec<_>>();
+				writeln!(out, "Extends {}.\n", bases.join(", "))?;
+			}
+			write_fields(out, root, "Fields", root.all_fields(model))
+		}
+		ItemKind::Enum(enum_) => {
+			writeln!(out, "Enumeration of `{}` values.\n", enum_.ty.symbol())?;
+			write_header(out, &["Variant", "Value", "Description"])?;
+			for variant in enum_.variants {
+				write_row(
+					out,
+					&[
+						format!("`{}`", variant.ident),
+						literal_to_markdown(variant.expr).unwrap_or_default(),
+						description(variant.attrs).unwrap_or_default(),
+					],
+				)?;
+			}
+			writeln!(out)
+		}
+		ItemKind::Union(union) => {
+			writeln!(
+				out,
+				"One of the following models, told apart by their `{}` field.\n",
+				union.discriminator
+			)?;
+			write_header(out, &[&format!("`{}`", union.discriminator), "Model"])?;
+			for variant in union.variants {
+				write_row(
+					out,
+					&[
+						format!("`\"{}\"`", variant.ident),
+						ty_to_markdown(root, variant.ty),
+					],
+				)?;
+			}
+			writeln!(out)
+		}
+		ItemKind::TypeAlias(alias) => {
+			writeln!(out, "Alias of {}.\n", ty_to_markdown(root, alias.ty))
+		}
+		_ => unreachable!("only models, enums, unions and type aliases are documented"),
+	}
+}
@@ -0,0 +1,95 @@
+use crate::text::{ty_to_markdown, write_fields, write_header, write_row};
+use dapic_generator_common::Anchor;
+use dapic_hir::types::{self as hir, docs};
+use std::fmt::{self, Write};
+
+/// Writes the section of an endpoint: its documentation, parameters, request
+/// body and responses.
+pub(crate) fn write_endpoint(
+	out: &mut String,
+	root: &hir::Root<'_>,
+	endpoint: &hir::Endpoint<'_>,
+) -> fmt::Result {
+	writeln!(out, "<a id=\"{}\"></a>\n", endpoint.anchor())?;
+	writeln!(out, "### `{} {}`\n", endpoint.verb.method, endpoint.path())?;
+	if let Some(docs) = docs(endpoint.item.attrs) {
+		writeln!(out, "{docs}\n")?;
+	}
+
+	write_fields(out, root, "Path parameters", endpoint.params())?;
+	write_fields(out, root, "Query parameters", endpoint.query())?;
+	write_fields(out, root, "Headers", endpoint.headers())?;
+
+	if endpoint.bodies().next().is_some() {
+		writeln!(out, "**Request body**\n")?;
+		write_header(out, &["Media type", "Type", "Description"])?;
+		for (item, body) in endpoint.bodies() {
+			for media_type in body.media_types {
+				write_row(
+					out,
+					&[
+						format!("`{}`", media_type.mime()),
+						ty_to_markdown(root, body.ty),
+						docs(item.attrs).unwrap_or_default(),
+					],
+				)?;
+			}
+		}
+		writeln!(out)?;
+	}
+
+	if endpoint.responses().next().is_some() {
+		writeln!(out, "**Responses**\n")?;
+		write_header(out, &["Status", "Description", "Media type", "Type"])?;
+		for (item, code) in endpoint.responses() {
+			write_response(out, root, item, code)?;
+		}
+		writeln!(out)?;
+	}
+
+	Ok(())
+}
+
+/// Writes a row per media type of the response, or a single one without a
+/// body.
+fn write_response(
+	out: &mut String,
+	root: &hir::Root<'_>,
+	item: &hir::Item<'_>,
+	code: &hir::StatusCode<'_>,
+) -> fmt::Result {
+	// Shared responses are documented on their definition
+	let attrs = code
+		.def
+		.map_or(item.attrs, |def_id| root.def(def_id).item.attrs);
+	let description = docs(attrs)
+		.or_else(|| code.status.reason().map(ToOwned::to_owned))
+		.unwrap_or_default();
+	let status = format!("`{}`", code.status);
+
+	let mut rows = code
+		.bodies()
+		.flat_map(|(_, body)| {
+			body.media_types
+				.iter()
+				.map(move |media_type| (media_type, body))
+		})
+		.peekable();
+	if rows.peek().is_none() {
+		return write_row(out, &[status, description, String::new(), String::new()]);
+	}
+
+	for (media_type, body) in rows {
+		write_row(
+			out,
+			&[
+				status.clone(),
+				description.clone(),
+				format!("`{}`", media_type.mime()),
+				ty_to_markdown(root, body.ty),
+			],
+		)?;
+	}
+
+	Ok(())
+}
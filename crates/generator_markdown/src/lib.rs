@@ -0,0 +1,115 @@
+//! Declarative API Markdown reference generator
+//!
+//! Entrypoint is [`generate_markdown`]. Writes a single document with a section
+//! per `scope`, each listing the endpoints and definitions declared in it.
+
+use crate::{definition::write_definition, endpoint::write_endpoint, text::def_link};
+use dapic_generator_common::{Anchor, Section, sections, write_string};
+use dapic_hir::types::{self as hir, docs};
+use dapic_session::Symbol;
+use std::fmt::{self, Write};
+
+mod definition;
+mod endpoint;
+mod text;
+
+/// Writes the reference of the api. Doc comments are copied verbatim, they
+/// are expected to be Markdown already.
+#[must_use]
+pub fn generate_markdown(root: &hir::Root<'_>) -> String {
+	write_string(|out| write_root(out, root))
+}
+
+fn write_root(out: &mut String, root: &hir::Root<'_>) -> fmt::Result {
+	let meta = root.meta();
+
+	match meta.name {
+		Some(name) => writeln!(out, "# {name}\n")?,
+		None => writeln!(out, "# API reference\n")?,
+	}
+	if let Some(version) = meta.version {
+		writeln!(out, "Version `{version}`\n")?;
+	}
+	if let Some(docs) = docs(root.attrs) {
+		writeln!(out, "{docs}\n")?;
+	}
+	if let Some(description) = meta.description {
+		writeln!(out, "{description}\n")?;
+	}
+
+	let sections = sections(root);
+
+	writeln!(out, "## Contents\n")?;
+	for section in &sections {
+		let indent = "  ".repeat(section.mod_path.len().saturating_sub(1));
+		writeln!(
+			out,
+			"{indent}- [{}](#{})",
+			section_title(section),
+			section.anchor()
+		)?;
+	}
+	writeln!(out)?;
+
+	for section in &sections {
+		write_section(out, root, section)?;
+	}
+
+	Ok(())
+}
+
+fn section_title(section: &Section<'_>) -> String {
+	if section.mod_path.is_empty() {
+		return "Root".into();
+	}
+
+	let path = section
+		.mod_path
+		.iter()
+		.map(Symbol::as_str)
+		.collect::<Vec<_>>()
+		.join(".");
+	format!("`{path}`")
+}
+
+fn write_section(out: &mut String, root: &hir::Root<'_>, section: &Section<'_>) -> fmt::Result {
+	writeln!(out, "<a id=\"{}\"></a>\n", section.anchor())?;
+	writeln!(out, "## {}\n", section_title(section))?;
+	if let Some(docs) = &section.docs {
+		writeln!(out, "{docs}\n")?;
+	}
+
+	// Table of contents of the scope
+	if !section.endpoints.is_empty() {
+		writeln!(out, "**Endpoints**\n")?;
+		for endpoint in &section.endpoints {
+			writeln!(
+				out,
+				"- [`{} {}`](#{})",
+				endpoint.verb.method,
+				endpoint.path(),
+				endpoint.anchor()
+			)?;
+		}
+		writeln!(out)?;
+	}
+	if !section.defs.is_empty() {
+		writeln!(out, "**Definitions**\n")?;
+		for &def_id in &section.defs {
+			writeln!(out, "- {}", def_link(root.def(def_id)))?;
+		}
+		writeln!(out)?;
+	}
+
+	for endpoint in &section.endpoints {
+		write_endpoint(out, root, endpoint)?;
+	}
+	for &def_id in &section.defs {
+		write_definition(out, root, root.def(def_id))?;
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,124 @@
+use dapic_generator_common::{Anchor, invalid_hir};
+use dapic_hir::types::{self as hir, ExprKind, Res, TyKind, description};
+use dapic_lexer::rich::LiteralKind;
+use std::fmt::{self, Write};
+
+pub(crate) fn def_link(def: &hir::Definition<'_>) -> String {
+	format!("[`{}`](#{})", def.item.ident, def.anchor())
+}
+
+/// A type as a sentence fragment, definitions link to their section.
+pub(crate) fn ty_to_markdown(root: &hir::Root<'_>, ty: &hir::Ty<'_>) -> String {
+	match ty.kind {
+		TyKind::Path(_, Res::PrimTy(prim)) => format!("`{}`", prim.symbol()),
+		TyKind::Path(_, Res::Def(_, def_id)) => def_link(root.def(def_id)),
+		TyKind::Path(_, Res::TyParam) => unreachable!("generic models are not documented"),
+		TyKind::Path(_, Res::Err) => invalid_hir(),
+		TyKind::Array(ty) => format!("array of {}", ty_to_markdown(root, ty)),
+		TyKind::Tuple([]) => "`()`".into(),
+		TyKind::Tuple(tys) => format!("tuple of ({})", tys_to_markdown(root, tys, ", ")),
+		TyKind::InlineModel(fields) => {
+			let fields = fields
+				.iter()
+				.map(|field| format!("`{}` {}", field.ident, ty_to_markdown(root, field.ty)))
+				.collect::<Vec<_>>();
+			format!("object with {}", fields.join(", "))
+		}
+		TyKind::Map(key, value) => format!(
+			"map of {} to {}",
+			ty_to_markdown(root, key),
+			ty_to_markdown(root, value)
+		),
+		TyKind::Nullable(ty) => format!("{} or `null`", ty_to_markdown(root, ty)),
+		TyKind::Union(tys) => tys_to_markdown(root, tys, " or "),
+	}
+}
+
+fn tys_to_markdown(root: &hir::Root<'_>, tys: &[hir::Ty<'_>], separator: &str) -> String {
+	tys.iter()
+		.map(|ty| ty_to_markdown(root, ty))
+		.collect::<Vec<_>>()
+		.join(separator)
+}
+
+/// A literal value as inline code, `None` for other expressions.
+pub(crate) fn literal_to_markdown(expr: &hir::Expr<'_>) -> Option<String> {
+	match expr.kind {
+		ExprKind::Literal(LiteralKind::Str, value) => Some(format!("`\"{value}\"`")),
+		ExprKind::Literal(_, value) => Some(format!("`{value}`")),
+		_ => None,
+	}
+}
+
+/// Makes text fit in a table cell, pipes would end the cell and line breaks
+/// the row.
+pub(crate) fn escape_cell(text: &str) -> String {
+	text.replace('|', "\\|")
+		.lines()
+		.collect::<Vec<_>>()
+		.join("<br>")
+}
+
+/// Writes a table row, cells are escaped.
+pub(crate) fn write_row<S: AsRef<str>>(out: &mut String, cells: &[S]) -> fmt::Result {
+	for cell in cells {
+		write!(out, "| {} ", escape_cell(cell.as_ref()))?;
+	}
+	writeln!(out, "|")
+}
+
+pub(crate) fn write_header(out: &mut String, columns: &[&str]) -> fmt::Result {
+	write_row(out, columns)?;
+	write_row(out, &vec!["---"; columns.len()])
+}
+
+/// Writes a titled table of fields, nothing if there is none.
+pub(crate) fn write_fields<'tcx>(
+	out: &mut String,
+	root: &hir::Root<'_>,
+	title: &str,
+	fields: impl IntoIterator<Item = &'tcx hir::FieldDef<'tcx>>,
+) -> fmt::Result {
+	let mut fields = fields.into_iter().peekable();
+	if fields.peek().is_none() {
+		return Ok(());
+	}
+
+	writeln!(out, "**{title}**\n")?;
+	write_header(out, &["Name", "Type", "Required", "Description"])?;
+	for field in fields {
+		let mut description = description(field.attrs).unwrap_or_default();
+		if let Some(default) = field.default.and_then(literal_to_markdown) {
+			if !description.is_empty() {
+				description.push('\n');
+			}
+			write!(description, "Default: {default}")?;
+		}
+
+		write_row(
+			out,
+			&[
+				format!("`{}`", field.ident),
+				ty_to_markdown(root, field.ty),
+				if field.required() { "yes" } else { "no" }.into(),
+				description,
+			],
+		)?;
+	}
+	writeln!(out)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::escape_cell;
+
+	#[test]
+	fn escape_cells() {
+		assert_eq!(escape_cell("a | b"), "a \\| b");
+		assert_eq!(
+			escape_cell("first line\nsecond line"),
+			"first line<br>second line"
+		);
+		assert_eq!(escape_cell("`int`"), "`int`");
+	}
+}
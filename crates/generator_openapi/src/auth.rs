@@ -0,0 +1,112 @@
+use dapic_hir::types::{self as hir, AuthScheme, DefKind, docs};
+use indexmap::IndexMap;
+use openapiv3::{APIKeyLocation, OAuth2Flows, ReferenceOr, SecurityRequirement, SecurityScheme};
+use serde_json::json;
+
+/// Builds a security scheme for every `auth` definition of the api.
+pub(crate) fn defs_to_security_schemes(
+	root: &hir::Root<'_>,
+) -> IndexMap<String, ReferenceOr<SecurityScheme>> {
+	root.defs()
+		.filter(|(_, def)| def.kind == DefKind::Auth)
+		.filter_map(|(_, def)| {
+			let hir::ItemKind::Auth(hir::Auth::Define(scheme)) = &def.item.kind else {
+				unreachable!("auth definitions are always `auth` items")
+			};
+			let scheme = auth_to_security_scheme(scheme, docs(def.item.attrs))?;
+
+			Some((def.qualified_name(), ReferenceOr::Item(scheme)))
+		})
+		.collect()
+}
+
+/// Every scheme used by the endpoint is required, they are put in a single
+/// requirement.
+pub(crate) fn endpoint_security(
+	root: &hir::Root<'_>,
+	endpoint: &hir::Endpoint<'_>,
+) -> Option<Vec<SecurityRequirement>> {
+	let requirement: SecurityRequirement = endpoint
+		.auths()
+		.map(|(def_id, scopes)| {
+			(
+				root.def(def_id).qualified_name(),
+				scopes.iter().map(ToString::to_string).collect(),
+			)
+		})
+		.collect();
+
+	(!requirement.is_empty()).then(|| vec![requirement])
+}
+
+fn auth_to_security_scheme(
+	scheme: &AuthScheme<'_>,
+	description: Option<String>,
+) -> Option<SecurityScheme> {
+	Some(match scheme {
+		AuthScheme::ApiKey { name, location } => SecurityScheme::APIKey {
+			location: match location {
+				hir::ApiKeyLocation::Header => APIKeyLocation::Header,
+				hir::ApiKeyLocation::Query => APIKeyLocation::Query,
+				hir::ApiKeyLocation::Cookie => APIKeyLocation::Cookie,
+			},
+			name: name.to_string(),
+			description,
+			extensions: IndexMap::default(),
+		},
+		AuthScheme::Http {
+			scheme,
+			bearer_format,
+		} => SecurityScheme::HTTP {
+			scheme: scheme.to_string(),
+			bearer_format: bearer_format.map(|format| format.to_string()),
+			description,
+			extensions: IndexMap::default(),
+		},
+		AuthScheme::OAuth2 { flows } => SecurityScheme::OAuth2 {
+			flows: oauth2_flows(flows),
+			description,
+			extensions: IndexMap::default(),
+		},
+		AuthScheme::OpenIdConnect { url } => SecurityScheme::OpenIDConnect {
+			open_id_connect_url: url.to_string(),
+			description,
+			extensions: IndexMap::default(),
+		},
+		AuthScheme::Err => return None,
+	})
+}
+
+fn oauth2_flows(flows: &[hir::AuthFlow<'_>]) -> OAuth2Flows {
+	// Flow fields are private in `openapiv3`, they can only be deserialized
+	let flows = flows
+		.iter()
+		.map(|flow| {
+			let scopes = flow
+				.scopes
+				.iter()
+				.map(|scope| {
+					(
+						scope.name.to_string(),
+						docs(scope.attrs).unwrap_or_default(),
+					)
+				})
+				.collect::<IndexMap<_, _>>();
+
+			let mut value = json!({ "scopes": scopes });
+			for (key, url) in [
+				("authorizationUrl", flow.authorization_url),
+				("tokenUrl", flow.token_url),
+				("refreshUrl", flow.refresh_url),
+			] {
+				if let Some(url) = url {
+					value[key] = url.as_str().into();
+				}
+			}
+
+			(flow.kind.as_str().to_owned(), value)
+		})
+		.collect::<serde_json::Map<_, _>>();
+
+	serde_json::from_value(flows.into()).expect("flows without their required urls are not lowered")
+}
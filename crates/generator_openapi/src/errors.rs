@@ -0,0 +1,10 @@
+use dapic_macros::IntoDiagnostic;
+use dapic_session::Ident;
+
+#[derive(Debug, IntoDiagnostic)]
+#[severity(Warning)]
+#[message("the custom verb `{verb}` cannot be described in OpenAPI")]
+pub struct UndescribableVerb {
+	#[label("path items only hold the methods of the HTTP spec, this operation is left out")]
+	pub verb: Ident,
+}
@@ -0,0 +1,74 @@
+use dapic_hir::types as hir;
+use indexmap::IndexMap;
+use openapiv3::{Contact, Info, License, Server};
+
+/// The name and the version are required during lowering, they are only empty
+/// if the api did not lower.
+pub(crate) fn meta_to_info(meta: &hir::Meta) -> Info {
+	Info {
+		title: meta.name.map(|name| name.to_string()).unwrap_or_default(),
+		description: meta.description.map(|description| description.to_string()),
+		contact: meta.contact.map(|contact| parse_contact(contact.as_str())),
+		license: meta.license.map(|license| License {
+			name: license.to_string(),
+			url: None,
+			extensions: IndexMap::default(),
+		}),
+		version: meta
+			.version
+			.map(|version| version.to_string())
+			.unwrap_or_default(),
+		..Default::default()
+	}
+}
+
+pub(crate) fn meta_to_servers(meta: &hir::Meta) -> Vec<Server> {
+	meta.urls
+		.iter()
+		.map(|url| Server {
+			url: url.to_string(),
+			..Default::default()
+		})
+		.collect()
+}
+
+/// Splits a contact written as `Name <email>`, a lone url or a lone email.
+fn parse_contact(contact: &str) -> Contact {
+	let contact = contact.trim();
+	let mut parsed = Contact::default();
+
+	if let Some((name, rest)) = contact.split_once('<')
+		&& let Some(email) = rest.strip_suffix('>')
+	{
+		let name = name.trim();
+		parsed.name = (!name.is_empty()).then(|| name.to_owned());
+		parsed.email = Some(email.trim().to_owned());
+	} else if contact.starts_with("http://") || contact.starts_with("https://") {
+		parsed.url = Some(contact.to_owned());
+	} else if contact.contains('@') {
+		parsed.email = Some(contact.to_owned());
+	} else {
+		parsed.name = Some(contact.to_owned());
+	}
+
+	parsed
+}
+
+#[cfg(test)]
+mod tests {
+	use super::parse_contact;
+
+	#[test]
+	fn parse_contacts() {
+		let contact = parse_contact("Jane Doe <jane@example.com>");
+		assert_eq!(contact.name.as_deref(), Some("Jane Doe"));
+		assert_eq!(contact.email.as_deref(), Some("jane@example.com"));
+
+		let contact = parse_contact("https://example.com/support");
+		assert_eq!(contact.url.as_deref(), Some("https://example.com/support"));
+
+		let contact = parse_contact("jane@example.com");
+		assert_eq!(contact.email.as_deref(), Some("jane@example.com"));
+		assert_eq!(contact.name, None);
+	}
+}
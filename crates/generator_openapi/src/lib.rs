@@ -1,56 +1,58 @@
-use dapic_hir::types as hir;
-use indexmap::IndexMap;
-use openapiv3::{
-	Components, Info, IntegerType, OpenAPI, Paths, ReferenceOr, Schema, SchemaData, SchemaKind,
-	Type,
+use crate::{
+	auth::defs_to_security_schemes,
+	info::{meta_to_info, meta_to_servers},
+	paths::{defs_to_parameters, defs_to_responses, endpoints_to_paths},
+	schema::defs_to_schemas,
 };
+use dapic_hir::types as hir;
+use dapic_session::DiagnosticsHandler;
+use openapiv3::{Components, OpenAPI};
+
+mod auth;
+mod errors;
+mod info;
+mod paths;
+mod schema;
 
 // Public exports
 pub use serde_json;
 
-fn models_to_schemas(models: &[&hir::Item]) -> IndexMap<String, ReferenceOr<Schema>> {
-	let mut map = IndexMap::new();
-
-	map.insert(
-		String::from("hello"),
-		ReferenceOr::Item(Schema {
-			schema_kind: SchemaKind::Type(Type::Integer(IntegerType::default())),
-			schema_data: SchemaData::default(),
-		}),
-	);
-
-	// for model in models {}
+/// Configuration of the generated spec.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+	pub composition: Composition,
+}
 
-	map
+/// How models extending other models are described.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Composition {
+	/// `allOf` with a reference to every base, followed by the model's own fields
+	#[default]
+	AllOf,
+	/// A single object with the inherited fields copied in
+	Flatten,
 }
 
 #[must_use]
-pub fn generate_openapi_spec(crate_: &hir::Root) -> OpenAPI {
-	// let meta = Option::<hir::Meta>::None;
-	let mut models = Vec::<&hir::Item>::new();
-
-	for item in crate_.items() {
-		match item.kind {
-			hir::ItemKind::Model => models.push(item),
-		}
-	}
-
-	let schemas = models_to_schemas(&models);
-
+pub fn generate_openapi_spec(
+	crate_: &hir::Root<'_>,
+	options: &Options,
+	diagnostics: &DiagnosticsHandler,
+) -> OpenAPI {
+	let meta = crate_.meta();
 	let components = Components {
-		schemas,
+		schemas: defs_to_schemas(crate_, options),
+		parameters: defs_to_parameters(crate_),
+		responses: defs_to_responses(crate_),
+		security_schemes: defs_to_security_schemes(crate_),
 		..Default::default()
 	};
 
 	OpenAPI {
 		openapi: "3".into(),
-		info: Info {
-			title: "my_spec".into(),
-			version: "0.0.0".into(),
-			..Default::default()
-		},
-		servers: vec![],
-		paths: Paths::default(),
+		info: meta_to_info(&meta),
+		servers: meta_to_servers(&meta),
+		paths: endpoints_to_paths(crate_, diagnostics),
 		components: Some(components),
 		security: None,
 		tags: vec![],
@@ -58,3 +60,6 @@ pub fn generate_openapi_spec(crate_: &hir::Root) -> OpenAPI {
 		..Default::default()
 	}
 }
+
+#[cfg(test)]
+mod tests;
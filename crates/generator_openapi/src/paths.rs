@@ -0,0 +1,305 @@
+use crate::{
+	auth::endpoint_security,
+	errors::UndescribableVerb,
+	schema::{body_to_schema, expr_to_json, field_to_schema},
+};
+use dapic_generator_common::invalid_hir;
+use dapic_hir::types::{self as hir, DefKind, description, docs};
+use dapic_session::{DiagnosticsHandler, symbols::remarkable};
+use indexmap::IndexMap;
+use openapiv3::{
+	Header, HeaderStyle, MediaType, Operation, Parameter, ParameterData, ParameterSchemaOrContent,
+	PathItem, PathStyle, Paths, QueryStyle, ReferenceOr, RequestBody, Response, Responses,
+	StatusCode,
+};
+
+/// Flattens every `path` and `verb` item into `OpenAPI` path templates, custom
+/// verbs are reported and left out.
+pub(crate) fn endpoints_to_paths(root: &hir::Root<'_>, diagnostics: &DiagnosticsHandler) -> Paths {
+	let mut paths = Paths::default();
+
+	for endpoint in root.endpoints() {
+		let Some(slot) = operation_slot(endpoint.verb.method) else {
+			diagnostics.emit(UndescribableVerb {
+				verb: endpoint.verb.method,
+			});
+			continue;
+		};
+
+		let ReferenceOr::Item(path_item) = paths
+			.paths
+			.entry(endpoint.path())
+			.or_insert_with(|| ReferenceOr::Item(PathItem::default()))
+		else {
+			unreachable!("path items are never references")
+		};
+
+		*slot(path_item) = Some(endpoint_to_operation(root, &endpoint));
+	}
+
+	paths
+}
+
+/// Field of the path item holding the operations of `method`, if it is one of
+/// the HTTP spec.
+fn operation_slot(
+	method: dapic_session::Ident,
+) -> Option<fn(&mut PathItem) -> &mut Option<Operation>> {
+	Some(match method.symbol {
+		remarkable::Delete => |path_item| &mut path_item.delete,
+		remarkable::Get => |path_item| &mut path_item.get,
+		remarkable::Head => |path_item| &mut path_item.head,
+		remarkable::Options => |path_item| &mut path_item.options,
+		remarkable::Patch => |path_item| &mut path_item.patch,
+		remarkable::Post => |path_item| &mut path_item.post,
+		remarkable::Put => |path_item| &mut path_item.put,
+		remarkable::Trace => |path_item| &mut path_item.trace,
+		_ => return None,
+	})
+}
+
+fn endpoint_to_operation(root: &hir::Root<'_>, endpoint: &hir::Endpoint<'_>) -> Operation {
+	let description = docs(endpoint.item.attrs);
+	// The first line is used as a summary, the whole text is kept as the description
+	let summary = description
+		.as_deref()
+		.and_then(|docs| docs.lines().next())
+		.map(ToOwned::to_owned);
+
+	let mut parameters: Vec<_> = endpoint
+		.path_params()
+		.map(|(def, field)| parameter_or_ref(root, DefKind::Params, def, field))
+		.collect();
+	for location in [DefKind::Query, DefKind::Headers] {
+		for item in endpoint.items() {
+			let Some((kind, fields, def)) = block_fields(&item.kind) else {
+				continue;
+			};
+			if kind != location {
+				continue;
+			}
+
+			parameters.extend(
+				fields
+					.iter()
+					.map(|field| parameter_or_ref(root, kind, def, field)),
+			);
+		}
+	}
+
+	let request_body = endpoint.bodies().next().map(|(item, _)| {
+		ReferenceOr::Item(RequestBody {
+			description: docs(item.attrs),
+			content: bodies_content(root, endpoint.bodies()),
+			required: true,
+			extensions: IndexMap::default(),
+		})
+	});
+
+	let mut responses = Responses::default();
+	for (item, code) in endpoint.responses() {
+		let response = code.def.map_or_else(
+			|| ReferenceOr::Item(code_to_response(root, item, code)),
+			|def_id| {
+				let name = root.def(def_id).qualified_name();
+				ReferenceOr::ref_(&format!("#/components/responses/{name}"))
+			},
+		);
+
+		match code.status {
+			hir::Status::Code(code) => {
+				responses.responses.insert(StatusCode::Code(code), response);
+			}
+			hir::Status::Range(class) => {
+				responses
+					.responses
+					.insert(StatusCode::Range(class.into()), response);
+			}
+			hir::Status::Default => responses.default = Some(response),
+			hir::Status::Err => invalid_hir(),
+		}
+	}
+
+	Operation {
+		tags: endpoint
+			.mod_path
+			.last()
+			.map(ToString::to_string)
+			.into_iter()
+			.collect(),
+		summary,
+		description,
+		operation_id: Some(endpoint.operation_id()),
+		parameters,
+		request_body,
+		responses,
+		security: endpoint_security(root, endpoint),
+		..Default::default()
+	}
+}
+
+/// Builds a component parameter for every field of the shared `params`,
+/// `query` and `headers` blocks.
+pub(crate) fn defs_to_parameters(root: &hir::Root<'_>) -> IndexMap<String, ReferenceOr<Parameter>> {
+	root.defs()
+		.filter_map(|(_, def)| {
+			let (kind, fields, _) = block_fields(&def.item.kind)?;
+			Some(fields.iter().map(move |field| {
+				let parameter = field_to_parameter(root, kind, field);
+				(parameter_name(def, field), ReferenceOr::Item(parameter))
+			}))
+		})
+		.flatten()
+		.collect()
+}
+
+/// Builds a component response for every shared `response` block.
+pub(crate) fn defs_to_responses(root: &hir::Root<'_>) -> IndexMap<String, ReferenceOr<Response>> {
+	root.defs()
+		.filter_map(|(_, def)| match &def.item.kind {
+			hir::ItemKind::Response(response) => {
+				// A description is required, fall back to the name
+				let description =
+					docs(def.item.attrs).unwrap_or_else(|| def.item.ident.to_string());
+				let response =
+					items_to_response(root, description, response.headers(), response.bodies());
+
+				Some((def.qualified_name(), ReferenceOr::Item(response)))
+			}
+			_ => None,
+		})
+		.collect()
+}
+
+/// Name of the component parameter of a field of a shared block, e.g.
+/// `Authorized.Authorization`.
+fn parameter_name(def: &hir::Definition<'_>, field: &hir::FieldDef<'_>) -> String {
+	format!("{}.{}", def.qualified_name(), field.ident)
+}
+
+/// Where the fields of a `params`, `query` or `headers` item go, along with the
+/// shared block they come from.
+const fn block_fields<'tcx>(
+	kind: &hir::ItemKind<'tcx>,
+) -> Option<(DefKind, &'tcx [hir::FieldDef<'tcx>], Option<hir::DefId>)> {
+	Some(match kind {
+		hir::ItemKind::Params(params) => (DefKind::Params, params.properties, params.def),
+		hir::ItemKind::Query(query) => (DefKind::Query, query.fields, query.def),
+		hir::ItemKind::Headers(headers) => (DefKind::Headers, headers.headers, headers.def),
+		_ => return None,
+	})
+}
+
+/// A reference to the component parameter of fields of shared blocks.
+fn parameter_or_ref(
+	root: &hir::Root<'_>,
+	kind: DefKind,
+	def: Option<hir::DefId>,
+	field: &hir::FieldDef<'_>,
+) -> ReferenceOr<Parameter> {
+	def.map_or_else(
+		|| ReferenceOr::Item(field_to_parameter(root, kind, field)),
+		|def_id| {
+			let name = parameter_name(root.def(def_id), field);
+			ReferenceOr::ref_(&format!("#/components/parameters/{name}"))
+		},
+	)
+}
+
+fn field_to_parameter(root: &hir::Root<'_>, kind: DefKind, field: &hir::FieldDef<'_>) -> Parameter {
+	let parameter_data = parameter_data(root, field);
+	match kind {
+		DefKind::Params => Parameter::Path {
+			parameter_data,
+			style: PathStyle::default(),
+		},
+		DefKind::Query => Parameter::Query {
+			parameter_data,
+			allow_reserved: false,
+			style: QueryStyle::default(),
+			allow_empty_value: None,
+		},
+		DefKind::Headers => Parameter::Header {
+			parameter_data,
+			style: HeaderStyle::default(),
+		},
+		_ => unreachable!("only params, query and headers blocks hold parameters"),
+	}
+}
+
+fn code_to_response(
+	root: &hir::Root<'_>,
+	item: &hir::Item<'_>,
+	code: &hir::StatusCode<'_>,
+) -> Response {
+	// A description is required, fall back to the reason phrase
+	let description = docs(item.attrs)
+		.or_else(|| code.status.reason().map(ToOwned::to_owned))
+		.unwrap_or_default();
+
+	items_to_response(root, description, code.headers(), code.bodies())
+}
+
+fn items_to_response<'tcx>(
+	root: &hir::Root<'_>,
+	response_description: String,
+	headers: impl Iterator<Item = &'tcx hir::FieldDef<'tcx>>,
+	bodies: impl Iterator<Item = (&'tcx hir::Item<'tcx>, &'tcx hir::Body<'tcx>)>,
+) -> Response {
+	let headers = headers
+		.map(|field| {
+			let header = Header {
+				description: description(field.attrs),
+				style: HeaderStyle::default(),
+				required: field.required(),
+				deprecated: None,
+				format: ParameterSchemaOrContent::Schema(field_to_schema(root, field)),
+				example: field.example.map(expr_to_json),
+				examples: IndexMap::default(),
+				extensions: IndexMap::default(),
+			};
+
+			(field.ident.to_string(), ReferenceOr::Item(header))
+		})
+		.collect();
+
+	Response {
+		description: response_description,
+		headers,
+		content: bodies_content(root, bodies),
+		..Default::default()
+	}
+}
+
+/// One media type entry per `@type` of every body.
+fn bodies_content<'tcx>(
+	root: &hir::Root<'_>,
+	bodies: impl Iterator<Item = (&'tcx hir::Item<'tcx>, &'tcx hir::Body<'tcx>)>,
+) -> IndexMap<String, MediaType> {
+	bodies
+		.flat_map(|(_, body)| {
+			body.media_types.iter().map(|media_type| {
+				let content = MediaType {
+					schema: Some(body_to_schema(root, body)),
+					..Default::default()
+				};
+
+				(media_type.mime().to_owned(), content)
+			})
+		})
+		.collect()
+}
+
+fn parameter_data(root: &hir::Root<'_>, field: &hir::FieldDef<'_>) -> ParameterData {
+	ParameterData {
+		name: field.ident.to_string(),
+		description: description(field.attrs),
+		required: field.required(),
+		deprecated: None,
+		format: ParameterSchemaOrContent::Schema(field_to_schema(root, field)),
+		example: field.example.map(expr_to_json),
+		examples: IndexMap::default(),
+		explode: None,
+		extensions: IndexMap::default(),
+	}
+}
@@ -0,0 +1,445 @@
+use crate::{Composition, Options};
+use dapic_generator_common::invalid_hir;
+use dapic_hir::types::{self as hir, DefKind, Res, description, find_meta, has_attr};
+use dapic_lexer::rich::LiteralKind;
+use dapic_session::{
+	JsonTy, PrimTy,
+	symbols::{attrs, kw},
+};
+use indexmap::IndexMap;
+use openapiv3::{
+	AdditionalProperties, AnySchema, ArrayType, BooleanType, Discriminator, IntegerType,
+	NumberType, ObjectType, ReferenceOr, Schema, SchemaData, SchemaKind, StringType, Type,
+};
+use serde_json::Value;
+
+/// Builds a component schema for every `model`, `enum`, `union` and type
+/// alias of the api, aliases with `@inline` are inlined where they are used.
+pub(crate) fn defs_to_schemas(
+	root: &hir::Root<'_>,
+	options: &Options,
+) -> IndexMap<String, ReferenceOr<Schema>> {
+	root.defs()
+		.filter_map(|(_, def)| {
+			let mut schema = match &def.item.kind {
+				// Only the instances of generic models are used
+				hir::ItemKind::Model(model) if !model.generics.is_empty() => return None,
+				hir::ItemKind::Model(model) => model_to_schema(root, model, options.composition),
+				hir::ItemKind::Enum(enum_) => enum_to_schema(enum_),
+				hir::ItemKind::Union(union) => union_to_schema(root, union),
+				hir::ItemKind::TypeAlias(alias) => {
+					if has_attr(def.item.attrs, attrs::inline) {
+						return None;
+					}
+					alias_to_schema(root, def.item.attrs, alias)
+				}
+				// Auth schemes, shared blocks and responses have components of their own
+				_ => return None,
+			};
+			schema.schema_data.title = Some(def.item.ident.to_string());
+			schema.schema_data.description = description(def.item.attrs);
+
+			Some((def.qualified_name(), ReferenceOr::Item(schema)))
+		})
+		.collect()
+}
+
+pub(crate) fn schema_ref(def: &hir::Definition<'_>) -> ReferenceOr<Schema> {
+	ReferenceOr::ref_(&format!("#/components/schemas/{}", def.qualified_name()))
+}
+
+pub(crate) fn ty_to_schema(root: &hir::Root<'_>, ty: &hir::Ty<'_>) -> ReferenceOr<Schema> {
+	match ty.kind {
+		hir::TyKind::Path(_, Res::PrimTy(prim)) => ReferenceOr::Item(prim_to_schema(prim)),
+		hir::TyKind::Path(_, Res::Def(DefKind::TypeAlias, def_id)) => {
+			let def = root.def(def_id);
+			match &def.item.kind {
+				hir::ItemKind::TypeAlias(alias) if has_attr(def.item.attrs, attrs::inline) => {
+					ReferenceOr::Item(alias_to_schema(root, def.item.attrs, alias))
+				}
+				_ => schema_ref(def),
+			}
+		}
+		hir::TyKind::Path(_, Res::Def(_, def_id)) => schema_ref(root.def(def_id)),
+		hir::TyKind::Path(_, Res::TyParam) => unreachable!("generic models are not generated"),
+		hir::TyKind::Path(_, Res::Err) => invalid_hir(),
+		hir::TyKind::Array(ty) => {
+			ReferenceOr::Item(new_schema(SchemaKind::Type(Type::Array(ArrayType {
+				items: Some(boxed(ty_to_schema(root, ty))),
+				min_items: None,
+				max_items: None,
+				unique_items: false,
+			}))))
+		}
+		hir::TyKind::Tuple(tys) => ReferenceOr::Item(tuple_to_schema(root, tys)),
+		hir::TyKind::InlineModel(fields) => {
+			ReferenceOr::Item(fields_to_schema(root, fields.iter()))
+		}
+		// OpenAPI 3.0 has no way to constrain the keys
+		hir::TyKind::Map(_, value) => {
+			ReferenceOr::Item(new_schema(SchemaKind::Type(Type::Object(ObjectType {
+				additional_properties: Some(AdditionalProperties::Schema(Box::new(ty_to_schema(
+					root, value,
+				)))),
+				..Default::default()
+			}))))
+		}
+		hir::TyKind::Union(tys) => ReferenceOr::Item(new_schema(SchemaKind::OneOf {
+			one_of: tys.iter().map(|ty| ty_to_schema(root, ty)).collect(),
+		})),
+		hir::TyKind::Nullable(ty) => {
+			let mut schema = inline_schema(ty_to_schema(root, ty));
+			schema.schema_data.nullable = true;
+			ReferenceOr::Item(schema)
+		}
+	}
+}
+
+/// Schema of the aliased type, completed with the alias attributes.
+fn alias_to_schema(
+	root: &hir::Root<'_>,
+	attrs: &[hir::Attribute<'_>],
+	alias: &hir::TypeAlias<'_>,
+) -> Schema {
+	let mut schema = inline_schema(ty_to_schema(root, alias.ty));
+	schema.schema_data.description = description(attrs);
+
+	if let Some(format) = find_meta(attrs, attrs::format).and_then(hir::Expr::str_literal) {
+		set_format(&mut schema.schema_kind, format.as_str());
+	}
+	set_constraints(&mut schema.schema_kind, alias.constraints);
+
+	schema
+}
+
+/// One of the variants, told apart by the discriminator mapped to their
+/// component.
+fn union_to_schema(root: &hir::Root<'_>, union: &hir::Union<'_>) -> Schema {
+	let one_of = union
+		.variants
+		.iter()
+		.map(|variant| ty_to_schema(root, variant.ty))
+		.collect::<Vec<_>>();
+	let mapping = union
+		.variants
+		.iter()
+		.zip(&one_of)
+		.filter_map(|(variant, schema)| match schema {
+			ReferenceOr::Reference { reference } => {
+				Some((variant.ident.to_string(), reference.clone()))
+			}
+			ReferenceOr::Item(_) => None,
+		})
+		.collect();
+
+	let mut schema = new_schema(SchemaKind::OneOf { one_of });
+	schema.schema_data.discriminator = Some(Discriminator {
+		property_name: union.discriminator.to_string(),
+		mapping,
+		extensions: IndexMap::default(),
+	});
+
+	schema
+}
+
+/// Siblings of a `$ref` are ignored, references are wrapped in an `allOf` to
+/// be completed. The wrapper is a schema of any type so that it can also hold
+/// the format and constraints.
+fn inline_schema(schema: ReferenceOr<Schema>) -> Schema {
+	match schema {
+		ReferenceOr::Item(schema) => schema,
+		reference @ ReferenceOr::Reference { .. } => new_schema(SchemaKind::Any(AnySchema {
+			all_of: vec![reference],
+			..Default::default()
+		})),
+	}
+}
+
+/// Schema of a field type, completed with the field attributes.
+pub(crate) fn field_to_schema(
+	root: &hir::Root<'_>,
+	field: &hir::FieldDef<'_>,
+) -> ReferenceOr<Schema> {
+	let description = description(field.attrs);
+	let format = find_meta(field.attrs, attrs::format).and_then(hir::Expr::str_literal);
+
+	let schema = ty_to_schema(root, field.ty);
+	let completed = description.is_some()
+		|| format.is_some()
+		|| field.default.is_some()
+		|| field.example.is_some()
+		|| !field.constraints.is_empty();
+	if !completed {
+		return schema;
+	}
+
+	let mut schema = inline_schema(schema);
+	// Inlined aliases keep their own description
+	if description.is_some() {
+		schema.schema_data.description = description;
+	}
+	schema.schema_data.default = field.default.map(expr_to_json);
+	schema.schema_data.example = field.example.map(expr_to_json);
+
+	if let Some(format) = format {
+		set_format(&mut schema.schema_kind, format.as_str());
+	}
+	set_constraints(&mut schema.schema_kind, field.constraints);
+
+	ReferenceOr::Item(schema)
+}
+
+/// Schema of a `body` type, completed with its constraints.
+pub(crate) fn body_to_schema(root: &hir::Root<'_>, body: &hir::Body<'_>) -> ReferenceOr<Schema> {
+	let schema = ty_to_schema(root, body.ty);
+	if body.constraints.is_empty() {
+		return schema;
+	}
+
+	let mut schema = inline_schema(schema);
+	set_constraints(&mut schema.schema_kind, body.constraints);
+
+	ReferenceOr::Item(schema)
+}
+
+/// JSON value of a literal, defaults and examples were already checked against
+/// their type during lowering.
+pub(crate) fn expr_to_json(expr: &hir::Expr<'_>) -> serde_json::Value {
+	match expr.kind {
+		hir::ExprKind::Literal(LiteralKind::Number, number) => {
+			let number = number.as_str().replace('_', "");
+			number.parse::<i64>().map_or_else(
+				|_| number.parse::<f64>().map_or(Value::Null, Value::from),
+				Value::from,
+			)
+		}
+		hir::ExprKind::Literal(LiteralKind::Str, value) => Value::from(value.as_str()),
+		hir::ExprKind::Literal(LiteralKind::Bool, value) => Value::from(value == kw::True),
+		hir::ExprKind::Array(exprs) => exprs.iter().map(expr_to_json).collect(),
+		hir::ExprKind::Path(_) | hir::ExprKind::Template(_) | hir::ExprKind::Field(..) => {
+			Value::Null
+		}
+	}
+}
+
+/// Models with bases either reference them in an `allOf`, or copy their fields.
+fn model_to_schema(
+	root: &hir::Root<'_>,
+	model: &hir::Model<'_>,
+	composition: Composition,
+) -> Schema {
+	if model.bases.is_empty() {
+		return fields_to_schema(root, model.fields.iter());
+	}
+
+	match composition {
+		Composition::AllOf => {
+			let all_of = model
+				.bases
+				.iter()
+				.map(|base| ty_to_schema(root, base))
+				.chain([ReferenceOr::Item(fields_to_schema(
+					root,
+					model.fields.iter(),
+				))])
+				.collect();
+
+			new_schema(SchemaKind::AllOf { all_of })
+		}
+		Composition::Flatten => fields_to_schema(root, root.all_fields(model).into_iter()),
+	}
+}
+
+fn fields_to_schema<'a, 'tcx: 'a>(
+	root: &hir::Root<'_>,
+	fields: impl Iterator<Item = &'a hir::FieldDef<'tcx>> + Clone,
+) -> Schema {
+	let properties = fields
+		.clone()
+		.map(|field| (field.ident.to_string(), boxed(field_to_schema(root, field))))
+		.collect();
+
+	new_schema(SchemaKind::Type(Type::Object(ObjectType {
+		properties,
+		required: fields
+			.filter(|field| field.required())
+			.map(|field| field.ident.to_string())
+			.collect(),
+		..Default::default()
+	})))
+}
+
+/// Variants are serialized as their value, their names and descriptions are
+/// kept in `x-enum-varnames` and `x-enum-descriptions` for client generators.
+fn enum_to_schema(enum_: &hir::Enum<'_>) -> Schema {
+	let mut schema = prim_to_schema(enum_.ty);
+	let values = enum_
+		.variants
+		.iter()
+		.map(|variant| match variant.expr.kind {
+			hir::ExprKind::Literal(_, value) => value,
+			_ => unreachable!("variant values were checked during lowering"),
+		});
+
+	match &mut schema.schema_kind {
+		SchemaKind::Type(Type::String(string)) => {
+			string.enumeration = values.map(|value| Some(value.to_string())).collect();
+		}
+		SchemaKind::Type(Type::Integer(integer)) => {
+			integer.enumeration = values
+				.map(|value| value.as_str().replace('_', "").parse().ok())
+				.collect();
+		}
+		_ => unreachable!("enums are backed by strings or integers"),
+	}
+
+	let extensions = &mut schema.schema_data.extensions;
+	let names = enum_
+		.variants
+		.iter()
+		.map(|variant| Value::from(variant.ident.to_string()));
+	extensions.insert("x-enum-varnames".to_owned(), names.collect());
+
+	let descriptions = enum_
+		.variants
+		.iter()
+		.map(|variant| description(variant.attrs))
+		.collect::<Vec<_>>();
+	if descriptions.iter().any(Option::is_some) {
+		let descriptions = descriptions
+			.into_iter()
+			.map(|description| Value::from(description.unwrap_or_default()));
+		extensions.insert("x-enum-descriptions".to_owned(), descriptions.collect());
+	}
+
+	schema
+}
+
+/// Tuples are fixed-length arrays, items are described in order by `prefixItems`.
+fn tuple_to_schema(root: &hir::Root<'_>, tys: &[hir::Ty<'_>]) -> Schema {
+	let prefix_items = tys
+		.iter()
+		.map(|ty| serde_json::to_value(ty_to_schema(root, ty)))
+		.collect::<Result<Vec<_>, _>>()
+		.expect("schemas are always serializable");
+
+	let mut schema = new_schema(SchemaKind::Any(AnySchema {
+		typ: Some("array".into()),
+		min_items: Some(tys.len()),
+		max_items: Some(tys.len()),
+		..Default::default()
+	}));
+	schema
+		.schema_data
+		.extensions
+		.insert("prefixItems".into(), prefix_items.into());
+
+	schema
+}
+
+pub(crate) fn prim_to_schema(prim: PrimTy) -> Schema {
+	let format = prim.format().map(ToOwned::to_owned);
+
+	let kind = match prim.json_ty() {
+		None => SchemaKind::Any(AnySchema::default()),
+		Some(JsonTy::Boolean) => SchemaKind::Type(Type::Boolean(BooleanType::default())),
+		Some(JsonTy::Integer) => SchemaKind::Type(Type::Integer(IntegerType {
+			format: format.into(),
+			..Default::default()
+		})),
+		Some(JsonTy::Number) => SchemaKind::Type(Type::Number(NumberType {
+			format: format.into(),
+			..Default::default()
+		})),
+		Some(JsonTy::String) => SchemaKind::Type(Type::String(StringType {
+			format: format.into(),
+			..Default::default()
+		})),
+	};
+
+	new_schema(kind)
+}
+
+/// Overrides the format implied by the primitive, the `@format` attribute was
+/// already checked against the field type during lowering.
+fn set_format(kind: &mut SchemaKind, format: &str) {
+	let format = Some(format.to_owned());
+
+	match kind {
+		SchemaKind::Type(Type::Integer(integer)) => integer.format = format.into(),
+		SchemaKind::Type(Type::Number(number)) => number.format = format.into(),
+		SchemaKind::Type(Type::String(string)) => string.format = format.into(),
+		SchemaKind::Any(any) => any.format = format,
+		_ => {}
+	}
+}
+
+/// Exports the constraints to the matching schema keywords, they were already
+/// checked against the type during lowering.
+#[allow(clippy::cast_possible_truncation)]
+fn set_constraints(kind: &mut SchemaKind, constraints: &[hir::Constraint]) {
+	use hir::Constraint as C;
+
+	let count = |count: u64| usize::try_from(count).ok();
+
+	for constraint in constraints {
+		match (&mut *kind, *constraint) {
+			// Bounds of integers are integral
+			(SchemaKind::Type(Type::Integer(integer)), C::Min(min)) => {
+				integer.minimum = Some(min as i64);
+			}
+			(SchemaKind::Type(Type::Integer(integer)), C::Max(max)) => {
+				integer.maximum = Some(max as i64);
+			}
+			(SchemaKind::Type(Type::Integer(integer)), C::MultipleOf(multiple)) => {
+				integer.multiple_of = Some(multiple as i64);
+			}
+			(SchemaKind::Type(Type::Number(number)), C::Min(min)) => number.minimum = Some(min),
+			(SchemaKind::Type(Type::Number(number)), C::Max(max)) => number.maximum = Some(max),
+			(SchemaKind::Type(Type::Number(number)), C::MultipleOf(multiple)) => {
+				number.multiple_of = Some(multiple);
+			}
+			(SchemaKind::Type(Type::String(string)), C::MinLength(min)) => {
+				string.min_length = count(min);
+			}
+			(SchemaKind::Type(Type::String(string)), C::MaxLength(max)) => {
+				string.max_length = count(max);
+			}
+			(SchemaKind::Type(Type::String(string)), C::Pattern(pattern)) => {
+				string.pattern = Some(pattern.to_string());
+			}
+			(SchemaKind::Type(Type::Array(array)), C::MinItems(min)) => {
+				array.min_items = count(min);
+			}
+			(SchemaKind::Type(Type::Array(array)), C::MaxItems(max)) => {
+				array.max_items = count(max);
+			}
+			(SchemaKind::Type(Type::Array(array)), C::Unique) => array.unique_items = true,
+			// Wrapped references, the keywords apply to the referenced type
+			(SchemaKind::Any(any), C::Min(min)) => any.minimum = Some(min),
+			(SchemaKind::Any(any), C::Max(max)) => any.maximum = Some(max),
+			(SchemaKind::Any(any), C::MultipleOf(multiple)) => any.multiple_of = Some(multiple),
+			(SchemaKind::Any(any), C::MinLength(min)) => any.min_length = count(min),
+			(SchemaKind::Any(any), C::MaxLength(max)) => any.max_length = count(max),
+			(SchemaKind::Any(any), C::Pattern(pattern)) => any.pattern = Some(pattern.to_string()),
+			(SchemaKind::Any(any), C::MinItems(min)) => any.min_items = count(min),
+			(SchemaKind::Any(any), C::MaxItems(max)) => any.max_items = count(max),
+			(SchemaKind::Any(any), C::Unique) => any.unique_items = Some(true),
+			_ => {}
+		}
+	}
+}
+
+pub(crate) fn new_schema(schema_kind: SchemaKind) -> Schema {
+	Schema {
+		schema_data: SchemaData::default(),
+		schema_kind,
+	}
+}
+
+pub(crate) fn boxed(schema: ReferenceOr<Schema>) -> ReferenceOr<Box<Schema>> {
+	match schema {
+		ReferenceOr::Reference { reference } => ReferenceOr::Reference { reference },
+		ReferenceOr::Item(schema) => ReferenceOr::Item(Box::new(schema)),
+	}
+}
@@ -0,0 +1,165 @@
+use crate::{Composition, Options, generate_openapi_spec};
+use dapic_ast::visit_mut::MutVisitor;
+use dapic_expand::NodeExpander;
+use dapic_hir::{Arena, compile_hir};
+use dapic_parser::Parser;
+use dapic_session::Session;
+
+/// Lowers `src` and gives its spec as pretty JSON.
+fn generate(src: &str, options: &Options) -> String {
+	let mut session = Session::default();
+
+	session.enter_source_map_ctx(|session| {
+		let sf = session.source_map.load_anon(src.into());
+		let mut ast = Parser::from_source(&session.parse_sess(), &sf)
+			.parse_root()
+			.expect("source should parse");
+		NodeExpander::default().visit_root(&mut ast);
+
+		let arena = Arena::new();
+		let hir = compile_hir(session, &arena, &ast);
+		assert!(!session.diagnostics.has_errors(), "source should lower");
+
+		let spec = generate_openapi_spec(&hir, options, &session.diagnostics);
+		serde_json::to_string_pretty(&spec).expect("spec should serialize")
+	})
+}
+
+const PETSTORE: &str = r#"
+##! A sample pet store
+meta {
+	name "Petstore"
+	version "1.0.0"
+	license "MIT"
+	urls ["https://petstore.example.com/v1"]
+}
+
+auth ApiKey {
+	type "apiKey"
+	in "header"
+	name "api_key"
+}
+
+## A failure
+model Error {
+	message string
+	code? int32
+}
+
+type Tags = [string]
+
+model Page<T> {
+	items [T]
+	total int |@min: 0|
+}
+
+scope pet {
+	model Base {
+		id int64
+		kind string
+	}
+
+	## Status of a pet
+	enum Status {
+		## Ready to be adopted
+		Available "available"
+		Sold "sold"
+	}
+
+	enum Priority : int { Low 1 High 2 Urgent 1_000 }
+
+	model Pet : Base {
+		## Name of the pet
+		name string "The name" |@maxLength: 64|
+		status? Status
+		tags Tags = []
+		## Other names, at most three
+		@@maxItems: 3
+		@@unique
+		nicknames Tags
+		## Pet adopted along with this one
+		sibling? Base
+		owner? { name string phone? string }
+		parent Pet?
+		ranks {[Status]: Priority}
+		born date
+	}
+
+	union Animal on kind { dog Pet cat Pet }
+
+	params PetId { petId int64 }
+	query Paging {
+		limit? int = 20
+		offset? int |@example: 40|
+	}
+	response NotFound { body Error }
+
+	auth ApiKey;
+
+	path pets {
+		verb GET {
+			query Paging;
+			code 200 { body Page<Pet> }
+			code ~5xx { body Error }
+		}
+
+		path {petId} {
+			params PetId;
+
+			verb PUT {
+				headers { X-Request-Id uuid }
+				body Pet
+				code 200 { body Animal }
+				code 404 NotFound;
+				code default { body Error }
+			}
+
+			verb POST {
+				@@type: "form"
+				body { name string }
+				code 204 {}
+			}
+		}
+
+		path search {
+			## Left out with a warning, `OpenAPI` has no custom verbs
+			verb GETTTY {
+				query { name string }
+				code 200 { body [Pet] }
+			}
+		}
+	}
+}
+"#;
+
+const COMPOSITION: &str = r#"
+meta {
+	name "Composition"
+	version "1.0.0"
+}
+
+model Base {
+	id int64
+}
+
+model Timestamps {
+	createdAt date-time
+}
+
+model Pet : Base, Timestamps {
+	name string
+}
+"#;
+
+#[test]
+fn generate_spec() {
+	insta::assert_snapshot!(generate(PETSTORE, &Options::default()));
+}
+
+#[test]
+fn generate_spec_flatten() {
+	let options = Options {
+		composition: Composition::Flatten,
+	};
+	insta::assert_snapshot!(generate(COMPOSITION, &options));
+}
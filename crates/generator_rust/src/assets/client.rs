@@ -0,0 +1,66 @@
+/// Error of an operation, `E` holds its documented error responses.
+#[derive(Debug)]
+pub enum Error<E> {
+    /// The api answered with one of the documented error responses
+    Api(E),
+    /// The request could not be sent or its response could not be decoded
+    Request(reqwest::Error),
+    /// The api answered with a status code the operation does not document
+    UnexpectedStatus(u16),
+    /// A parameter, a header or the body could not be serialized
+    Encode(serde_json::Error),
+}
+
+impl<E> From<reqwest::Error> for Error<E> {
+    fn from(error: reqwest::Error) -> Self {
+        Self::Request(error)
+    }
+}
+
+impl<E> From<serde_json::Error> for Error<E> {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Encode(error)
+    }
+}
+
+impl<E: std::fmt::Debug> std::fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Api(error) => write!(f, "api error: {error:?}"),
+            Self::Request(error) => write!(f, "request error: {error}"),
+            Self::UnexpectedStatus(status) => write!(f, "unexpected status code {status}"),
+            Self::Encode(error) => write!(f, "encode error: {error}"),
+        }
+    }
+}
+
+impl<E: std::fmt::Debug> std::error::Error for Error<E> {}
+
+/// Client of the api, the operations of a scope are reached through the
+/// method of the same name.
+#[derive(Debug, Clone)]
+pub struct Client {
+    base_url: reqwest::Url,
+    http: reqwest::Client,
+}
+
+impl Client {
+    /// `base_url` is the URL the paths are appended to, e.g.
+    /// `https://api.example.com/v1`.
+    pub fn new(base_url: reqwest::Url) -> Self {
+        Self::with_client(base_url, reqwest::Client::new())
+    }
+
+    /// Sends the requests with `http`, e.g. to set default headers.
+    pub fn with_client(base_url: reqwest::Url, http: reqwest::Client) -> Self {
+        Self { base_url, http }
+    }
+
+    fn request(&self, method: reqwest::Method, segments: &[&str]) -> reqwest::RequestBuilder {
+        let mut url = self.base_url.clone();
+        if let Ok(mut path) = url.path_segments_mut() {
+            path.pop_if_empty().extend(segments);
+        }
+        self.http.request(method, url)
+    }
+}
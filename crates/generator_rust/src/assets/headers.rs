@@ -0,0 +1,31 @@
+
+/// Value of a required header, requests without it are rejected with
+/// `400 Bad Request`.
+fn header<T: serde::de::DeserializeOwned>(
+    headers: &axum::http::HeaderMap,
+    name: &str,
+) -> Result<T, axum::response::Response> {
+    optional_header(headers, name)?.ok_or_else(|| bad_request(format!("missing header `{name}`")))
+}
+
+/// Value of an optional header, requests with an invalid value are rejected
+/// with `400 Bad Request`.
+fn optional_header<T: serde::de::DeserializeOwned>(
+    headers: &axum::http::HeaderMap,
+    name: &str,
+) -> Result<Option<T>, axum::response::Response> {
+    let Some(value) = headers.get(name) else {
+        return Ok(None);
+    };
+    let invalid = || bad_request(format!("invalid header `{name}`"));
+    let text = value.to_str().map_err(|_| invalid())?;
+    // Strings are taken as is, other values are parsed from their JSON text
+    serde_json::from_value(serde_json::Value::String(text.to_owned()))
+        .or_else(|_| serde_json::from_str(text))
+        .map(Some)
+        .map_err(|_| invalid())
+}
+
+fn bad_request(message: String) -> axum::response::Response {
+    axum::response::IntoResponse::into_response((axum::http::StatusCode::BAD_REQUEST, message))
+}
@@ -0,0 +1,20 @@
+
+/// Form of a multipart body, arrays of bytes are sent as is and other fields
+/// as text.
+fn multipart_form<T: serde::Serialize>(body: &T) -> Result<reqwest::multipart::Form, serde_json::Error> {
+    let mut form = reqwest::multipart::Form::new();
+    if let serde_json::Value::Object(fields) = serde_json::to_value(body)? {
+        for (name, value) in fields {
+            match value {
+                serde_json::Value::Null => {}
+                serde_json::Value::String(text) => form = form.text(name, text),
+                serde_json::Value::Array(items) if !items.is_empty() && items.iter().all(|item| item.as_u64().is_some_and(|byte| byte <= 255)) => {
+                    let bytes = items.iter().filter_map(serde_json::Value::as_u64).map(|byte| byte as u8);
+                    form = form.part(name, reqwest::multipart::Part::bytes(bytes.collect::<Vec<_>>()));
+                }
+                value => form = form.text(name, value.to_string()),
+            }
+        }
+    }
+    Ok(form)
+}
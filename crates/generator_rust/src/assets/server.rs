@@ -0,0 +1,4 @@
+/// Status code of a response, invalid codes become `500 Internal Server Error`.
+fn status(code: u16) -> axum::http::StatusCode {
+    axum::http::StatusCode::from_u16(code).unwrap_or(axum::http::StatusCode::INTERNAL_SERVER_ERROR)
+}
@@ -0,0 +1,8 @@
+
+/// Text of a path parameter or a header, strings are not quoted.
+fn to_text<T: serde::Serialize + ?Sized>(value: &T) -> Result<String, serde_json::Error> {
+    Ok(match serde_json::to_value(value)? {
+        serde_json::Value::String(text) => text,
+        value => value.to_string(),
+    })
+}
@@ -0,0 +1,402 @@
+use crate::{
+	Module,
+	operation::{
+		Encoding, Outcome, is_standard_method, method_name, outcomes, type_prefix,
+		write_input_structs, write_operation_docs, write_outcome_enum,
+	},
+	text::{indent, pascal_case, snake_case, string_literal, write_docs, write_signature},
+	types::Types,
+};
+use dapic_hir::types::{self as hir, EndpointSegment};
+use std::fmt::{self, Write};
+
+/// An operation with the Rust types of its arguments and results.
+struct Operation<'tcx> {
+	endpoint: hir::Endpoint<'tcx>,
+	prefix: String,
+	/// Path parameters as their argument name and type
+	params: Vec<(String, String)>,
+	has_query: bool,
+	has_headers: bool,
+	/// Type, encoding and media type of the first body
+	body: Option<(String, Encoding, &'tcx str)>,
+	outcomes: Vec<Outcome<'tcx>>,
+}
+
+impl Operation<'_> {
+	fn successes(&self) -> Vec<&Outcome<'_>> {
+		self.outcomes.iter().filter(|o| o.is_success()).collect()
+	}
+
+	fn errors(&self) -> Vec<&Outcome<'_>> {
+		self.outcomes.iter().filter(|o| !o.is_success()).collect()
+	}
+
+	/// A single success is returned as is, several as a `Response` enum.
+	fn success_ty(&self) -> String {
+		match self.successes().as_slice() {
+			[] => "()".into(),
+			[outcome] if !outcome.has_status() => outcome.ty.clone().unwrap_or_else(|| "()".into()),
+			_ => format!("{}Response", self.prefix),
+		}
+	}
+
+	fn error_ty(&self) -> String {
+		if self.errors().is_empty() {
+			"std::convert::Infallible".into()
+		} else {
+			format!("{}Error", self.prefix)
+		}
+	}
+}
+
+/// Writes the types of the operations of the scope and its client: a struct
+/// borrowing the root client with a method per operation and per child scope.
+pub(crate) fn write_scope<'tcx>(
+	out: &mut String,
+	types: &mut Types<'_, 'tcx>,
+	module: &Module<'tcx>,
+) -> fmt::Result {
+	if !module.has_endpoints() {
+		return Ok(());
+	}
+
+	let mut chunks = Vec::new();
+	let mut operations = Vec::new();
+	for endpoint in &module.endpoints {
+		let mut chunk = String::new();
+		operations.push(write_operation_types(&mut chunk, types, endpoint)?);
+		if !chunk.is_empty() {
+			chunks.push(chunk);
+		}
+	}
+
+	let mut methods = Vec::new();
+	for operation in &operations {
+		let mut method = String::new();
+		write_method(&mut method, types, operation)?;
+		methods.push(method);
+	}
+	for child in &module.children {
+		if child.has_endpoints() {
+			let mut method = String::new();
+			write_accessor(&mut method, types, child)?;
+			methods.push(method);
+		}
+	}
+
+	let mut chunk = String::new();
+	if types.depth == 0 {
+		// The struct of the root client is part of the runtime
+		writeln!(chunk, "impl Client {{")?;
+	} else {
+		writeln!(
+			chunk,
+			"/// Operations of the `{}` scope.",
+			module
+				.ident
+				.map_or_else(String::new, |ident| ident.to_string())
+		)?;
+		writeln!(chunk, "#[derive(Debug, Clone, Copy)]")?;
+		writeln!(chunk, "pub struct Client<'a> {{")?;
+		writeln!(
+			chunk,
+			"    pub(super) root: &'a {},",
+			types.root_path("Client")
+		)?;
+		writeln!(chunk, "}}")?;
+		writeln!(chunk)?;
+		writeln!(chunk, "impl Client<'_> {{")?;
+	}
+	write!(chunk, "{}", indent(&methods.join("\n")))?;
+	writeln!(chunk, "}}")?;
+	chunks.push(chunk);
+
+	write!(out, "{}", chunks.join("\n"))
+}
+
+/// Writes the query, headers, response and error types of the operation.
+fn write_operation_types<'tcx>(
+	out: &mut String,
+	types: &mut Types<'_, 'tcx>,
+	endpoint: &hir::Endpoint<'tcx>,
+) -> Result<Operation<'tcx>, fmt::Error> {
+	let prefix = type_prefix(endpoint);
+	let mut chunks = Vec::new();
+
+	let params = endpoint
+		.params()
+		.map(|field| {
+			let name = field.ident.symbol.as_str();
+			let ty = match types.ty_to_rust(field.ty, &format!("{prefix}{}", pascal_case(name))) {
+				ty if ty == "String" => "&str".into(),
+				ty => format!("&{ty}"),
+			};
+			(snake_case(name), ty)
+		})
+		.collect();
+
+	let has_query = endpoint.query().next().is_some();
+	let has_headers = endpoint.headers().next().is_some();
+	write_input_structs(
+		&mut chunks,
+		types,
+		endpoint,
+		&format!("Client::{}", method_name(endpoint)),
+	)?;
+
+	let body = endpoint.bodies().next().map(|(_, body)| {
+		let encoding = Encoding::of(body);
+		let ty = match encoding {
+			Encoding::Text => "&str".into(),
+			Encoding::Bytes => "&[u8]".into(),
+			_ => format!("&{}", types.ty_to_rust(body.ty, &format!("{prefix}Body"))),
+		};
+		let mime = body
+			.media_types
+			.first()
+			.map_or("application/json", |media_type| media_type.mime());
+		(ty, encoding, mime)
+	});
+
+	let operation = Operation {
+		endpoint: endpoint.clone(),
+		outcomes: outcomes(types, endpoint),
+		prefix,
+		params,
+		has_query,
+		has_headers,
+		body,
+	};
+
+	let successes = operation.successes();
+	if operation.success_ty() == format!("{}Response", operation.prefix) {
+		let mut chunk = String::new();
+		writeln!(
+			chunk,
+			"/// Successful responses of [`Client::{}`].",
+			method_name(endpoint)
+		)?;
+		write_outcome_enum(&mut chunk, &operation.success_ty(), &successes)?;
+		chunks.push(chunk);
+	}
+	let errors = operation.errors();
+	if !errors.is_empty() {
+		let mut chunk = String::new();
+		writeln!(
+			chunk,
+			"/// Error responses of [`Client::{}`].",
+			method_name(endpoint)
+		)?;
+		write_outcome_enum(&mut chunk, &operation.error_ty(), &errors)?;
+		chunks.push(chunk);
+	}
+
+	let mut pending = String::new();
+	types.write_pending(&mut pending)?;
+	if !pending.is_empty() {
+		chunks.push(pending.trim_start().to_owned());
+	}
+
+	write!(out, "{}", chunks.join("\n"))?;
+	Ok(operation)
+}
+
+/// Writes the async method sending the request of the operation and
+/// decoding its response.
+fn write_method(out: &mut String, types: &Types<'_, '_>, operation: &Operation<'_>) -> fmt::Result {
+	let endpoint = &operation.endpoint;
+	let to_text = types.root_path("to_text");
+	let error = types.root_path("Error");
+
+	let mut arguments = vec!["&self".to_owned()];
+	arguments.extend(
+		operation
+			.params
+			.iter()
+			.map(|(name, ty)| format!("{name}: {ty}")),
+	);
+	if operation.has_query {
+		arguments.push(format!("query: &{}Query", operation.prefix));
+	}
+	if operation.has_headers {
+		arguments.push(format!("headers: &{}Headers", operation.prefix));
+	}
+	if let Some((ty, ..)) = &operation.body {
+		arguments.push(format!("body: {ty}"));
+	}
+
+	write_operation_docs(out, "", endpoint)?;
+	write_signature(
+		out,
+		4 * (types.depth + 1),
+		&format!("pub async fn {}", method_name(endpoint)),
+		&arguments,
+		&format!(
+			"Result<{}, {error}<{}>>",
+			operation.success_ty(),
+			operation.error_ty()
+		),
+	)?;
+
+	let mut params = operation.params.iter();
+	let segments = endpoint
+		.segments
+		.iter()
+		.map(|segment| match segment {
+			EndpointSegment::Literal(ident) => string_literal(ident.symbol.as_str()),
+			EndpointSegment::Variable(_) => {
+				let (name, _) = params.next().expect("every variable has a parameter");
+				format!("&{to_text}({name})?")
+			}
+		})
+		.collect::<Vec<_>>();
+	let client = if types.depth == 0 {
+		"self"
+	} else {
+		"self.root"
+	};
+	let method = endpoint.verb.method.symbol.as_str();
+	let method = if is_standard_method(endpoint) {
+		format!("reqwest::Method::{method}")
+	} else {
+		format!(
+			"reqwest::Method::from_bytes({}.as_bytes()).expect(\"custom verbs are valid methods\")",
+			string_literal(method)
+		)
+	};
+	let request = format!("{client}.request({method}, &[{}])", segments.join(", "));
+
+	let statements = request_statements(types, operation);
+	let mut body = String::new();
+	if statements.is_empty() {
+		writeln!(body, "let response = {request}.send().await?;")?;
+	} else {
+		writeln!(body, "let mut request = {request};")?;
+		for statement in &statements {
+			writeln!(body, "{statement}")?;
+		}
+		writeln!(body, "let response = request.send().await?;")?;
+	}
+	writeln!(body, "let status = response.status().as_u16();")?;
+	writeln!(body)?;
+	writeln!(body, "match status {{")?;
+	write!(body, "{}", indent(&match_arms(operation, &error)?))?;
+	writeln!(body, "}}")?;
+
+	write!(out, "{}", indent(&body))?;
+	writeln!(out, "}}")
+}
+
+/// Statements adding the query, headers and body to the `request`.
+fn request_statements(types: &Types<'_, '_>, operation: &Operation<'_>) -> Vec<String> {
+	let endpoint = &operation.endpoint;
+	let to_text = types.root_path("to_text");
+
+	let mut statements = Vec::new();
+	if operation.has_query {
+		statements.push("request = request.query(query);".to_owned());
+	}
+	for field in endpoint.headers() {
+		let name = string_literal(field.ident.symbol.as_str());
+		let ident = snake_case(field.ident.symbol.as_str());
+		if field.required() {
+			statements.push(format!(
+				"request = request.header({name}, {to_text}(&headers.{ident})?);"
+			));
+		} else {
+			statements.push(format!(
+				"if let Some(value) = &headers.{ident} {{\n    request = request.header({name}, {to_text}(value)?);\n}}"
+			));
+		}
+	}
+	if let Some((_, encoding, mime)) = &operation.body {
+		let content_type = string_literal(mime);
+		statements.push(match encoding {
+			Encoding::Json if *mime == "application/json" => "request = request.json(body);".into(),
+			Encoding::Json => format!(
+				"request = request\n    .header(reqwest::header::CONTENT_TYPE, {content_type})\n    .body(serde_json::to_vec(body)?);"
+			),
+			Encoding::Form => "request = request.form(body);".into(),
+			Encoding::Multipart => format!(
+				"request = request.multipart({}(body)?);",
+				types.root_path("multipart_form")
+			),
+			Encoding::Text => format!(
+				"request = request\n    .header(reqwest::header::CONTENT_TYPE, {content_type})\n    .body(body.to_owned());"
+			),
+			Encoding::Bytes => format!(
+				"request = request\n    .header(reqwest::header::CONTENT_TYPE, {content_type})\n    .body(body.to_vec());"
+			),
+		});
+	}
+
+	statements
+}
+
+/// The arms decoding the response of every outcome, by status code.
+fn match_arms(operation: &Operation<'_>, error: &str) -> Result<String, fmt::Error> {
+	let success_ty = operation.success_ty();
+	let is_enum = success_ty == format!("{}Response", operation.prefix);
+
+	let mut arms = String::new();
+	for outcome in &operation.outcomes {
+		let decoded = outcome.body.map(|body| match Encoding::of(body) {
+			Encoding::Text => "response.text().await?",
+			Encoding::Bytes => "response.bytes().await?.to_vec()",
+			_ => "response.json().await?",
+		});
+
+		let value = if outcome.is_success() && !is_enum {
+			decoded.unwrap_or("()").to_owned()
+		} else {
+			let ty = if outcome.is_success() {
+				&success_ty
+			} else {
+				&operation.error_ty()
+			};
+			let variant = &outcome.variant;
+			match (decoded, outcome.has_status()) {
+				(None, false) => format!("{ty}::{variant}"),
+				(Some(decoded), false) => format!("{ty}::{variant}({decoded})"),
+				(None, true) => format!("{ty}::{variant} {{ status }}"),
+				(Some(decoded), true) => {
+					format!("{ty}::{variant} {{\n    status,\n    body: {decoded},\n}}")
+				}
+			}
+		};
+
+		let result = if outcome.is_success() {
+			format!("Ok({value})")
+		} else {
+			format!("Err({error}::Api({value}))")
+		};
+		writeln!(arms, "{} => {result},", outcome.pattern())?;
+	}
+
+	if !operation
+		.outcomes
+		.iter()
+		.any(|outcome| outcome.pattern() == "_")
+	{
+		writeln!(arms, "_ => Err({error}::UnexpectedStatus(status)),")?;
+	}
+	Ok(arms)
+}
+
+/// Writes the method giving the client of a child scope.
+fn write_accessor(out: &mut String, types: &Types<'_, '_>, child: &Module<'_>) -> fmt::Result {
+	let name = child.name();
+	let root = if types.depth == 0 {
+		"self"
+	} else {
+		"self.root"
+	};
+
+	if let Some(docs) = &child.docs {
+		write_docs(out, "", docs)?;
+	}
+	writeln!(out, "pub const fn {name}(&self) -> {name}::Client<'_> {{")?;
+	writeln!(out, "    {name}::Client {{ root: {root} }}")?;
+	writeln!(out, "}}")
+}
@@ -0,0 +1,10 @@
+use dapic_macros::IntoDiagnostic;
+use dapic_session::Ident;
+
+#[derive(Debug, IntoDiagnostic)]
+#[severity(Warning)]
+#[message("the server cannot route the custom verb `{verb}`")]
+pub struct UnroutableVerb {
+	#[label("axum only routes the methods of the HTTP spec, this operation is left out")]
+	pub verb: Ident,
+}
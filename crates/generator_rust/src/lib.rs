@@ -0,0 +1,318 @@
+//! Declarative API Rust generator
+//!
+//! Entrypoints are [`generate_rust_client`] and [`generate_rust_server`].
+//! Both write a single module file: the definitions become `serde` types of a
+//! `types` module and every `scope` a module of the same name, in both the
+//! `types` module and the operations.
+
+use crate::{
+	errors::UnroutableVerb,
+	operation::{Encoding, is_standard_method},
+	text::{indent, snake_case, write_docs},
+	types::Types,
+};
+use dapic_generator_common::write_string;
+use dapic_hir::types::{self as hir, DefId, ItemKind};
+use dapic_session::{DiagnosticsHandler, Ident, Symbol};
+use std::fmt::{self, Write};
+
+mod client;
+mod errors;
+mod operation;
+mod server;
+mod text;
+mod types;
+
+const CLIENT_RUNTIME: &str = include_str!("assets/client.rs");
+const MULTIPART_RUNTIME: &str = include_str!("assets/multipart.rs");
+const TO_TEXT_RUNTIME: &str = include_str!("assets/to_text.rs");
+const SERVER_RUNTIME: &str = include_str!("assets/server.rs");
+const HEADERS_RUNTIME: &str = include_str!("assets/headers.rs");
+
+/// Writes a client module with an async method per operation.
+///
+/// The module needs the `reqwest` crate with the `json` feature, `serde`
+/// with the `derive` feature and `serde_json`. Operations with multipart
+/// bodies also need the `multipart` feature.
+#[must_use]
+pub fn generate_rust_client(root: &hir::Root<'_>) -> String {
+	write_string(|out| write_client(out, root))
+}
+
+/// Writes a server module with an `Api` trait to implement, having a method
+/// per operation, and a `router` function serving it with `axum`.
+///
+/// The module needs the `axum` crate 0.8 with its default features, `serde`
+/// with the `derive` feature and `serde_json`. Operations with multipart
+/// bodies also need the `multipart` feature of `axum`.
+///
+/// `axum` only routes the methods of the HTTP spec, operations with a custom
+/// verb are left out with a warning.
+#[must_use]
+pub fn generate_rust_server(root: &hir::Root<'_>, diagnostics: &DiagnosticsHandler) -> String {
+	write_string(|out| write_server(out, root, diagnostics))
+}
+
+/// Whether an operation has a multipart body.
+fn has_multipart(endpoints: &[hir::Endpoint<'_>]) -> bool {
+	endpoints.iter().any(|endpoint| {
+		endpoint
+			.bodies()
+			.next()
+			.is_some_and(|(_, body)| Encoding::of(body) == Encoding::Multipart)
+	})
+}
+
+fn write_client(out: &mut String, root: &hir::Root<'_>) -> fmt::Result {
+	let endpoints = root.endpoints();
+	let has_multipart = has_multipart(&endpoints);
+	let has_text = endpoints
+		.iter()
+		.any(|endpoint| endpoint.params().next().is_some() || endpoint.headers().next().is_some());
+
+	let mut requirements = "Requires `reqwest` with the `json` feature, `serde` with the `derive` \
+	                        feature and `serde_json`."
+		.to_owned();
+	if has_multipart {
+		requirements.push_str(" Multipart bodies need the `multipart` feature of `reqwest`.");
+	}
+	write_crate_docs(out, root, &requirements)?;
+
+	write!(out, "{CLIENT_RUNTIME}")?;
+	if has_text {
+		write!(out, "{TO_TEXT_RUNTIME}")?;
+	}
+	if has_multipart {
+		write!(out, "{MULTIPART_RUNTIME}")?;
+	}
+
+	let tree = Module::tree(root, &endpoints);
+	let mut types = Types::new(root);
+	write_types(out, &mut types, &tree)?;
+	writeln!(out)?;
+	write_module(out, &mut types, &tree, &client::write_scope)
+}
+
+fn write_server(
+	out: &mut String,
+	root: &hir::Root<'_>,
+	diagnostics: &DiagnosticsHandler,
+) -> fmt::Result {
+	let (endpoints, custom): (Vec<_>, Vec<_>) =
+		root.endpoints().into_iter().partition(is_standard_method);
+	for endpoint in custom {
+		diagnostics.emit(UnroutableVerb {
+			verb: endpoint.verb.method,
+		});
+	}
+
+	let has_multipart = has_multipart(&endpoints);
+	let has_headers = endpoints
+		.iter()
+		.any(|endpoint| endpoint.headers().next().is_some());
+
+	let mut requirements =
+		"Requires `axum` 0.8, `serde` with the `derive` feature and `serde_json`.".to_owned();
+	if has_multipart {
+		requirements.push_str(" Multipart bodies need the `multipart` feature of `axum`.");
+	}
+	write_crate_docs(out, root, &requirements)?;
+
+	write!(out, "{SERVER_RUNTIME}")?;
+	if has_headers {
+		write!(out, "{HEADERS_RUNTIME}")?;
+	}
+
+	let tree = Module::tree(root, &endpoints);
+	let mut types = Types::new(root);
+	write_types(out, &mut types, &tree)?;
+	writeln!(out)?;
+	server::write_trait(out, &endpoints)?;
+	writeln!(out)?;
+	write_module(out, &mut types, &tree, &server::write_scope)
+}
+
+/// A `scope` with the definitions and endpoints declared directly in it.
+struct Module<'tcx> {
+	/// `None` for the api root
+	ident: Option<Ident>,
+	docs: Option<String>,
+	defs: Vec<DefId>,
+	endpoints: Vec<hir::Endpoint<'tcx>>,
+	children: Vec<Self>,
+}
+
+impl<'tcx> Module<'tcx> {
+	/// Modules of the api with its definitions and the given endpoints.
+	fn tree(root: &hir::Root<'tcx>, endpoints: &[hir::Endpoint<'tcx>]) -> Self {
+		fn collect<'tcx>(
+			ident: Option<Ident>,
+			docs: Option<String>,
+			items: &[hir::Item<'_>],
+		) -> Module<'tcx> {
+			let children = items
+				.iter()
+				.filter_map(|item| match &item.kind {
+					ItemKind::Scope(scope) => Some(collect(
+						Some(item.ident),
+						hir::docs(item.attrs),
+						scope.items,
+					)),
+					_ => None,
+				})
+				.collect();
+
+			Module {
+				ident,
+				docs,
+				defs: Vec::new(),
+				endpoints: Vec::new(),
+				children,
+			}
+		}
+
+		let mut tree = collect(None, None, root.items());
+
+		for (def_id, def) in root.defs() {
+			if def.is_concrete_type() {
+				let mod_path = symbols(def.scopes());
+				if let Some(module) = tree.find(&mod_path) {
+					module.defs.push(def_id);
+				}
+			}
+		}
+
+		for endpoint in endpoints {
+			if let Some(module) = tree.find(&symbols(&endpoint.mod_path)) {
+				module.endpoints.push(endpoint.clone());
+			}
+		}
+
+		tree
+	}
+
+	fn find(&mut self, mod_path: &[Symbol]) -> Option<&mut Self> {
+		match mod_path {
+			[] => Some(self),
+			[first, rest @ ..] => self
+				.children
+				.iter_mut()
+				.find(|child| child.ident.is_some_and(|ident| ident.symbol == *first))?
+				.find(rest),
+		}
+	}
+
+	/// Whether the scope or one of its children has endpoints.
+	fn has_endpoints(&self) -> bool {
+		!self.endpoints.is_empty() || self.children.iter().any(Self::has_endpoints)
+	}
+
+	/// Name of the module, e.g. `pet`
+	fn name(&self) -> String {
+		self.ident
+			.map_or_else(String::new, |ident| snake_case(ident.symbol.as_str()))
+	}
+}
+
+fn symbols(idents: &[Ident]) -> Vec<Symbol> {
+	idents.iter().map(|ident| ident.symbol).collect()
+}
+
+/// Writes the inner documentation of the file: the api name and version,
+/// its docs and what the generated code needs.
+fn write_crate_docs(out: &mut String, root: &hir::Root<'_>, requirements: &str) -> fmt::Result {
+	let meta = root.meta();
+
+	let mut docs = Vec::new();
+	match (meta.name, meta.version) {
+		(Some(name), Some(version)) => docs.push(format!("{name} {version}")),
+		(Some(name), None) => docs.push(name.to_string()),
+		(None, Some(version)) => docs.push(format!("Version {version}")),
+		(None, None) => {}
+	}
+	docs.extend(hir::docs(root.attrs));
+	docs.extend(meta.description.map(|description| description.to_string()));
+	docs.push(format!("Generated by dapic, do not edit. {requirements}"));
+
+	for line in docs.join("\n\n").lines() {
+		if line.is_empty() {
+			writeln!(out, "//!")?;
+		} else {
+			writeln!(out, "//! {line}")?;
+		}
+	}
+	writeln!(out)
+}
+
+/// Writes the `types` module, definitions are kept apart so that their names
+/// cannot collide with the generated code.
+fn write_types<'tcx>(
+	out: &mut String,
+	types: &mut Types<'_, 'tcx>,
+	tree: &Module<'tcx>,
+) -> fmt::Result {
+	let mut body = String::new();
+	types.depth += 1;
+	write_module(&mut body, types, tree, &|out, types, module| {
+		let mut chunks = Vec::new();
+		for &def_id in &module.defs {
+			let mut chunk = String::new();
+			types.write_definition(&mut chunk, def_id)?;
+			chunks.push(chunk);
+		}
+		write!(out, "{}", chunks.join("\n"))
+	})?;
+	types.depth -= 1;
+
+	if body.is_empty() {
+		return Ok(());
+	}
+	writeln!(out)?;
+	writeln!(out, "pub mod types {{")?;
+	write!(out, "{}", indent(body.trim_end()))?;
+	writeln!(out, "}}")
+}
+
+/// Writes what a generator emits in a module.
+type WriteScope<'tcx> = dyn Fn(&mut String, &mut Types<'_, 'tcx>, &Module<'tcx>) -> fmt::Result;
+
+/// Writes what `write_scope` emits for the module, followed by the modules of
+/// the child scopes. Modules left empty are skipped.
+fn write_module<'tcx>(
+	out: &mut String,
+	types: &mut Types<'_, 'tcx>,
+	module: &Module<'tcx>,
+	write_scope: &WriteScope<'tcx>,
+) -> fmt::Result {
+	let mut chunks = Vec::new();
+
+	let mut chunk = String::new();
+	write_scope(&mut chunk, types, module)?;
+	if !chunk.is_empty() {
+		chunks.push(chunk);
+	}
+
+	for child in &module.children {
+		let mut body = String::new();
+		types.depth += 1;
+		write_module(&mut body, types, child, write_scope)?;
+		types.depth -= 1;
+		if body.is_empty() {
+			continue;
+		}
+
+		let mut chunk = String::new();
+		if let Some(docs) = &child.docs {
+			write_docs(&mut chunk, "", docs)?;
+		}
+		writeln!(chunk, "pub mod {} {{", child.name())?;
+		write!(chunk, "{}", indent(body.trim_end()))?;
+		writeln!(chunk, "}}")?;
+		chunks.push(chunk);
+	}
+
+	write!(out, "{}", chunks.join("\n"))
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,214 @@
+use crate::{
+	text::{indent, pascal_case, snake_case, write_docs},
+	types::Types,
+};
+use dapic_generator_common::invalid_hir;
+use dapic_hir::types::{self as hir, MediaType, Res, Status, TyKind, docs};
+use dapic_session::{PrimTy, symbols::remarkable};
+use std::fmt::{self, Write};
+
+/// How a body is put on the wire, from its first media type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Encoding {
+	Json,
+	Form,
+	Multipart,
+	/// A `string` body, e.g. `text/plain`
+	Text,
+	/// A `binary` body, e.g. `application/octet-stream`
+	Bytes,
+}
+
+impl Encoding {
+	pub(crate) const fn of(body: &hir::Body<'_>) -> Self {
+		let prim = match body.ty.kind {
+			TyKind::Path(_, Res::PrimTy(prim)) => Some(prim),
+			_ => None,
+		};
+
+		match (body.media_types.first(), prim) {
+			(Some(MediaType::Form), _) => Self::Form,
+			(Some(MediaType::Multipart), _) => Self::Multipart,
+			(_, Some(PrimTy::Binary)) => Self::Bytes,
+			(Some(MediaType::Text | MediaType::Mime(_)), Some(PrimTy::String)) => Self::Text,
+			_ => Self::Json,
+		}
+	}
+}
+
+/// A `code` item of an operation, as a variant of its response or error
+/// enum.
+pub(crate) struct Outcome<'tcx> {
+	/// e.g. `NotFound` for `404`, `ClientError` for `~4xx`
+	pub(crate) variant: String,
+	pub(crate) status: Status,
+	pub(crate) docs: Option<String>,
+	/// The first body, operations have a single type per status
+	pub(crate) body: Option<&'tcx hir::Body<'tcx>>,
+	/// Rust type of the body
+	pub(crate) ty: Option<String>,
+}
+
+impl Outcome<'_> {
+	pub(crate) const fn is_success(&self) -> bool {
+		matches!(self.status, Status::Code(200..=299) | Status::Range(2))
+	}
+
+	/// Ranges and `default` responses also hold their status code.
+	pub(crate) const fn has_status(&self) -> bool {
+		matches!(self.status, Status::Range(_) | Status::Default)
+	}
+
+	/// Pattern matching the status codes of the outcome.
+	pub(crate) fn pattern(&self) -> String {
+		match self.status {
+			Status::Code(code) => code.to_string(),
+			Status::Range(class) => format!("{class}00..={class}99"),
+			Status::Default => "_".into(),
+			Status::Err => invalid_hir(),
+		}
+	}
+}
+
+/// The `code` items of an operation with specific codes first, then ranges
+/// and `default` last, the order they are matched in.
+pub(crate) fn outcomes<'tcx>(
+	types: &mut Types<'_, 'tcx>,
+	endpoint: &hir::Endpoint<'tcx>,
+) -> Vec<Outcome<'tcx>> {
+	let root = types.root;
+	let prefix = type_prefix(endpoint);
+	let mut outcomes = endpoint
+		.responses()
+		.map(|(item, code)| {
+			// Shared responses are documented on their definition
+			let attrs = code
+				.def
+				.map_or(item.attrs, |def_id| root.def(def_id).item.attrs);
+			let variant = match code.status {
+				Status::Default => "Default".into(),
+				status => status
+					.reason()
+					.map_or_else(|| format!("Status{status}"), pascal_case),
+			};
+
+			let body = code.bodies().next().map(|(_, body)| body);
+			let ty = body.map(|body| match Encoding::of(body) {
+				Encoding::Text => "String".into(),
+				Encoding::Bytes => "Vec<u8>".into(),
+				_ => types.ty_to_rust(body.ty, &format!("{prefix}{variant}Body")),
+			});
+
+			Outcome {
+				variant,
+				status: code.status,
+				docs: docs(attrs).or_else(|| code.status.reason().map(ToOwned::to_owned)),
+				body,
+				ty,
+			}
+		})
+		.collect::<Vec<_>>();
+
+	outcomes.sort_by_key(|outcome| match outcome.status {
+		Status::Code(_) | Status::Err => 0,
+		Status::Range(_) => 1,
+		Status::Default => 2,
+	});
+	outcomes
+}
+
+/// e.g. `get_pet_by_pet_id`
+pub(crate) fn method_name(endpoint: &hir::Endpoint<'_>) -> String {
+	snake_case(&endpoint.operation_id())
+}
+
+/// Prefix of the types of an operation, e.g. `GetPetByPetId`.
+pub(crate) fn type_prefix(endpoint: &hir::Endpoint<'_>) -> String {
+	pascal_case(&endpoint.operation_id())
+}
+
+/// Whether the verb is a method of the HTTP spec, custom verbs have no
+/// constant in `http`.
+pub(crate) fn is_standard_method(endpoint: &hir::Endpoint<'_>) -> bool {
+	use remarkable::{Connect, Delete, Get, Head, Options, Patch, Post, Put, Trace};
+	[Connect, Delete, Get, Head, Options, Patch, Post, Put, Trace]
+		.contains(&endpoint.verb.method.symbol)
+}
+
+/// Writes the documentation of an operation followed by its method and path.
+pub(crate) fn write_operation_docs(
+	out: &mut String,
+	indent: &str,
+	endpoint: &hir::Endpoint<'_>,
+) -> fmt::Result {
+	let mut docs = docs(endpoint.item.attrs).unwrap_or_default();
+	if !docs.is_empty() {
+		docs.push_str("\n\n");
+	}
+	write!(docs, "`{} {}`", endpoint.verb.method, endpoint.path())?;
+	write_docs(out, indent, &docs)
+}
+
+/// Writes the `Query` and `Headers` structs of the operation as chunks,
+/// `method` is the path of the method taking them.
+pub(crate) fn write_input_structs<'tcx>(
+	chunks: &mut Vec<String>,
+	types: &mut Types<'_, 'tcx>,
+	endpoint: &hir::Endpoint<'tcx>,
+	method: &str,
+) -> fmt::Result {
+	let prefix = type_prefix(endpoint);
+
+	if endpoint.query().next().is_some() {
+		let mut chunk = String::new();
+		writeln!(chunk, "/// Query parameters of [`{method}`].")?;
+		types.write_struct(
+			&mut chunk,
+			&format!("{prefix}Query"),
+			None,
+			endpoint.query(),
+		)?;
+		chunks.push(chunk);
+	}
+
+	if endpoint.headers().next().is_some() {
+		let mut chunk = String::new();
+		writeln!(chunk, "/// Headers of [`{method}`].")?;
+		types.write_struct(
+			&mut chunk,
+			&format!("{prefix}Headers"),
+			None,
+			endpoint.headers(),
+		)?;
+		chunks.push(chunk);
+	}
+
+	Ok(())
+}
+
+/// Writes an enum with a variant per outcome, ranges and `default` ones hold
+/// their status code.
+pub(crate) fn write_outcome_enum(
+	out: &mut String,
+	name: &str,
+	outcomes: &[&Outcome<'_>],
+) -> fmt::Result {
+	let mut variants = String::new();
+	for outcome in outcomes {
+		if let Some(docs) = &outcome.docs {
+			write_docs(&mut variants, "", docs)?;
+		}
+		let variant = &outcome.variant;
+		match (&outcome.ty, outcome.has_status()) {
+			(None, false) => writeln!(variants, "{variant},")?,
+			(Some(ty), false) => writeln!(variants, "{variant}({ty}),")?,
+			(None, true) => writeln!(variants, "{variant} {{ status: u16 }},")?,
+			(Some(ty), true) => writeln!(variants, "{variant} {{ status: u16, body: {ty} }},")?,
+		}
+	}
+
+	writeln!(out, "#[derive(Debug, Clone, PartialEq)]")?;
+	writeln!(out, "pub enum {name} {{")?;
+	write!(out, "{}", indent(&variants))?;
+	writeln!(out, "}}")
+}
@@ -0,0 +1,447 @@
+use crate::{
+	Module,
+	operation::{
+		Encoding, Outcome, method_name, outcomes, type_prefix, write_input_structs,
+		write_operation_docs, write_outcome_enum,
+	},
+	text::{indent, pascal_case, snake_case, string_literal, write_docs, write_signature},
+	types::Types,
+};
+use dapic_hir::types::{self as hir, MediaType, Status, description};
+use std::fmt::{self, Write};
+
+/// An operation with the Rust types of its inputs.
+struct Operation<'tcx> {
+	endpoint: hir::Endpoint<'tcx>,
+	prefix: String,
+	/// Path parameters as their field name and type
+	params: Vec<(String, String)>,
+	has_query: bool,
+	has_headers: bool,
+	/// Type and encoding of the first body
+	body: Option<(String, Encoding)>,
+}
+
+/// Whether the operation takes a `Request`, i.e. it has inputs.
+fn has_request(endpoint: &hir::Endpoint<'_>) -> bool {
+	endpoint.params().next().is_some()
+		|| endpoint.query().next().is_some()
+		|| endpoint.headers().next().is_some()
+		|| endpoint.bodies().next().is_some()
+}
+
+/// Writes the `Api` trait with a method per operation of the api, taking
+/// its `Request` and giving its `Response`.
+pub(crate) fn write_trait(out: &mut String, endpoints: &[hir::Endpoint<'_>]) -> fmt::Result {
+	let mut methods = Vec::new();
+	for endpoint in endpoints {
+		// The types of an operation are in the module of its scope
+		let mut module = String::new();
+		for ident in &endpoint.mod_path {
+			module.push_str(&snake_case(ident.symbol.as_str()));
+			module.push_str("::");
+		}
+		let prefix = type_prefix(endpoint);
+
+		let mut arguments = vec!["&self".to_owned()];
+		if has_request(endpoint) {
+			arguments.push(format!("request: {module}{prefix}Request"));
+		}
+
+		let mut method = String::new();
+		write_operation_docs(&mut method, "", endpoint)?;
+		write_signature(
+			&mut method,
+			4,
+			&format!("fn {}", method_name(endpoint)),
+			&arguments,
+			&format!("impl std::future::Future<Output = {module}{prefix}Response> + Send"),
+		)?;
+		// Trait methods end with a semicolon instead of a body
+		method.truncate(method.trim_end().len() - 2);
+		method.push_str(";\n");
+		methods.push(method);
+	}
+
+	writeln!(out, "/// The operations of the api, served by [`router`].")?;
+	writeln!(out, "pub trait Api: Send + Sync + 'static {{")?;
+	write!(out, "{}", indent(&methods.join("\n")))?;
+	writeln!(out, "}}")?;
+	writeln!(out)?;
+	writeln!(out, "/// Routes every operation to its method of `api`.")?;
+	writeln!(out, "pub fn router<A: Api>(api: A) -> axum::Router {{")?;
+	writeln!(
+		out,
+		"    routes(axum::Router::new()).with_state(std::sync::Arc::new(api))"
+	)?;
+	writeln!(out, "}}")
+}
+
+/// Writes the types and handlers of the operations of the scope, followed
+/// by the function adding their routes to a router.
+pub(crate) fn write_scope<'tcx>(
+	out: &mut String,
+	types: &mut Types<'_, 'tcx>,
+	module: &Module<'tcx>,
+) -> fmt::Result {
+	// The router of the api always adds the routes of the root
+	if types.depth > 0 && !module.has_endpoints() {
+		return Ok(());
+	}
+
+	let mut chunks = Vec::new();
+	let mut operations = Vec::new();
+	for endpoint in &module.endpoints {
+		let mut chunk = String::new();
+		operations.push(write_operation_types(&mut chunk, types, endpoint)?);
+		if !chunk.is_empty() {
+			chunks.push(chunk);
+		}
+	}
+
+	for operation in &operations {
+		let mut chunk = String::new();
+		write_handler(&mut chunk, types, operation)?;
+		chunks.push(chunk);
+	}
+
+	let mut chunk = String::new();
+	write_routes(&mut chunk, types, module, &operations)?;
+	chunks.push(chunk);
+
+	write!(out, "{}", chunks.join("\n"))
+}
+
+/// Writes the function adding the routes of the operations of the scope and
+/// of its children.
+fn write_routes(
+	out: &mut String,
+	types: &Types<'_, '_>,
+	module: &Module<'_>,
+	operations: &[Operation<'_>],
+) -> fmt::Result {
+	let mut children = module
+		.children
+		.iter()
+		.filter(|child| child.has_endpoints())
+		.map(|child| format!("{}::routes(router)", child.name()))
+		.collect::<Vec<_>>();
+	let tail = if operations.is_empty() {
+		children.pop().unwrap_or_else(|| "router".into())
+	} else {
+		let mut tail = String::from("router");
+		for operation in operations {
+			let endpoint = &operation.endpoint;
+			write!(
+				tail,
+				"\n    .route({}, axum::routing::on(axum::routing::MethodFilter::{}, {}))",
+				string_literal(&endpoint.path()),
+				endpoint.verb.method.symbol.as_str(),
+				method_name(endpoint)
+			)?;
+		}
+		tail
+	};
+
+	let mut body = String::new();
+	for child in children {
+		writeln!(body, "let router = {child};")?;
+	}
+	writeln!(body, "{tail}")?;
+
+	let router = "axum::Router<std::sync::Arc<A>>";
+	// Child scopes add their routes from the module of their parent
+	let visibility = if types.depth == 0 { "" } else { "pub(super) " };
+	writeln!(out, "/// Adds the routes of the scope and its children.")?;
+	write_signature(
+		out,
+		4 * types.depth,
+		&format!("{visibility}fn routes<A: {}>", types.root_path("Api")),
+		&[format!("router: {router}")],
+		router,
+	)?;
+	write!(out, "{}", indent(&body))?;
+	writeln!(out, "}}")
+}
+
+/// Writes the query, headers, request and response types of the operation.
+fn write_operation_types<'tcx>(
+	out: &mut String,
+	types: &mut Types<'_, 'tcx>,
+	endpoint: &hir::Endpoint<'tcx>,
+) -> Result<Operation<'tcx>, fmt::Error> {
+	let prefix = type_prefix(endpoint);
+	let method = types.root_path(&format!("Api::{}", method_name(endpoint)));
+	let mut chunks = Vec::new();
+
+	let mut fields = String::new();
+	let mut params = Vec::new();
+	for field in endpoint.params() {
+		let name = field.ident.symbol.as_str();
+		let ident = snake_case(name);
+		let ty = types.ty_to_rust(field.ty, &format!("{prefix}{}", pascal_case(name)));
+		if let Some(docs) = description(field.attrs) {
+			write_docs(&mut fields, "", &docs)?;
+		}
+		writeln!(fields, "pub {ident}: {ty},")?;
+		params.push((ident, ty));
+	}
+
+	let has_query = endpoint.query().next().is_some();
+	let has_headers = endpoint.headers().next().is_some();
+	write_input_structs(&mut chunks, types, endpoint, &method)?;
+	if has_query {
+		writeln!(fields, "pub query: {prefix}Query,")?;
+	}
+	if has_headers {
+		writeln!(fields, "pub headers: {prefix}Headers,")?;
+	}
+
+	let body = endpoint.bodies().next().map(|(_, body)| {
+		let encoding = Encoding::of(body);
+		let ty = match encoding {
+			Encoding::Text => "String".into(),
+			Encoding::Bytes => "Vec<u8>".into(),
+			// Parts are read as they are received
+			Encoding::Multipart => "axum::extract::Multipart".into(),
+			Encoding::Json | Encoding::Form => types.ty_to_rust(body.ty, &format!("{prefix}Body")),
+		};
+		(ty, encoding)
+	});
+	if let Some((ty, _)) = &body {
+		writeln!(fields, "pub body: {ty},")?;
+	}
+
+	let operation = Operation {
+		endpoint: endpoint.clone(),
+		prefix,
+		params,
+		has_query,
+		has_headers,
+		body,
+	};
+
+	if has_request(&operation.endpoint) {
+		let mut chunk = String::new();
+		writeln!(chunk, "/// Request of [`{method}`].")?;
+		writeln!(chunk, "#[derive(Debug)]")?;
+		writeln!(chunk, "pub struct {}Request {{", operation.prefix)?;
+		write!(chunk, "{}", indent(&fields))?;
+		writeln!(chunk, "}}")?;
+		chunks.push(chunk);
+	}
+
+	let outcomes = outcomes(types, endpoint);
+	let name = format!("{}Response", operation.prefix);
+	let mut chunk = String::new();
+	writeln!(chunk, "/// Responses of [`{method}`].")?;
+	write_outcome_enum(&mut chunk, &name, &outcomes.iter().collect::<Vec<_>>())?;
+	writeln!(chunk)?;
+	write_into_response(&mut chunk, types, &name, &outcomes)?;
+	chunks.push(chunk);
+
+	let mut pending = String::new();
+	types.write_pending(&mut pending)?;
+	if !pending.is_empty() {
+		chunks.push(pending.trim_start().to_owned());
+	}
+
+	write!(out, "{}", chunks.join("\n"))?;
+	Ok(operation)
+}
+
+/// Writes the `IntoResponse` implementation of a response enum, bodies are
+/// encoded with their first media type.
+fn write_into_response(
+	out: &mut String,
+	types: &Types<'_, '_>,
+	name: &str,
+	outcomes: &[Outcome<'_>],
+) -> fmt::Result {
+	let status = types.root_path("status");
+
+	let mut arms = String::new();
+	for outcome in outcomes {
+		let mut parts = vec![match outcome.status {
+			Status::Code(code) => format!("{status}({code})"),
+			_ => format!("{status}(status)"),
+		}];
+		if let Some(body) = outcome.body {
+			let encoding = Encoding::of(body);
+			let mime = body.media_types.first().map(MediaType::mime);
+			// The content type set by the encoding is the default one
+			let default = match encoding {
+				Encoding::Text => "text/plain",
+				Encoding::Bytes => "application/octet-stream",
+				Encoding::Form => "application/x-www-form-urlencoded",
+				Encoding::Json | Encoding::Multipart => "application/json",
+			};
+			if let Some(mime) = mime.filter(|&mime| mime != default) {
+				parts.push(format!(
+					"[(axum::http::header::CONTENT_TYPE, {})]",
+					string_literal(mime)
+				));
+			}
+			parts.push(match encoding {
+				Encoding::Text | Encoding::Bytes => "body".into(),
+				Encoding::Form => "axum::Form(body)".into(),
+				Encoding::Json | Encoding::Multipart => "axum::Json(body)".into(),
+			});
+		}
+
+		let variant = &outcome.variant;
+		let pattern = match (outcome.body.is_some(), outcome.has_status()) {
+			(false, false) => format!("Self::{variant}"),
+			(true, false) => format!("Self::{variant}(body)"),
+			(false, true) => format!("Self::{variant} {{ status }}"),
+			(true, true) => format!("Self::{variant} {{ status, body }}"),
+		};
+		let response = match parts.as_slice() {
+			[status] => format!("{status}.into_response()"),
+			_ => format!("({}).into_response()", parts.join(", ")),
+		};
+		// Arms are nested three levels deep in the module
+		let arm = format!("{pattern} => {response},");
+		if 4 * (types.depth + 3) + arm.len() <= 100 {
+			writeln!(arms, "{arm}")?;
+		} else {
+			writeln!(arms, "{pattern} => {{\n    {response}\n}}")?;
+		}
+	}
+
+	writeln!(out, "impl axum::response::IntoResponse for {name} {{")?;
+	writeln!(
+		out,
+		"    fn into_response(self) -> axum::response::Response {{"
+	)?;
+	writeln!(out, "        match self {{")?;
+	write!(out, "{}", indent(&indent(&indent(&arms))))?;
+	writeln!(out, "        }}")?;
+	writeln!(out, "    }}")?;
+	writeln!(out, "}}")
+}
+
+/// Writes the handler of the operation, extracting its request and calling
+/// its method of the api.
+fn write_handler(
+	out: &mut String,
+	types: &Types<'_, '_>,
+	operation: &Operation<'_>,
+) -> fmt::Result {
+	let endpoint = &operation.endpoint;
+	let prefix = &operation.prefix;
+	let name = method_name(endpoint);
+
+	// Invalid headers are rejected before calling the api
+	let output = if operation.has_headers {
+		format!("Result<{prefix}Response, axum::response::Response>")
+	} else {
+		format!("{prefix}Response")
+	};
+	write_signature(
+		out,
+		4 * types.depth,
+		&format!("async fn {name}<A: {}>", types.root_path("Api")),
+		&extractors(operation),
+		&output,
+	)?;
+
+	let mut body = String::new();
+	if operation.has_headers {
+		writeln!(body, "let headers = {prefix}Headers {{")?;
+		for field in endpoint.headers() {
+			let function = if field.required() {
+				"header"
+			} else {
+				"optional_header"
+			};
+			writeln!(
+				body,
+				"    {}: {}(&headers, {})?,",
+				snake_case(field.ident.symbol.as_str()),
+				types.root_path(function),
+				string_literal(field.ident.symbol.as_str())
+			)?;
+		}
+		writeln!(body, "}};")?;
+	}
+
+	let call = if has_request(&operation.endpoint) {
+		let mut fields = operation
+			.params
+			.iter()
+			.map(|(ident, _)| ident.clone())
+			.collect::<Vec<_>>();
+		if operation.has_query {
+			fields.push("query".into());
+		}
+		if operation.has_headers {
+			fields.push("headers".into());
+		}
+		match &operation.body {
+			Some((_, Encoding::Bytes)) => fields.push("body: body.to_vec()".into()),
+			Some(_) => fields.push("body".into()),
+			None => {}
+		}
+		writeln!(
+			body,
+			"let request = {prefix}Request {{ {} }};",
+			fields.join(", ")
+		)?;
+		format!("api.{name}(request).await")
+	} else {
+		format!("api.{name}().await")
+	};
+	if operation.has_headers {
+		writeln!(body, "Ok({call})")?;
+	} else {
+		writeln!(body, "{call}")?;
+	}
+
+	write!(out, "{}", indent(&body))?;
+	writeln!(out, "}}")
+}
+
+/// The arguments of the handler of the operation, extracting its inputs.
+fn extractors(operation: &Operation<'_>) -> Vec<String> {
+	let prefix = &operation.prefix;
+	let mut arguments =
+		vec!["axum::extract::State(api): axum::extract::State<std::sync::Arc<A>>".to_owned()];
+	match operation.params.as_slice() {
+		[] => {}
+		[(ident, ty)] => {
+			arguments.push(format!(
+				"axum::extract::Path({ident}): axum::extract::Path<{ty}>"
+			));
+		}
+		params => {
+			let (idents, tys): (Vec<_>, Vec<_>) = params
+				.iter()
+				.map(|(ident, ty)| (ident.as_str(), ty.as_str()))
+				.unzip();
+			arguments.push(format!(
+				"axum::extract::Path(({})): axum::extract::Path<({})>",
+				idents.join(", "),
+				tys.join(", ")
+			));
+		}
+	}
+	if operation.has_query {
+		arguments.push(format!(
+			"axum::extract::Query(query): axum::extract::Query<{prefix}Query>"
+		));
+	}
+	if operation.has_headers {
+		arguments.push("headers: axum::http::HeaderMap".into());
+	}
+	if let Some((ty, encoding)) = &operation.body {
+		// The body is extracted last as it consumes the request
+		arguments.push(match encoding {
+			Encoding::Json => format!("axum::Json(body): axum::Json<{ty}>"),
+			Encoding::Form => format!("axum::Form(body): axum::Form<{ty}>"),
+			Encoding::Text | Encoding::Multipart => format!("body: {ty}"),
+			Encoding::Bytes => "body: axum::body::Bytes".into(),
+		});
+	}
+	arguments
+}
@@ -0,0 +1,179 @@
+use crate::{generate_rust_client, generate_rust_server};
+use dapic_ast::visit_mut::MutVisitor;
+use dapic_expand::NodeExpander;
+use dapic_hir::{Arena, compile_hir};
+use dapic_parser::Parser;
+use dapic_session::{DiagnosticsHandler, Session};
+use std::{fs, process::Command};
+
+/// Lowers `src` and gives the HIR to `generate`, along with the handler of
+/// the session.
+fn generate(
+	src: &str,
+	generate: impl FnOnce(&dapic_hir::types::Root<'_>, &DiagnosticsHandler) -> String,
+) -> String {
+	let mut session = Session::default();
+
+	session.enter_source_map_ctx(|session| {
+		let sf = session.source_map.load_anon(src.into());
+		let mut ast = Parser::from_source(&session.parse_sess(), &sf)
+			.parse_root()
+			.expect("source should parse");
+		NodeExpander::default().visit_root(&mut ast);
+
+		let arena = Arena::new();
+		let hir = compile_hir(session, &arena, &ast);
+		assert!(!session.diagnostics.has_errors(), "source should lower");
+
+		generate(&hir, &session.diagnostics)
+	})
+}
+
+/// Type checks `module` as the root of a throwaway crate with `cargo check`,
+/// against the registry releases of `dependencies`, the lines of its
+/// `[dependencies]` table.
+fn assert_compiles(name: &str, module: &str, dependencies: &str) {
+	let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+	let dir = std::env::temp_dir().join(format!("dapic-{name}-{}", std::process::id()));
+	fs::create_dir_all(dir.join("src")).expect("temporary directory should be writable");
+
+	let manifest = format!(
+		"[package]\nname = \"dapic-{name}\"\nversion = \"0.0.0\"\nedition = \"2021\"\n\n\
+		 [dependencies]\n{dependencies}\n\n[workspace]\n"
+	);
+	fs::write(dir.join("Cargo.toml"), manifest).expect("temporary directory should be writable");
+	fs::write(dir.join("src/lib.rs"), module).expect("temporary directory should be writable");
+
+	let output = Command::new(cargo)
+		.args(["check", "--offline", "--quiet"])
+		.current_dir(&dir)
+		// Dependencies are only built once across runs
+		.env(
+			"CARGO_TARGET_DIR",
+			std::env::temp_dir().join("dapic-check-target"),
+		)
+		.output()
+		.expect("cargo should run");
+	assert!(
+		output.status.success(),
+		"`{name}` should compile:\n{}",
+		String::from_utf8_lossy(&output.stderr)
+	);
+
+	let _ = fs::remove_dir_all(&dir);
+}
+
+const CLIENT_DEPENDENCIES: &str = r#"
+reqwest = { version = "0.12", default-features = false, features = ["json", "multipart"] }
+serde = { version = "1", features = ["derive"] }
+serde_json = "1"
+"#;
+
+const SERVER_DEPENDENCIES: &str = r#"
+axum = { version = "0.8", features = ["multipart"] }
+serde = { version = "1", features = ["derive"] }
+serde_json = "1"
+"#;
+
+const PETSTORE: &str = r#"
+##! A sample pet store
+meta { name "Petstore" version "1.0.0" }
+
+## A failure
+model Error { message string }
+
+type Tags = [string] "Labels of a pet"
+
+scope pet {
+	model Base { id int kind string }
+
+	## Status of a pet
+	enum Status {
+		## Ready to be adopted
+		Available "available"
+		Sold "sold"
+	}
+
+	enum Priority : int { Low 1 High 2 }
+
+	model Pet : Base {
+		## Name of the pet
+		name string
+		status? Status
+		tags Tags = []
+		owner? { name string phone? string }
+		parent? Pet?
+		ranks {[Status]: Priority}
+	}
+
+	union Animal on kind { dog Pet cat Pet }
+
+	path {petId} {
+		params { petId int }
+
+		## Get a pet
+		verb GET {
+			query { limit? int = 20 }
+			headers { X-Request-Id string X-Trace? bool }
+			code 200 { body Pet }
+			code 404 NotFound;
+			code ~5xx { body Error }
+		}
+
+		verb PUT {
+			body Pet
+			code 201 { body Pet }
+			code 204 {}
+			code default { body Error }
+		}
+	}
+
+	path upload {
+		verb POST {
+			@@type: "binary"
+			body binary
+			code 200 {
+				@@type: "text"
+				body string
+			}
+		}
+
+		verb PUT {
+			@@type: "multipart"
+			body { name string "Name of the file" file binary }
+			code 204 {}
+		}
+
+		verb PATCH {
+			@@type: "form"
+			body { name string }
+			code 204 {}
+		}
+	}
+
+	path search {
+		## Finds pets by name
+		verb GETTTY {
+			query { name string }
+			code 200 { body [Pet] }
+		}
+	}
+}
+
+## The resource does not exist
+response NotFound { body Error }
+"#;
+
+#[test]
+fn generate_client() {
+	let client = generate(PETSTORE, |hir, _| generate_rust_client(hir));
+	assert_compiles("client", &client, CLIENT_DEPENDENCIES);
+	insta::assert_snapshot!(client);
+}
+
+#[test]
+fn generate_server() {
+	let server = generate(PETSTORE, generate_rust_server);
+	assert_compiles("server", &server, SERVER_DEPENDENCIES);
+	insta::assert_snapshot!(server);
+}
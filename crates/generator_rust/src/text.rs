@@ -0,0 +1,158 @@
+use std::fmt::{self, Write};
+
+/// Keywords which cannot be raw identifiers, they get a trailing underscore.
+const RESERVED: &[&str] = &["crate", "self", "Self", "super"];
+
+const KEYWORDS: &[&str] = &[
+	"abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+	"else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+	"loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+	"static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+	"virtual", "where", "while", "yield",
+];
+
+/// Splits a name in words on separators and case changes, e.g. `petId`,
+/// `pet_id` and `X-Pet-Id` have the words `pet` and `id`.
+fn words(name: &str) -> Vec<String> {
+	let chars = name.chars().collect::<Vec<_>>();
+	let mut words = Vec::new();
+	let mut word = String::new();
+
+	for (index, &char) in chars.iter().enumerate() {
+		if !char.is_ascii_alphanumeric() {
+			if !word.is_empty() {
+				words.push(std::mem::take(&mut word));
+			}
+			continue;
+		}
+
+		let previous = index.checked_sub(1).map(|index| chars[index]);
+		let next = chars.get(index + 1);
+		// e.g. `petId` or the `I` of `HTTPId`
+		let boundary = char.is_ascii_uppercase()
+			&& previous.is_some_and(|previous| {
+				previous.is_ascii_lowercase()
+					|| previous.is_ascii_digit()
+					|| (previous.is_ascii_uppercase() && next.is_some_and(char::is_ascii_lowercase))
+			});
+		if boundary && !word.is_empty() {
+			words.push(std::mem::take(&mut word));
+		}
+		word.push(char.to_ascii_lowercase());
+	}
+	if !word.is_empty() {
+		words.push(word);
+	}
+
+	words
+}
+
+/// Makes an identifier of a name, escaping keywords.
+fn identifier(name: String) -> String {
+	if name.is_empty() {
+		return "_".into();
+	}
+	if name.starts_with(|char: char| char.is_ascii_digit()) {
+		return format!("_{name}");
+	}
+	if RESERVED.contains(&name.as_str()) {
+		return format!("{name}_");
+	}
+	if KEYWORDS.contains(&name.as_str()) {
+		return format!("r#{name}");
+	}
+	name
+}
+
+/// e.g. `pet_id` for `petId`, used for fields, functions and modules.
+pub(crate) fn snake_case(name: &str) -> String {
+	identifier(words(name).join("_"))
+}
+
+/// e.g. `NotFound` for `Not Found`, used for types and variants.
+pub(crate) fn pascal_case(name: &str) -> String {
+	let words = words(name)
+		.into_iter()
+		.map(|word| {
+			let mut chars = word.chars();
+			chars.next().map_or_else(String::new, |first| {
+				first.to_ascii_uppercase().to_string() + chars.as_str()
+			})
+		})
+		.collect::<String>();
+	identifier(words)
+}
+
+/// Whether a field needs a `#[serde(rename)]` to keep its name on the wire.
+pub(crate) fn is_renamed(name: &str, ident: &str) -> bool {
+	ident.strip_prefix("r#").unwrap_or(ident) != name
+}
+
+pub(crate) fn string_literal(value: &str) -> String {
+	format!("{value:?}")
+}
+
+/// Writes doc comments, line by line.
+pub(crate) fn write_docs(out: &mut String, indent: &str, docs: &str) -> fmt::Result {
+	for line in docs.lines() {
+		if line.is_empty() {
+			writeln!(out, "{indent}///")?;
+		} else {
+			writeln!(out, "{indent}/// {line}")?;
+		}
+	}
+	Ok(())
+}
+
+/// Writes a function signature up to its opening brace, with an argument per
+/// line when it is wider than `rustfmt` allows. `indent` is the width of the
+/// indentation it will have.
+pub(crate) fn write_signature(
+	out: &mut String,
+	indent: usize,
+	head: &str,
+	arguments: &[String],
+	output: &str,
+) -> fmt::Result {
+	let line = format!("{head}({}) -> {output} {{", arguments.join(", "));
+	if indent + line.len() <= 100 {
+		return writeln!(out, "{line}");
+	}
+
+	writeln!(out, "{head}(")?;
+	for argument in arguments {
+		writeln!(out, "    {argument},")?;
+	}
+	writeln!(out, ") -> {output} {{")
+}
+
+/// Indents every non-empty line of `text` by one level.
+pub(crate) fn indent(text: &str) -> String {
+	let mut indented = String::with_capacity(text.len());
+	for line in text.lines() {
+		if !line.is_empty() {
+			indented.push_str("    ");
+		}
+		indented.push_str(line);
+		indented.push('\n');
+	}
+	indented
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{pascal_case, snake_case};
+
+	#[test]
+	fn convert_case() {
+		assert_eq!(snake_case("petId"), "pet_id");
+		assert_eq!(snake_case("X-Request-Id"), "x_request_id");
+		assert_eq!(snake_case("HTTPStatus"), "http_status");
+		assert_eq!(snake_case("type"), "r#type");
+		assert_eq!(snake_case("self"), "self_");
+		assert_eq!(snake_case("2fa"), "_2fa");
+		assert_eq!(pascal_case("Not Found"), "NotFound");
+		assert_eq!(pascal_case("getPetByPetId"), "GetPetByPetId");
+		assert_eq!(pascal_case("available"), "Available");
+	}
+}
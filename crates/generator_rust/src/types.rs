@@ -0,0 +1,379 @@
+use crate::text::{indent, is_renamed, pascal_case, snake_case, string_literal, write_docs};
+use dapic_generator_common::invalid_hir;
+use dapic_hir::types::{self as hir, DefId, ExprKind, ItemKind, Res, TyKind, description};
+use dapic_session::PrimTy;
+use std::fmt::{self, Write};
+
+/// Converts types to Rust from a module of the generated code.
+///
+/// Inline models and anonymous unions have no name in the source, they are
+/// named after where they appear (e.g. `PetOwner` for the `owner` field of
+/// `Pet`) and written after the item using them.
+pub(crate) struct Types<'r, 'tcx> {
+	pub(crate) root: &'r hir::Root<'tcx>,
+	/// Number of modules the code is nested in, to reach other scopes
+	pub(crate) depth: usize,
+	pending: Vec<(String, &'tcx hir::Ty<'tcx>)>,
+}
+
+impl<'r, 'tcx> Types<'r, 'tcx> {
+	pub(crate) const fn new(root: &'r hir::Root<'tcx>) -> Self {
+		Self {
+			root,
+			depth: 0,
+			pending: Vec::new(),
+		}
+	}
+
+	/// Path of an item of the module of the root, e.g. `super::Error`.
+	pub(crate) fn root_path(&self, name: &str) -> String {
+		format!("{}{name}", "super::".repeat(self.depth))
+	}
+
+	/// Path of a definition from the current module, e.g.
+	/// `super::types::pet::Pet`.
+	pub(crate) fn def_path(&self, def_id: DefId) -> String {
+		let def = self.root.def(def_id);
+		let mut modules = String::new();
+		for ident in def.scopes() {
+			modules.push_str(&snake_case(ident.symbol.as_str()));
+			modules.push_str("::");
+		}
+		self.root_path(&format!(
+			"types::{modules}{}",
+			pascal_case(def.item.ident.symbol.as_str())
+		))
+	}
+
+	/// The Rust type of `ty`, `hint` names the inline types it contains.
+	pub(crate) fn ty_to_rust(&mut self, ty: &'tcx hir::Ty<'tcx>, hint: &str) -> String {
+		self.ty_in(ty, hint, None)
+	}
+
+	/// The Rust type of a field of the `owner` model, references to the
+	/// model itself are boxed so that it has a size.
+	fn ty_in(&mut self, ty: &'tcx hir::Ty<'tcx>, hint: &str, owner: Option<DefId>) -> String {
+		match ty.kind {
+			TyKind::Path(_, Res::PrimTy(prim)) => prim_to_rust(prim).into(),
+			TyKind::Path(_, Res::Def(_, def_id)) if Some(def_id) == owner => {
+				format!("Box<{}>", self.def_path(def_id))
+			}
+			TyKind::Path(_, Res::Def(_, def_id)) => self.def_path(def_id),
+			TyKind::Path(_, Res::TyParam) => unreachable!("generic models are not emitted"),
+			TyKind::Path(_, Res::Err) => invalid_hir(),
+			TyKind::Array(item) => format!("Vec<{}>", self.ty_in(item, hint, None)),
+			TyKind::Tuple(tys) => {
+				let tys = tys
+					.iter()
+					.enumerate()
+					.map(|(index, ty)| self.ty_in(ty, &format!("{hint}{index}"), None))
+					.collect::<Vec<_>>();
+				match tys.as_slice() {
+					[ty] => format!("({ty},)"),
+					_ => format!("({})", tys.join(", ")),
+				}
+			}
+			TyKind::InlineModel(_) | TyKind::Union(_) => {
+				self.pending.push((hint.into(), ty));
+				hint.into()
+			}
+			TyKind::Map(key, value) => format!(
+				"std::collections::HashMap<{}, {}>",
+				self.ty_in(key, &format!("{hint}Key"), None),
+				self.ty_in(value, hint, None)
+			),
+			TyKind::Nullable(ty) => format!("Option<{}>", self.ty_in(ty, hint, owner)),
+		}
+	}
+
+	/// Writes the inline types met since the last call.
+	pub(crate) fn write_pending(&mut self, out: &mut String) -> fmt::Result {
+		while let Some((name, ty)) = self.pending.pop() {
+			writeln!(out)?;
+			match ty.kind {
+				TyKind::InlineModel(fields) => {
+					self.write_struct(out, &name, None, fields.iter())?;
+				}
+				TyKind::Union(tys) => self.write_untagged(out, &name, tys)?,
+				_ => unreachable!("only inline models and unions are pending"),
+			}
+		}
+		Ok(())
+	}
+
+	/// Writes the declaration of a `model`, `enum`, `union` or type alias.
+	pub(crate) fn write_definition(&mut self, out: &mut String, def_id: DefId) -> fmt::Result {
+		let def = self.root.def(def_id);
+		let name = pascal_case(def.item.ident.symbol.as_str());
+
+		if let Some(description) = description(def.item.attrs) {
+			write_docs(out, "", &description)?;
+		}
+
+		match &def.item.kind {
+			ItemKind::Model(model) => {
+				// Bases are flattened, Rust has no inheritance
+				let fields = self.root.all_fields(model);
+				self.write_struct(out, &name, Some(def_id), fields)?;
+			}
+			ItemKind::Enum(enum_) => write_enum(out, &name, enum_)?,
+			ItemKind::Union(union) => self.write_union(out, &name, union)?,
+			ItemKind::TypeAlias(alias) => {
+				let ty = self.ty_to_rust(alias.ty, &name);
+				writeln!(out, "pub type {name} = {ty};")?;
+			}
+			_ => unreachable!("only models, enums, unions and type aliases are declared"),
+		}
+
+		self.write_pending(out)
+	}
+
+	/// Writes a struct of the fields, `owner` is the model it comes from.
+	/// Optional fields are `Option`s skipped when absent.
+	pub(crate) fn write_struct<'f>(
+		&mut self,
+		out: &mut String,
+		name: &str,
+		owner: Option<DefId>,
+		fields: impl IntoIterator<Item = &'f hir::FieldDef<'tcx>>,
+	) -> fmt::Result
+	where
+		'tcx: 'f,
+	{
+		let mut body = String::new();
+		for field in fields {
+			let mut docs = description(field.attrs).unwrap_or_default();
+			if let Some(default) = field.default.and_then(literal_to_rust) {
+				if !docs.is_empty() {
+					docs.push_str("\n\n");
+				}
+				write!(docs, "Defaults to `{default}`.")?;
+			}
+			write_docs(&mut body, "", &docs)?;
+
+			let original = field.ident.symbol.as_str();
+			let ident = snake_case(original);
+			let hint = format!("{name}{}", pascal_case(original));
+			let ty = if field.required() {
+				self.ty_in(field.ty, &hint, owner)
+			} else {
+				body.push_str("#[serde(default, skip_serializing_if = \"Option::is_none\")]\n");
+				// Both absent and `null` values are `None`
+				let ty = match field.ty.kind {
+					TyKind::Nullable(ty) => ty,
+					_ => field.ty,
+				};
+				format!("Option<{}>", self.ty_in(ty, &hint, owner))
+			};
+			if is_renamed(original, &ident) {
+				writeln!(body, "#[serde(rename = {})]", string_literal(original))?;
+			}
+			writeln!(body, "pub {ident}: {ty},")?;
+		}
+
+		writeln!(
+			out,
+			"#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]"
+		)?;
+		if body.is_empty() {
+			return writeln!(out, "pub struct {name} {{}}");
+		}
+		writeln!(out, "pub struct {name} {{")?;
+		write!(out, "{}", indent(&body))?;
+		writeln!(out, "}}")
+	}
+
+	/// Writes a tagged union, deserialized with the variant named by its
+	/// discriminator. Variants keep the discriminator field, so they are
+	/// serialized as is.
+	fn write_union(
+		&mut self,
+		out: &mut String,
+		name: &str,
+		union: &hir::Union<'tcx>,
+	) -> fmt::Result {
+		let discriminator = string_literal(union.discriminator.symbol.as_str());
+
+		writeln!(out, "#[derive(Debug, Clone, PartialEq, serde::Serialize)]")?;
+		writeln!(out, "#[serde(untagged)]")?;
+		writeln!(out, "pub enum {name} {{")?;
+		for variant in union.variants {
+			if let Some(description) = description(variant.attrs) {
+				write_docs(out, "    ", &description)?;
+			}
+			let variant_name = pascal_case(variant.ident.symbol.as_str());
+			let ty = self.ty_to_rust(variant.ty, &format!("{name}{variant_name}"));
+			writeln!(out, "    {variant_name}({ty}),")?;
+		}
+		writeln!(out, "}}")?;
+		writeln!(out)?;
+
+		writeln!(out, "impl<'de> serde::Deserialize<'de> for {name} {{")?;
+		writeln!(
+			out,
+			"    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {{"
+		)?;
+		writeln!(out, "        use serde::de::Error;")?;
+		writeln!(out)?;
+		writeln!(
+			out,
+			"        let value = serde_json::Value::deserialize(deserializer)?;"
+		)?;
+		writeln!(
+			out,
+			"        match value.get({discriminator}).and_then(serde_json::Value::as_str) {{"
+		)?;
+		for variant in union.variants {
+			writeln!(
+				out,
+				"            Some({}) => serde_json::from_value(value).map(Self::{}),",
+				string_literal(variant.ident.symbol.as_str()),
+				pascal_case(variant.ident.symbol.as_str())
+			)?;
+		}
+		writeln!(
+			out,
+			"            _ => return Err(D::Error::custom(\"unknown {} of {name}\")),",
+			union.discriminator
+		)?;
+		writeln!(out, "        }}")?;
+		writeln!(out, "        .map_err(D::Error::custom)")?;
+		writeln!(out, "    }}")?;
+		writeln!(out, "}}")
+	}
+
+	/// Writes an anonymous union as an untagged enum, variants are named after
+	/// their type.
+	fn write_untagged(
+		&mut self,
+		out: &mut String,
+		name: &str,
+		tys: &'tcx [hir::Ty<'tcx>],
+	) -> fmt::Result {
+		let mut variants = Vec::<String>::new();
+		for (index, ty) in tys.iter().enumerate() {
+			let variant = match ty.kind {
+				TyKind::Path(_, Res::PrimTy(prim)) => pascal_case(prim.symbol().as_str()),
+				TyKind::Path(_, Res::Def(_, def_id)) => {
+					pascal_case(self.root.def(def_id).item.ident.symbol.as_str())
+				}
+				_ => format!("Variant{index}"),
+			};
+			let variant = if variants.contains(&variant) {
+				format!("{variant}{index}")
+			} else {
+				variant
+			};
+			variants.push(variant);
+		}
+
+		writeln!(
+			out,
+			"#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]"
+		)?;
+		writeln!(out, "#[serde(untagged)]")?;
+		writeln!(out, "pub enum {name} {{")?;
+		for (variant, ty) in variants.iter().zip(tys) {
+			let ty = self.ty_to_rust(ty, &format!("{name}{variant}"));
+			writeln!(out, "    {variant}({ty}),")?;
+		}
+		writeln!(out, "}}")
+	}
+}
+
+/// Writes an `enum`, string values are renamed variants and integer ones
+/// are converted through `i64`.
+fn write_enum(out: &mut String, name: &str, enum_: &hir::Enum<'_>) -> fmt::Result {
+	let is_string = prim_to_rust(enum_.ty) == "String";
+	let variants = enum_
+		.variants
+		.iter()
+		.map(|variant| {
+			let ExprKind::Literal(_, value) = variant.expr.kind else {
+				unreachable!("variant values were checked during lowering")
+			};
+			(variant, pascal_case(variant.ident.symbol.as_str()), value)
+		})
+		.collect::<Vec<_>>();
+
+	writeln!(
+		out,
+		"#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]"
+	)?;
+	if !is_string {
+		writeln!(out, "#[serde(into = \"i64\", try_from = \"i64\")]")?;
+	}
+	writeln!(out, "pub enum {name} {{")?;
+	for (variant, ident, value) in &variants {
+		if let Some(description) = description(variant.attrs) {
+			write_docs(out, "    ", &description)?;
+		}
+		if is_string {
+			writeln!(
+				out,
+				"    #[serde(rename = {})]",
+				string_literal(value.as_str())
+			)?;
+		}
+		writeln!(out, "    {ident},")?;
+	}
+	writeln!(out, "}}")?;
+
+	if is_string {
+		return Ok(());
+	}
+
+	writeln!(out)?;
+	writeln!(out, "impl From<{name}> for i64 {{")?;
+	writeln!(out, "    fn from(value: {name}) -> Self {{")?;
+	writeln!(out, "        match value {{")?;
+	for (_, ident, value) in &variants {
+		writeln!(out, "            {name}::{ident} => {value},")?;
+	}
+	writeln!(out, "        }}")?;
+	writeln!(out, "    }}")?;
+	writeln!(out, "}}")?;
+	writeln!(out)?;
+	writeln!(out, "impl TryFrom<i64> for {name} {{")?;
+	writeln!(out, "    type Error = String;")?;
+	writeln!(out)?;
+	writeln!(
+		out,
+		"    fn try_from(value: i64) -> Result<Self, Self::Error> {{"
+	)?;
+	writeln!(out, "        match value {{")?;
+	for (_, ident, value) in &variants {
+		writeln!(out, "            {value} => Ok(Self::{ident}),")?;
+	}
+	writeln!(
+		out,
+		"            _ => Err(format!(\"unknown {name} value {{value}}\")),"
+	)?;
+	writeln!(out, "        }}")?;
+	writeln!(out, "    }}")?;
+	writeln!(out, "}}")
+}
+
+pub(crate) const fn prim_to_rust(prim: PrimTy) -> &'static str {
+	match prim {
+		PrimTy::Any => "serde_json::Value",
+		PrimTy::Binary => "Vec<u8>",
+		PrimTy::Bool => "bool",
+		PrimTy::Date | PrimTy::DateTime | PrimTy::String | PrimTy::Uuid => "String",
+		PrimTy::Double => "f64",
+		PrimTy::Float => "f32",
+		PrimTy::Int | PrimTy::Int64 => "i64",
+		PrimTy::Int32 => "i32",
+	}
+}
+
+/// A literal value as written in the source, `None` for other expressions.
+fn literal_to_rust(expr: &hir::Expr<'_>) -> Option<String> {
+	match expr.kind {
+		ExprKind::Literal(dapic_lexer::rich::LiteralKind::Str, value) => {
+			Some(string_literal(value.as_str()))
+		}
+		ExprKind::Literal(_, value) => Some(value.to_string()),
+		_ => None,
+	}
+}
@@ -0,0 +1,94 @@
+/** Settings shared by every request of the client. */
+export interface ClientOptions {
+	/** URL the paths are appended to, e.g. `https://api.example.com/v1` */
+	baseUrl: string;
+	/** Headers sent with every request, e.g. the `Authorization` credentials */
+	headers?: Record<string, string>;
+	/** Implementation of `fetch`, the global one by default */
+	fetch?: typeof fetch;
+}
+
+/** A response along with its decoded body. */
+export interface ApiResponse<Status extends number, Body> {
+	status: Status;
+	body: Body;
+	headers: Headers;
+}
+
+async function send(
+	client: ClientOptions,
+	method: string,
+	path: string,
+	query: object | undefined,
+	headers: object | undefined,
+	body: unknown,
+	mediaType: string | undefined,
+): Promise<ApiResponse<number, unknown>> {
+	const url = new URL(client.baseUrl.replace(/\/+$/, "") + path);
+	for (const [name, value] of Object.entries(query ?? {})) {
+		for (const item of Array.isArray(value) ? value : [value]) {
+			if (item !== undefined && item !== null) {
+				url.searchParams.append(name, String(item));
+			}
+		}
+	}
+
+	const requestHeaders: Record<string, string> = { ...client.headers };
+	for (const [name, value] of Object.entries(headers ?? {})) {
+		if (value !== undefined && value !== null) {
+			requestHeaders[name] = String(value);
+		}
+	}
+
+	const init: RequestInit = { method, headers: requestHeaders };
+	if (body !== undefined && mediaType !== undefined) {
+		init.body = encode(body, mediaType);
+		// The boundary of multipart bodies is set by `fetch`
+		if (mediaType !== "multipart/form-data") {
+			requestHeaders["Content-Type"] = mediaType;
+		}
+	}
+
+	const response = await (client.fetch ?? fetch)(url, init);
+	return {
+		status: response.status,
+		body: await decode(response),
+		headers: response.headers,
+	};
+}
+
+function encode(body: unknown, mediaType: string): BodyInit {
+	if (mediaType === "application/json" || mediaType.endsWith("+json")) {
+		return JSON.stringify(body);
+	}
+
+	const entries = Object.entries(body as object).filter(
+		([, value]) => value !== undefined && value !== null,
+	);
+	if (mediaType === "application/x-www-form-urlencoded") {
+		return new URLSearchParams(entries.map(([name, value]) => [name, String(value)]));
+	}
+	if (mediaType === "multipart/form-data") {
+		const form = new FormData();
+		for (const [name, value] of entries) {
+			form.append(name, value instanceof Blob ? value : String(value));
+		}
+		return form;
+	}
+
+	return body as BodyInit;
+}
+
+async function decode(response: Response): Promise<unknown> {
+	const contentType = response.headers.get("Content-Type") ?? "";
+	if (contentType.includes("json")) {
+		const text = await response.text();
+		return text === "" ? undefined : JSON.parse(text);
+	}
+	if (contentType.startsWith("text/")) {
+		return response.text();
+	}
+
+	const blob = await response.blob();
+	return blob.size === 0 ? undefined : blob;
+}
@@ -0,0 +1,248 @@
+use crate::{
+	declaration::write_members,
+	text::{pascal_case, property_key, string_literal, ty_to_ts, write_tsdoc},
+};
+use dapic_generator_common::invalid_hir;
+use dapic_hir::types::{self as hir, EndpointSegment, MediaType, docs};
+use std::fmt::{self, Write};
+
+/// Writes a request interface, a response union and a function per endpoint.
+pub(crate) fn write_client(out: &mut String, root: &hir::Root<'_>) -> fmt::Result {
+	for endpoint in root.endpoints() {
+		writeln!(out)?;
+		write_operation(out, root, &endpoint)?;
+	}
+	Ok(())
+}
+
+fn write_operation(
+	out: &mut String,
+	root: &hir::Root<'_>,
+	endpoint: &hir::Endpoint<'_>,
+) -> fmt::Result {
+	let name = endpoint.operation_id();
+	let type_name = pascal_case(&name);
+
+	let mut media_types = Vec::<&MediaType>::new();
+	for (_, body) in endpoint.bodies() {
+		for media_type in body.media_types {
+			if !media_types.contains(&media_type) {
+				media_types.push(media_type);
+			}
+		}
+	}
+
+	let has_request = write_request(out, root, endpoint, &type_name, &media_types)?;
+	write_responses(out, root, endpoint, &type_name)?;
+
+	let mut summary = format!("`{} {}`", endpoint.verb.method, endpoint.path());
+	if let Some(docs) = docs(endpoint.item.attrs) {
+		summary = format!("{docs}\n\n{summary}");
+	}
+	write_tsdoc(out, "", &summary)?;
+	writeln!(out, "export async function {name}(")?;
+	writeln!(out, "\tclient: ClientOptions,")?;
+	if has_request {
+		writeln!(out, "\trequest: {type_name}Request,")?;
+	}
+	writeln!(out, "): Promise<{type_name}Response> {{")?;
+
+	let argument = |present: bool, field: &str| {
+		if present {
+			format!("request.{field}")
+		} else {
+			"undefined".into()
+		}
+	};
+	let media_type = match media_types.as_slice() {
+		[] => "undefined".into(),
+		[media_type] => string_literal(media_type.mime()),
+		[first, ..] => format!("request.contentType ?? {}", string_literal(first.mime())),
+	};
+
+	writeln!(out, "\tconst response = await send(")?;
+	writeln!(out, "\t\tclient,")?;
+	writeln!(
+		out,
+		"\t\t{},",
+		string_literal(endpoint.verb.method.symbol.as_str())
+	)?;
+	writeln!(out, "\t\t{},", path_to_ts(endpoint))?;
+	writeln!(
+		out,
+		"\t\t{},",
+		argument(endpoint.query().next().is_some(), "query")
+	)?;
+	writeln!(
+		out,
+		"\t\t{},",
+		argument(endpoint.headers().next().is_some(), "headers")
+	)?;
+	writeln!(out, "\t\t{},", argument(!media_types.is_empty(), "body"))?;
+	writeln!(out, "\t\t{media_type},")?;
+	writeln!(out, "\t);")?;
+	writeln!(out, "\treturn response as {type_name}Response;")?;
+	writeln!(out, "}}")
+}
+
+/// Writes the interface of the request, returns whether there is one: an
+/// endpoint without parameters nor body takes no request.
+fn write_request(
+	out: &mut String,
+	root: &hir::Root<'_>,
+	endpoint: &hir::Endpoint<'_>,
+	type_name: &str,
+	media_types: &[&MediaType],
+) -> Result<bool, fmt::Error> {
+	let mut members = String::new();
+	write_group(&mut members, root, "params", endpoint.params())?;
+	write_group(&mut members, root, "query", endpoint.query())?;
+	write_group(&mut members, root, "headers", endpoint.headers())?;
+
+	if !media_types.is_empty() {
+		let bodies = endpoint.bodies().collect::<Vec<_>>();
+		if let Some(docs) = bodies.iter().find_map(|(item, _)| docs(item.attrs)) {
+			write_tsdoc(&mut members, "\t", &docs)?;
+		}
+		let tys = bodies
+			.iter()
+			.map(|(_, body)| ty_to_ts(root, body.ty))
+			.collect::<Vec<_>>();
+		writeln!(members, "\tbody: {};", tys.join(" | "))?;
+	}
+	if let [first, _, ..] = media_types {
+		let mimes = media_types
+			.iter()
+			.map(|media_type| string_literal(media_type.mime()))
+			.collect::<Vec<_>>();
+		writeln!(
+			members,
+			"\t/** Media type of the body, `{}` by default */",
+			first.mime()
+		)?;
+		writeln!(members, "\tcontentType?: {};", mimes.join(" | "))?;
+	}
+
+	if members.is_empty() {
+		return Ok(false);
+	}
+
+	writeln!(
+		out,
+		"/** Request of {{@link {}}}. */",
+		endpoint.operation_id()
+	)?;
+	writeln!(out, "export interface {type_name}Request {{")?;
+	out.push_str(&members);
+	writeln!(out, "}}")?;
+	writeln!(out)?;
+	Ok(true)
+}
+
+/// Writes a member holding the fields, optional when none of them is
+/// required and skipped when there is none.
+fn write_group<'tcx>(
+	out: &mut String,
+	root: &hir::Root<'_>,
+	name: &str,
+	fields: impl IntoIterator<Item = &'tcx hir::FieldDef<'tcx>>,
+) -> fmt::Result {
+	let fields = fields.into_iter().collect::<Vec<_>>();
+	if fields.is_empty() {
+		return Ok(());
+	}
+
+	let optional = fields.iter().all(|field| !field.required());
+	writeln!(out, "\t{name}{}: {{", if optional { "?" } else { "" })?;
+	write_members(out, root, "\t\t", fields)?;
+	writeln!(out, "\t}};")
+}
+
+/// Writes the union of the responses, told apart by their status. Ranges and
+/// `default` responses have a `number` status.
+fn write_responses(
+	out: &mut String,
+	root: &hir::Root<'_>,
+	endpoint: &hir::Endpoint<'_>,
+	type_name: &str,
+) -> fmt::Result {
+	writeln!(
+		out,
+		"/** Responses of {{@link {}}}. */",
+		endpoint.operation_id()
+	)?;
+	writeln!(out, "export type {type_name}Response =")?;
+
+	let responses = endpoint.responses().collect::<Vec<_>>();
+	if responses.is_empty() {
+		writeln!(out, "\tApiResponse<number, unknown>;")?;
+		return writeln!(out);
+	}
+
+	for (index, (item, code)) in responses.iter().enumerate() {
+		// Shared responses are documented on their definition
+		let attrs = code
+			.def
+			.map_or(item.attrs, |def_id| root.def(def_id).item.attrs);
+		if let Some(docs) = docs(attrs) {
+			write_tsdoc(out, "\t", &docs)?;
+		}
+
+		let status = match code.status {
+			hir::Status::Code(code) => code.to_string(),
+			hir::Status::Range(_) | hir::Status::Default => "number".into(),
+			hir::Status::Err => invalid_hir(),
+		};
+		let tys = code
+			.bodies()
+			.map(|(_, body)| ty_to_ts(root, body.ty))
+			.collect::<Vec<_>>();
+		let body = if tys.is_empty() {
+			"undefined".into()
+		} else {
+			tys.join(" | ")
+		};
+		let end = if index + 1 == responses.len() {
+			";"
+		} else {
+			""
+		};
+		writeln!(out, "\t| ApiResponse<{status}, {body}>{end}")?;
+	}
+
+	writeln!(out)
+}
+
+/// The path of the endpoint as a template literal, variables are read from
+/// the `params` of the request.
+fn path_to_ts(endpoint: &hir::Endpoint<'_>) -> String {
+	if endpoint.variables.is_empty() {
+		return string_literal(&endpoint.path());
+	}
+
+	let mut path = String::from("`");
+	for segment in &endpoint.segments {
+		match segment {
+			EndpointSegment::Literal(ident) => {
+				path.push('/');
+				path.push_str(ident.symbol.as_str());
+			}
+			EndpointSegment::Variable(ident) => {
+				let name = ident.symbol.as_str();
+				let key = property_key(name);
+				let access = if key == name {
+					format!(".{name}")
+				} else {
+					format!("[{key}]")
+				};
+				write!(
+					path,
+					"/${{encodeURIComponent(String(request.params{access}))}}"
+				)
+				.expect("writing to a string never fails");
+			}
+		}
+	}
+	path.push('`');
+	path
+}
@@ -0,0 +1,168 @@
+use crate::text::{indent, literal_to_ts, property_key, string_literal, ty_to_ts, write_tsdoc};
+use dapic_hir::types::{self as hir, DefId, ItemKind, description, docs};
+use dapic_session::Symbol;
+use std::{
+	fmt::{self, Write},
+	ptr,
+};
+
+/// Writes the declarations of the definitions, those of a `scope` go in a
+/// namespace of the same name.
+pub(crate) fn write_declarations(out: &mut String, root: &hir::Root<'_>) -> fmt::Result {
+	let mut defs = Vec::<(Vec<Symbol>, DefId)>::new();
+	for (def_id, def) in root.defs() {
+		if def.is_concrete_type() {
+			let mod_path = def.scopes().iter().map(|ident| ident.symbol).collect();
+			defs.push((mod_path, def_id));
+		}
+	}
+
+	write_scope(out, root, root.items(), &mut Vec::new(), &defs)
+}
+
+fn write_scope(
+	out: &mut String,
+	root: &hir::Root<'_>,
+	items: &[hir::Item<'_>],
+	mod_path: &mut Vec<Symbol>,
+	defs: &[(Vec<Symbol>, DefId)],
+) -> fmt::Result {
+	for (_, def_id) in defs.iter().filter(|(path, _)| path == mod_path) {
+		write_declaration(out, root, *def_id)?;
+		writeln!(out)?;
+	}
+
+	for item in items {
+		let ItemKind::Scope(scope) = &item.kind else {
+			continue;
+		};
+
+		mod_path.push(item.ident.symbol);
+		let mut body = String::new();
+		write_scope(&mut body, root, scope.items, mod_path, defs)?;
+		mod_path.pop();
+
+		// Scopes with only endpoints have no namespace
+		if body.is_empty() {
+			continue;
+		}
+
+		if let Some(docs) = docs(item.attrs) {
+			write_tsdoc(out, "", &docs)?;
+		}
+		writeln!(out, "export namespace {} {{", item.ident)?;
+		write!(out, "{}", indent(body.trim_end()))?;
+		writeln!(out, "}}")?;
+		writeln!(out)?;
+	}
+
+	Ok(())
+}
+
+/// Writes the interface of a model, or the type of an enum, union or alias.
+fn write_declaration(out: &mut String, root: &hir::Root<'_>, def_id: DefId) -> fmt::Result {
+	let def = root.def(def_id);
+	let name = def.item.ident;
+
+	if let Some(description) = description(def.item.attrs) {
+		write_tsdoc(out, "", &description)?;
+	}
+
+	match &def.item.kind {
+		ItemKind::Model(model) => {
+			write!(out, "export interface {name}")?;
+			if !model.bases.is_empty() {
+				let bases = model
+					.bases
+					.iter()
+					.map(|base| ty_to_ts(root, base))
+					.collect::<Vec<_>>();
+				write!(out, " extends {}", bases.join(", "))?;
+			}
+			writeln!(out, " {{")?;
+
+			// Fields redeclaring an inherited one are skipped, as in
+			// `Root::all_fields`
+			let all_fields = root.all_fields(model);
+			let fields = model
+				.fields
+				.iter()
+				.filter(|field| all_fields.iter().any(|other| ptr::eq(*other, *field)));
+			write_members(out, root, "\t", fields)?;
+			writeln!(out, "}}")
+		}
+		ItemKind::Enum(enum_) => {
+			writeln!(out, "export type {name} =")?;
+			for (index, variant) in enum_.variants.iter().enumerate() {
+				if let Some(description) = description(variant.attrs) {
+					write_tsdoc(out, "\t", &description)?;
+				}
+				let value = literal_to_ts(variant.expr)
+					.expect("variant values were checked during lowering");
+				let end = if index + 1 == enum_.variants.len() {
+					";"
+				} else {
+					""
+				};
+				writeln!(out, "\t| {value}{end}")?;
+			}
+			Ok(())
+		}
+		ItemKind::Union(union) => {
+			// The discriminator of each variant is narrowed to its name
+			let discriminator = property_key(union.discriminator.symbol.as_str());
+			writeln!(out, "export type {name} =")?;
+			for (index, variant) in union.variants.iter().enumerate() {
+				if let Some(description) = description(variant.attrs) {
+					write_tsdoc(out, "\t", &description)?;
+				}
+				let end = if index + 1 == union.variants.len() {
+					";"
+				} else {
+					""
+				};
+				writeln!(
+					out,
+					"\t| ({} & {{ {discriminator}: {} }}){end}",
+					ty_to_ts(root, variant.ty),
+					string_literal(variant.ident.symbol.as_str())
+				)?;
+			}
+			Ok(())
+		}
+		ItemKind::TypeAlias(alias) => {
+			writeln!(out, "export type {name} = {};", ty_to_ts(root, alias.ty))
+		}
+		_ => unreachable!("only models, enums, unions and type aliases are declared"),
+	}
+}
+
+/// Writes fields as interface members, one per line with their docs.
+pub(crate) fn write_members<'tcx>(
+	out: &mut String,
+	root: &hir::Root<'_>,
+	indent: &str,
+	fields: impl IntoIterator<Item = &'tcx hir::FieldDef<'tcx>>,
+) -> fmt::Result {
+	for field in fields {
+		let mut docs = description(field.attrs).unwrap_or_default();
+		if let Some(default) = field.default.and_then(literal_to_ts) {
+			if !docs.is_empty() {
+				docs.push('\n');
+			}
+			write!(docs, "@defaultValue `{default}`")?;
+		}
+		if !docs.is_empty() {
+			write_tsdoc(out, indent, &docs)?;
+		}
+
+		writeln!(
+			out,
+			"{indent}{}{}: {};",
+			property_key(field.ident.symbol.as_str()),
+			if field.required() { "" } else { "?" },
+			ty_to_ts(root, field.ty)
+		)?;
+	}
+	Ok(())
+}
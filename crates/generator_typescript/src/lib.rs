@@ -0,0 +1,49 @@
+//! Declarative API TypeScript generator
+//!
+//! Entrypoint is [`generate_typescript`]. Writes a single module holding the
+//! declarations of the definitions, grouped in a namespace per `scope`, and a
+//! `fetch`-based client function per endpoint.
+
+use crate::{client::write_client, declaration::write_declarations, text::write_tsdoc};
+use dapic_generator_common::write_string;
+use dapic_hir::types::{self as hir, docs};
+use std::fmt::{self, Write};
+
+mod client;
+mod declaration;
+mod text;
+
+const RUNTIME: &str = include_str!("assets/runtime.ts");
+
+/// Writes the TypeScript module of the api.
+#[must_use]
+pub fn generate_typescript(root: &hir::Root<'_>) -> String {
+	write_string(|out| write_module(out, root))
+}
+
+fn write_module(out: &mut String, root: &hir::Root<'_>) -> fmt::Result {
+	let meta = root.meta();
+
+	let mut header = Vec::new();
+	match (meta.name, meta.version) {
+		(Some(name), Some(version)) => header.push(format!("{name} {version}")),
+		(Some(name), None) => header.push(name.to_string()),
+		(None, Some(version)) => header.push(format!("Version {version}")),
+		(None, None) => {}
+	}
+	header.extend(docs(root.attrs));
+	header.extend(meta.description.map(|description| description.to_string()));
+	header.push("@packageDocumentation".into());
+	write_tsdoc(out, "", &header.join("\n\n"))?;
+
+	writeln!(out, "// Generated by dapic, do not edit.")?;
+	writeln!(out)?;
+
+	write_declarations(out, root)?;
+
+	write!(out, "{RUNTIME}")?;
+	write_client(out, root)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,151 @@
+use dapic_generator_common::invalid_hir;
+use dapic_hir::types::{self as hir, ExprKind, Res, TyKind};
+use dapic_lexer::rich::LiteralKind;
+use dapic_session::{JsonTy, PrimTy};
+use std::fmt::{self, Write};
+
+/// Writes doc comments as a `TSDoc` block, on one line when they fit.
+pub(crate) fn write_tsdoc(out: &mut String, indent: &str, docs: &str) -> fmt::Result {
+	// The comment would end early
+	let docs = docs.replace("*/", "*\\/");
+
+	if !docs.contains('\n') {
+		return writeln!(out, "{indent}/** {docs} */");
+	}
+
+	writeln!(out, "{indent}/**")?;
+	for line in docs.lines() {
+		if line.is_empty() {
+			writeln!(out, "{indent} *")?;
+		} else {
+			writeln!(out, "{indent} * {line}")?;
+		}
+	}
+	writeln!(out, "{indent} */")
+}
+
+/// Indents every non-empty line of `text` by one tab.
+pub(crate) fn indent(text: &str) -> String {
+	let mut indented = String::with_capacity(text.len());
+	for line in text.lines() {
+		if !line.is_empty() {
+			indented.push('\t');
+		}
+		indented.push_str(line);
+		indented.push('\n');
+	}
+	indented
+}
+
+/// A property name, quoted when it is not a valid identifier (e.g. the
+/// `X-Request-Id` header).
+pub(crate) fn property_key(name: &str) -> String {
+	let mut chars = name.chars();
+	let is_identifier = chars
+		.next()
+		.is_some_and(|char| char.is_ascii_alphabetic() || matches!(char, '_' | '$'))
+		&& chars.all(|char| char.is_ascii_alphanumeric() || matches!(char, '_' | '$'));
+
+	if is_identifier {
+		name.into()
+	} else {
+		string_literal(name)
+	}
+}
+
+pub(crate) fn string_literal(value: &str) -> String {
+	format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// e.g. `GetPetByPetId` for the `getPetByPetId` operation.
+pub(crate) fn pascal_case(name: &str) -> String {
+	let mut chars = name.chars();
+	chars.next().map_or_else(String::new, |first| {
+		first.to_uppercase().chain(chars).collect()
+	})
+}
+
+pub(crate) const fn prim_to_ts(prim: PrimTy) -> &'static str {
+	match prim {
+		PrimTy::Any => "unknown",
+		PrimTy::Binary => "Blob",
+		_ => match prim.json_ty() {
+			Some(JsonTy::Boolean) => "boolean",
+			Some(JsonTy::Integer | JsonTy::Number) => "number",
+			Some(JsonTy::String) | None => "string",
+		},
+	}
+}
+
+pub(crate) fn ty_to_ts(root: &hir::Root<'_>, ty: &hir::Ty<'_>) -> String {
+	match ty.kind {
+		TyKind::Path(_, Res::PrimTy(prim)) => prim_to_ts(prim).into(),
+		TyKind::Path(_, Res::Def(_, def_id)) => root.def(def_id).qualified_name(),
+		TyKind::Path(_, Res::TyParam) => unreachable!("generic models are not emitted"),
+		TyKind::Path(_, Res::Err) => invalid_hir(),
+		TyKind::Array(item) => match item.kind {
+			TyKind::Nullable(_) | TyKind::Union(_) => format!("Array<{}>", ty_to_ts(root, item)),
+			_ => format!("{}[]", ty_to_ts(root, item)),
+		},
+		TyKind::Tuple(tys) => format!("[{}]", tys_to_ts(root, tys, ", ")),
+		TyKind::InlineModel([]) => "Record<string, never>".into(),
+		TyKind::InlineModel(fields) => {
+			let fields = fields
+				.iter()
+				.map(|field| {
+					format!(
+						"{}{}: {}",
+						property_key(field.ident.symbol.as_str()),
+						if field.required() { "" } else { "?" },
+						ty_to_ts(root, field.ty)
+					)
+				})
+				.collect::<Vec<_>>();
+			format!("{{ {} }}", fields.join("; "))
+		}
+		TyKind::Map(key, value) => match key.kind {
+			// Not every variant has to be present
+			TyKind::Path(_, Res::Def(..)) => format!(
+				"Partial<Record<{}, {}>>",
+				ty_to_ts(root, key),
+				ty_to_ts(root, value)
+			),
+			TyKind::Path(_, Res::PrimTy(prim)) if prim_to_ts(prim) == "number" => {
+				format!("Record<number, {}>", ty_to_ts(root, value))
+			}
+			_ => format!("Record<string, {}>", ty_to_ts(root, value)),
+		},
+		TyKind::Nullable(ty) => format!("{} | null", ty_to_ts(root, ty)),
+		TyKind::Union(tys) => tys_to_ts(root, tys, " | "),
+	}
+}
+
+pub(crate) fn tys_to_ts(root: &hir::Root<'_>, tys: &[hir::Ty<'_>], separator: &str) -> String {
+	tys.iter()
+		.map(|ty| ty_to_ts(root, ty))
+		.collect::<Vec<_>>()
+		.join(separator)
+}
+
+/// A literal value as a `TypeScript` expression, `None` for other expressions.
+pub(crate) fn literal_to_ts(expr: &hir::Expr<'_>) -> Option<String> {
+	match expr.kind {
+		ExprKind::Literal(LiteralKind::Str, value) => Some(string_literal(value.as_str())),
+		ExprKind::Literal(_, value) => Some(value.to_string()),
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::property_key;
+
+	#[test]
+	fn property_keys() {
+		assert_eq!(property_key("petId"), "petId");
+		assert_eq!(property_key("$ref_2"), "$ref_2");
+		assert_eq!(property_key("X-Request-Id"), "\"X-Request-Id\"");
+		assert_eq!(property_key("2fa"), "\"2fa\"");
+		assert_eq!(property_key("a\"b"), "\"a\\\"b\"");
+	}
+}
@@ -0,0 +1,496 @@
+pub(crate) mod resolve {
+	use crate::types::DefKind;
+	use dapic_macros::IntoDiagnostic;
+	use dapic_session::{Ident, PrimTy, Span};
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("the name `{name}` is defined multiple times in this scope")]
+	pub struct DuplicateDefinition {
+		#[label("`{name}` redefined here")]
+		pub name: Ident,
+		#[label("previous definition of `{name}` here")]
+		pub previous: Span,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("cannot find type `{path}` in this scope")]
+	pub struct UnknownType {
+		#[label("not found in this scope")]
+		pub span: Span,
+
+		pub path: String,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("cannot find auth scheme `{name}` in this scope")]
+	pub struct UnknownAuth {
+		#[label("not found in this scope")]
+		pub name: Ident,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("cannot find {kind} `{name}` in this scope")]
+	pub struct UnknownBlock {
+		#[label("not found in this scope")]
+		pub name: Ident,
+
+		pub kind: DefKind,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("cannot find scope `{scope}`")]
+	pub struct UnknownScope {
+		#[label("no scope named `{scope}` is reachable from here")]
+		pub scope: Ident,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("`{name}` is ambiguous, it is defined in multiple scopes: {candidates}")]
+	pub struct AmbiguousType {
+		#[label("qualify the path to pick one of the candidates")]
+		pub name: Ident,
+
+		pub candidates: String,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("{kind} `{name}` is shadowed by the built-in `{prim}` type")]
+	#[severity(Warning)]
+	pub struct ShadowedPrimitive {
+		#[label("unqualified `{name}` paths always refer to the primitive")]
+		pub name: Ident,
+
+		pub kind: DefKind,
+		pub prim: PrimTy,
+	}
+}
+
+pub(crate) mod lower {
+	use crate::types::{AuthFlowKind, ConstraintTarget, MediaType, MetaKey, Status};
+	use dapic_macros::IntoDiagnostic;
+	use dapic_session::{Ident, PrimTy, Span, Symbol};
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("invalid format `{format}` for type `{prim}`")]
+	pub struct InvalidFormat {
+		#[label("expected one of: {allowed}")]
+		pub span: Span,
+
+		pub format: Symbol,
+		pub prim: PrimTy,
+		pub allowed: String,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("type `{prim}` does not accept any format")]
+	pub struct UnexpectedFormat {
+		#[label("remove this attribute")]
+		pub span: Span,
+
+		pub prim: PrimTy,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("unknown meta property `{key}`")]
+	pub struct UnknownMetaKey {
+		#[label("expected one of: {expected}")]
+		pub key: Ident,
+
+		pub expected: String,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("property `{key}` is defined multiple times")]
+	pub struct DuplicateProperty {
+		#[label("`{key}` redefined here")]
+		pub key: Ident,
+		#[label("previous definition of `{key}` here")]
+		pub previous: Span,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("invalid value for meta property `{key}`")]
+	pub struct InvalidMetaValue {
+		#[label("expected {expected}")]
+		pub span: Span,
+
+		pub key: MetaKey,
+		pub expected: &'static str,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("the meta block is missing the `{key}` property")]
+	pub struct MissingMetaProperty {
+		#[label("generators need the {key} of the api")]
+		pub span: Span,
+
+		pub key: MetaKey,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("unknown property `{key}` for this auth scheme")]
+	pub struct UnknownAuthProperty {
+		#[label("expected one of: {expected}")]
+		pub key: Ident,
+
+		pub expected: String,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("auth scheme `{name}` is missing the `{property}` property")]
+	pub struct MissingAuthProperty {
+		#[label("add a `{property}` property to this definition")]
+		pub name: Ident,
+
+		pub property: Symbol,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("invalid value for auth property `{key}`")]
+	pub struct InvalidAuthValue {
+		#[label("expected {expected}")]
+		pub span: Span,
+
+		pub key: Symbol,
+		pub expected: String,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("unknown OAuth2 flow `{kind}`")]
+	pub struct UnknownAuthFlow {
+		#[label("expected one of: {expected}")]
+		pub kind: Ident,
+
+		pub expected: String,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("flows are only allowed in `oauth2` auth schemes")]
+	pub struct UnexpectedAuthFlow {
+		#[label("remove this flow")]
+		pub span: Span,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("OAuth2 flow `{kind}` is missing the `{property}` property")]
+	pub struct MissingAuthFlowProperty {
+		#[label("add a `{property}` property to this flow")]
+		pub span: Span,
+
+		pub kind: AuthFlowKind,
+		pub property: Symbol,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("invalid status code `{code}`")]
+	pub struct InvalidStatusCode {
+		#[label("expected a code between 100 and 599")]
+		pub span: Span,
+
+		pub code: Symbol,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("invalid status code range `~{range}`")]
+	pub struct InvalidStatusRange {
+		#[label("expected one of: ~1xx, ~2xx, ~3xx, ~4xx, ~5xx")]
+		pub span: Span,
+
+		pub range: Symbol,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("unknown status code name `{name}`")]
+	pub struct UnknownStatusName {
+		#[label("expected a status name like `NOT_FOUND`")]
+		pub name: Ident,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("expected a status code")]
+	pub struct ExpectedStatusCode {
+		#[label(
+			"expected a number (e.g. `200`), a range (e.g. `~2xx`), a status name or `default`"
+		)]
+		pub span: Span,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("status `{status}` has multiple responses")]
+	pub struct DuplicateStatusCode {
+		#[label("`{status}` redefined here")]
+		pub span: Span,
+		#[label("previous response for `{status}` here")]
+		pub previous: Span,
+
+		pub status: Status,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("invalid media type")]
+	pub struct InvalidMediaType {
+		#[label("expected a string (e.g. \"json\") or an array of strings")]
+		pub span: Span,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("unknown media type `{media_type}`")]
+	pub struct UnknownMediaType {
+		#[label("expected a MIME type (e.g. \"application/xml\") or one of: {expected}")]
+		pub span: Span,
+
+		pub media_type: Symbol,
+		pub expected: String,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("media type `{media_type}` is used by multiple bodies")]
+	pub struct DuplicateMediaType {
+		#[label("`{media_type}` used again here")]
+		pub span: Span,
+		#[label("previously used here")]
+		pub previous: Span,
+
+		pub media_type: MediaType,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("path parameter `{name}` cannot have a default value")]
+	pub struct DefaultPathParam {
+		#[label("path parameters are always part of the url")]
+		pub name: Ident,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("path parameter `{name}` cannot be optional")]
+	pub struct OptionalPathParam {
+		#[label("remove the `?`, path parameters are always required")]
+		pub name: Ident,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("path parameter `{name}` is not a variable of the path")]
+	pub struct UnusedPathParam {
+		#[label("no enclosing path has a `{{{name}}}` segment")]
+		pub name: Ident,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("path parameter `{name}` is defined multiple times")]
+	pub struct DuplicatePathParam {
+		#[label("`{name}` redefined here")]
+		pub name: Ident,
+		#[label("previous definition of `{name}` here")]
+		pub previous: Span,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("path variable `{name}` is used multiple times")]
+	pub struct DuplicatePathVariable {
+		#[label("`{name}` used again here")]
+		pub name: Ident,
+		#[label("first use of `{name}` here")]
+		pub previous: Span,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("operation `{method} {path}` is defined multiple times")]
+	pub struct DuplicateOperation {
+		#[label("`{method} {path}` redefined here")]
+		pub method: Ident,
+		#[label("previous definition of `{method} {path}` here")]
+		pub previous: Span,
+
+		pub path: String,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("operations `{operation}` and `{previous_operation}` have the same id `{id}`")]
+	pub struct DuplicateOperationId {
+		#[label("`{operation}` is named `{id}`")]
+		pub span: Span,
+		#[label("`{previous_operation}` is also named `{id}`")]
+		pub previous: Span,
+
+		pub id: String,
+		pub operation: String,
+		pub previous_operation: String,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("path variable `{name}` is defined as a {location}")]
+	pub struct MisplacedPathVariable {
+		#[label("define `{name}` in a `params` block instead")]
+		pub name: Ident,
+		#[label("`{name}` is a variable of this path")]
+		pub variable: Span,
+
+		pub location: &'static str,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("`@{name}` cannot be used on {found}")]
+	pub struct UnexpectedConstraint {
+		#[label("`@{name}` only applies to {target}")]
+		pub span: Span,
+
+		pub name: Symbol,
+		pub target: ConstraintTarget,
+		pub found: &'static str,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("invalid value for `@{name}`")]
+	pub struct InvalidConstraintValue {
+		#[label("expected {expected}")]
+		pub span: Span,
+
+		pub name: Symbol,
+		pub expected: &'static str,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("`@{lower}` is greater than `@{upper}`")]
+	pub struct ConflictingConstraints {
+		#[label("no value can satisfy this bound")]
+		pub span: Span,
+		#[label("and this one")]
+		pub previous: Span,
+
+		pub lower: Symbol,
+		pub upper: Symbol,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("invalid example")]
+	pub struct InvalidExample {
+		#[label("expected a literal or an array of literals, e.g. `@example(\"rex\")`")]
+		pub span: Span,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("type alias `{name}` is recursive")]
+	pub struct RecursiveTypeAlias {
+		#[label("`{name}` refers to itself, use a model to define recursive types")]
+		pub name: Ident,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("invalid base type")]
+	pub struct InvalidBase {
+		#[label("only models can be extended")]
+		pub span: Span,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("model `{name}` is recursive")]
+	pub struct RecursiveModel {
+		#[label("`{name}` inherits from itself")]
+		pub name: Ident,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("field `{name}` is defined multiple times")]
+	pub struct ConflictingField {
+		#[label("`{name}` redefined here")]
+		pub name: Ident,
+		#[label("previous definition of `{name}` here")]
+		pub previous: Span,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("wrong number of type arguments")]
+	pub struct WrongTypeArgCount {
+		#[label("expected {expected} type arguments, found {found}")]
+		pub span: Span,
+
+		pub expected: usize,
+		pub found: usize,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("generic model `{name}` instantiates itself endlessly")]
+	pub struct RecursiveInstantiation {
+		#[label("the arguments of `{name}` grow with every instantiation")]
+		pub span: Span,
+
+		pub name: Ident,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("invalid enum type")]
+	pub struct InvalidEnumType {
+		#[label("enums are backed by strings or integers")]
+		pub span: Span,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("variant `{name}` is defined multiple times")]
+	pub struct DuplicateVariant {
+		#[label("`{name}` redefined here")]
+		pub name: Ident,
+		#[label("previous definition of `{name}` here")]
+		pub previous: Span,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("enum value is used by multiple variants")]
+	pub struct DuplicateVariantValue {
+		#[label("value used again here")]
+		pub span: Span,
+		#[label("previously used here")]
+		pub previous: Span,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("`{name}` is used in its own definition")]
+	pub struct RecursiveBlock {
+		#[label("`{name}` used here")]
+		pub name: Ident,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("invalid map key type")]
+	pub struct InvalidMapKey {
+		#[label("map keys must be strings or enums")]
+		pub span: Span,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("invalid variant `{name}` in union")]
+	pub struct InvalidUnionVariant {
+		#[label("{reason}")]
+		pub span: Span,
+
+		pub name: Ident,
+		pub reason: &'static str,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("model `{model}` has no discriminator field `{discriminator}`")]
+	pub struct MissingDiscriminator {
+		#[label("variants of the union must have a `{discriminator}` field")]
+		pub span: Span,
+
+		pub model: Ident,
+		pub discriminator: Ident,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("invalid discriminator field `{discriminator}`")]
+	pub struct InvalidDiscriminator {
+		#[label("discriminators must be required strings")]
+		pub span: Span,
+
+		pub discriminator: Ident,
+	}
+
+	#[derive(Debug, IntoDiagnostic)]
+	#[message("value does not match the type of the field")]
+	pub struct MismatchedValue {
+		#[label("expected {expected}")]
+		pub span: Span,
+
+		pub expected: String,
+	}
+}
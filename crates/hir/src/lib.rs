@@ -1,50 +1,142 @@
 //! Declarative API high-level intermediate representation
 //!
-//! Entrypoint is [`HirLowerer::lower_root`]. Takes an [AST `Root`](dapic_ast::types::Root) and lowers it to a more
+//! Entrypoint is [`compile_hir`]. Takes an [AST `Root`](dapic_ast::types::Root) and lowers it to a more
 //! queryable form: [HIR `Root`](crate::types::Root). This form is used to easily resolve types.
+//!
+//! Type paths are resolved to the `model`s and `enum`s they refer to while lowering.
 
-use crate::types::Root;
+use crate::{
+	resolve::{DefCollector, ModuleId, Resolver},
+	types::{DefId, Expr, HirId, Item, Root, Ty, TyKind},
+};
 use bumpalo::Bump;
 use dapic_ast::types as ast;
-use dapic_session::IndexVec;
-use std::marker::PhantomData;
+use dapic_session::{Idx, IndexVec, Session, Symbol};
+use std::{collections::HashMap, mem};
 
+mod errors;
+mod lower;
+mod resolve;
 pub mod types;
 
+/// Arena in which every HIR node is allocated. It must outlive the lowered [`Root`].
+pub type Arena = Bump;
+
+/// Lowers the expanded `AST` to HIR, reporting resolution errors to the session.
+///
+/// Every [`NodeId`](dapic_ast::types::NodeId) must have been assigned beforehand.
 #[must_use]
-pub fn compile_hir(crate_: &ast::Root) -> Root {
-	let lcx = LoweringContext::new();
+pub fn compile_hir<'tcx>(session: &Session, arena: &'tcx Arena, crate_: &ast::Root) -> Root<'tcx> {
+	let resolver = DefCollector::collect(session, crate_);
+	let lcx = LoweringContext::new(session, arena);
 
-	HirLowerer { lcx }.lower_root(crate_)
+	HirLowerer::new(lcx, resolver).lower_root(crate_)
 }
 
-struct LoweringContext<'tcx> {
-	arena: Bump,
-	_marker: PhantomData<&'tcx ()>,
+struct LoweringContext<'a, 'tcx> {
+	session: &'a Session,
+	arena: &'tcx Bump,
+	next_hir_id: HirId,
 }
 
-impl LoweringContext<'_> {
-	fn new() -> Self {
+impl<'a, 'tcx> LoweringContext<'a, 'tcx> {
+	fn new(session: &'a Session, arena: &'tcx Bump) -> Self {
 		Self {
-			arena: Bump::new(),
-			_marker: PhantomData,
+			session,
+			arena,
+			next_hir_id: HirId::new(0),
 		}
 	}
+
+	fn next_id(&mut self) -> HirId {
+		let next = self.next_hir_id.inc();
+		mem::replace(&mut self.next_hir_id, next)
+	}
 }
 
-pub struct HirLowerer<'tcx> {
-	lcx: LoweringContext<'tcx>,
+pub struct HirLowerer<'a, 'tcx> {
+	lcx: LoweringContext<'a, 'tcx>,
+	resolver: Resolver,
+
+	/// Scope in which type paths are currently resolved.
+	current_module: ModuleId,
+	/// Lowered item of each definition, filled as they are lowered.
+	def_items: IndexVec<DefId, Option<&'tcx Item<'tcx>>>,
+	/// Defaults and examples, checked against their type once every
+	/// definition is lowered.
+	values: Vec<(&'tcx Expr<'tcx>, &'tcx Ty<'tcx>)>,
+
+	/// Type parameters of the generic model being lowered, mapped to the
+	/// arguments of the instance. They are unresolved in the generic model itself.
+	ty_params: HashMap<Symbol, Option<TyKind<'tcx>>>,
+	/// Instances of generic models, by generic model and arguments.
+	instances: HashMap<(DefId, String), DefId>,
+	/// Number of nested instantiations, bounded to stop on models whose
+	/// arguments grow on each instantiation.
+	instance_depth: usize,
 }
 
-impl<'tcx> HirLowerer<'tcx> {
-	const fn new(lcx: LoweringContext<'tcx>) -> Self {
-		Self { lcx }
+impl<'a, 'tcx> HirLowerer<'a, 'tcx> {
+	fn new(lcx: LoweringContext<'a, 'tcx>, resolver: Resolver) -> Self {
+		let def_items = resolver.defs.iter().map(|_| None).collect();
+
+		Self {
+			lcx,
+			resolver,
+			current_module: ModuleId::ROOT,
+			def_items,
+			values: Vec::new(),
+			ty_params: HashMap::new(),
+			instances: HashMap::new(),
+			instance_depth: 0,
+		}
 	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{compile_hir, types::Root};
+	use bumpalo::Bump;
+	use dapic_ast::visit_mut::MutVisitor;
+	use dapic_expand::NodeExpander;
+	use dapic_parser::Parser;
+	use dapic_session::{DiagnosticsHandler, Session};
+
+	/// A `meta` block with the required properties, to prefix sources with.
+	pub(crate) const META: &str = r#"meta { name "Test" version "1" }"#;
+
+	/// Lowers `src` and gives the HIR to `f`, along with whether errors were emitted.
+	///
+	/// Only inline scopes can be used as external ones can't be loaded.
+	pub(crate) fn with_hir<T>(src: &str, f: impl FnOnce(&Root<'_>) -> T) -> (T, bool) {
+		lower(src, |hir, diagnostics| (f(hir), diagnostics.has_errors()))
+	}
+
+	/// Checks that the first diagnostic reported for every source is the one
+	/// paired with it, given by the name of its type.
+	#[track_caller]
+	pub(crate) fn assert_reports(cases: impl IntoIterator<Item = (impl AsRef<str>, &'static str)>) {
+		for (src, expected) in cases {
+			let src = src.as_ref();
+			let codes = lower(src, |_, diagnostics| diagnostics.emitted_codes());
+			assert_eq!(codes.first().map(String::as_str), Some(expected), "{src}");
+		}
+	}
+
+	fn lower<T>(src: &str, f: impl FnOnce(&Root<'_>, &DiagnosticsHandler) -> T) -> T {
+		let mut session = Session::default();
+
+		session.enter_source_map_ctx(|session| {
+			let sf = session.source_map.load_anon(src.into());
+			let mut ast = Parser::from_source(&session.parse_sess(), &sf)
+				.parse_root()
+				.expect("source should parse");
+			NodeExpander::default().visit_root(&mut ast);
 
-	pub fn lower_root(&mut self, crate_: &ast::Root) -> Root {
-		// crate_.
+			let arena = Bump::new();
+			let hir = compile_hir(session, &arena, &ast);
 
-		let items = IndexVec::default();
-		Root { items }
+			f(&hir, &session.diagnostics)
+		})
 	}
 }
@@ -0,0 +1,38 @@
+use crate::{
+	HirLowerer,
+	types::{AttrKind, Attribute, MetaAttr, NormalAttr},
+};
+use dapic_ast::types::{self as ast};
+
+impl<'tcx> HirLowerer<'_, 'tcx> {
+	pub(super) fn lower_attrs(&mut self, attrs: &ast::AttrVec) -> &'tcx [Attribute<'tcx>] {
+		let arena = self.lcx.arena;
+		arena.alloc_slice_fill_iter(attrs.iter().map(|attr| self.lower_attr(attr)))
+	}
+
+	fn lower_attr(&mut self, attr: &ast::Attribute) -> Attribute<'tcx> {
+		let kind = match &attr.kind {
+			ast::AttrKind::Normal(ast::NormalAttr {
+				path,
+				delim,
+				tokens,
+			}) => AttrKind::Normal(NormalAttr {
+				path: *path,
+				delim: *delim,
+				tokens: self.lcx.arena.alloc_slice_clone(tokens),
+			}),
+			ast::AttrKind::Meta(ast::MetaAttr { ident, expr }) => AttrKind::Meta(MetaAttr {
+				ident: *ident,
+				expr: expr.as_ref().map(|expr| self.lower_expr(expr)),
+			}),
+			ast::AttrKind::DocComment(symbol) => AttrKind::DocComment(*symbol),
+		};
+
+		Attribute {
+			kind,
+			style: attr.style,
+			id: self.lcx.next_id(),
+			span: attr.span,
+		}
+	}
+}
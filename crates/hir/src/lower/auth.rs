@@ -0,0 +1,284 @@
+use crate::{
+	HirLowerer,
+	errors::lower::{
+		DuplicateProperty, InvalidAuthValue, MissingAuthFlowProperty, MissingAuthProperty,
+		UnexpectedAuthFlow, UnknownAuthFlow, UnknownAuthProperty,
+	},
+	types::{
+		ApiKeyLocation, Auth, AuthFlow, AuthFlowKind, AuthScheme, AuthScope, Expr, PropertyDef, Res,
+	},
+};
+use dapic_ast::types::{self as ast};
+use dapic_lexer::rich::LiteralKind;
+use dapic_session::{
+	Ident, Span, Symbol,
+	symbols::{auth, kw, meta},
+};
+use std::collections::HashMap;
+
+impl<'tcx> HirLowerer<'_, 'tcx> {
+	pub(super) fn lower_auth(&mut self, name: Ident, auth: &ast::Auth) -> Auth<'tcx> {
+		match auth {
+			ast::Auth::Use { scopes } => {
+				let res = match self.resolver.resolve_auth(self.current_module, name) {
+					Ok(res) => res,
+					Err(diag) => {
+						self.lcx.session.diagnostics.emit_diagnostic(&diag);
+						Res::Err
+					}
+				};
+
+				let scopes = scopes.as_ref().map(|scopes| self.lower_expr(scopes));
+				let names = scopes.map_or_else(|| Some(Vec::new()), Expr::str_array);
+				if let (Some(scopes), None) = (scopes, &names) {
+					self.lcx.session.diagnostics.emit(InvalidAuthValue {
+						span: scopes.span,
+						key: auth::scopes,
+						expected: "an array of strings".into(),
+					});
+				}
+				let scopes = self.lcx.arena.alloc_slice_copy(&names.unwrap_or_default());
+
+				Auth::Use { res, scopes }
+			}
+			ast::Auth::Define(def) => Auth::Define(self.lower_auth_scheme(name, def)),
+		}
+	}
+
+	fn lower_auth_scheme(&mut self, name: Ident, def: &ast::AuthDef) -> AuthScheme<'tcx> {
+		const SCHEMES: &[&str] = &["apiKey", "http", "oauth2", "openIdConnect"];
+
+		let properties = self.lower_property_defs(&def.properties);
+		let diagnostics = &self.lcx.session.diagnostics;
+
+		let Some(ty) = self.required_property(name, properties, kw::Type) else {
+			return AuthScheme::Err;
+		};
+
+		let allowed: &[Symbol] = match ty.as_str() {
+			"apiKey" => &[kw::Type, meta::name, auth::r#in],
+			"http" => &[kw::Type, auth::scheme, auth::bearerFormat],
+			"oauth2" => &[kw::Type],
+			"openIdConnect" => &[kw::Type, auth::url],
+			_ => {
+				diagnostics.emit(InvalidAuthValue {
+					span: property_span(properties, kw::Type),
+					key: kw::Type,
+					expected: format!("one of: {}", SCHEMES.join(", ")),
+				});
+				return AuthScheme::Err;
+			}
+		};
+		self.check_properties(properties, allowed);
+
+		if ty.as_str() != "oauth2" {
+			for flow in &def.flows {
+				diagnostics.emit(UnexpectedAuthFlow { span: flow.span });
+			}
+		}
+
+		match ty.as_str() {
+			"apiKey" => {
+				let key_name = self.required_property(name, properties, meta::name);
+				let location = self
+					.required_property(name, properties, auth::r#in)
+					.and_then(|location| {
+						let parsed = ApiKeyLocation::from_name(location.as_str());
+						if parsed.is_none() {
+							diagnostics.emit(InvalidAuthValue {
+								span: property_span(properties, auth::r#in),
+								key: auth::r#in,
+								expected: format!(
+									"one of: {}",
+									ApiKeyLocation::ALL
+										.iter()
+										.map(ToString::to_string)
+										.collect::<Vec<_>>()
+										.join(", ")
+								),
+							});
+						}
+						parsed
+					});
+
+				match (key_name, location) {
+					(Some(name), Some(location)) => AuthScheme::ApiKey { name, location },
+					_ => AuthScheme::Err,
+				}
+			}
+			"http" => {
+				let bearer_format = self.optional_property(properties, auth::bearerFormat);
+
+				self.required_property(name, properties, auth::scheme)
+					.map_or(AuthScheme::Err, |scheme| AuthScheme::Http {
+						scheme,
+						bearer_format,
+					})
+			}
+			"oauth2" => {
+				if def.flows.is_empty() {
+					diagnostics.emit(MissingAuthProperty {
+						name,
+						property: auth::flow,
+					});
+					return AuthScheme::Err;
+				}
+
+				let flows = def
+					.flows
+					.iter()
+					.filter_map(|flow| self.lower_auth_flow(flow))
+					.collect::<Vec<_>>();
+
+				AuthScheme::OAuth2 {
+					flows: self.lcx.arena.alloc_slice_fill_iter(flows),
+				}
+			}
+			"openIdConnect" => self
+				.required_property(name, properties, auth::url)
+				.map_or(AuthScheme::Err, |url| AuthScheme::OpenIdConnect { url }),
+			_ => unreachable!("unknown schemes are reported above"),
+		}
+	}
+
+	fn lower_auth_flow(&mut self, flow: &ast::AuthFlow) -> Option<AuthFlow<'tcx>> {
+		let properties = self.lower_property_defs(&flow.properties);
+		self.check_properties(
+			properties,
+			&[auth::authorizationUrl, auth::tokenUrl, auth::refreshUrl],
+		);
+
+		let Some(kind) = AuthFlowKind::from_name(flow.kind.symbol.as_str()) else {
+			self.lcx.session.diagnostics.emit(UnknownAuthFlow {
+				kind: flow.kind,
+				expected: AuthFlowKind::ALL
+					.iter()
+					.map(ToString::to_string)
+					.collect::<Vec<_>>()
+					.join(", "),
+			});
+			return None;
+		};
+
+		let authorization_url = self.optional_property(properties, auth::authorizationUrl);
+		let token_url = self.optional_property(properties, auth::tokenUrl);
+
+		let mut missing = false;
+		for (property, value, required) in [
+			(
+				auth::authorizationUrl,
+				authorization_url,
+				kind.requires_authorization_url(),
+			),
+			(auth::tokenUrl, token_url, kind.requires_token_url()),
+		] {
+			if required && value.is_none() {
+				missing = true;
+				if !properties.iter().any(|prop| prop.ident.symbol == property) {
+					self.lcx.session.diagnostics.emit(MissingAuthFlowProperty {
+						span: flow.kind.span,
+						kind,
+						property,
+					});
+				}
+			}
+		}
+		if missing {
+			return None;
+		}
+
+		let scopes = flow
+			.scopes
+			.iter()
+			.filter_map(|scope| {
+				let ast::ExprKind::Literal(LiteralKind::Str, name) = scope.kind else {
+					self.lcx.session.diagnostics.emit(InvalidAuthValue {
+						span: scope.span,
+						key: auth::scopes,
+						expected: "a string".into(),
+					});
+					return None;
+				};
+
+				Some(AuthScope {
+					attrs: self.lower_attrs(&scope.attrs),
+					name,
+					span: scope.span,
+				})
+			})
+			.collect::<Vec<_>>();
+
+		Some(AuthFlow {
+			kind,
+			authorization_url,
+			token_url,
+			refresh_url: self.optional_property(properties, auth::refreshUrl),
+			scopes: self.lcx.arena.alloc_slice_fill_iter(scopes),
+			span: flow.span,
+		})
+	}
+
+	/// Reports properties which are not `allowed` or defined multiple times.
+	fn check_properties(&self, properties: &[PropertyDef<'_>], allowed: &[Symbol]) {
+		let diagnostics = &self.lcx.session.diagnostics;
+		let mut seen = HashMap::new();
+
+		for property in properties {
+			if !allowed.contains(&property.ident.symbol) {
+				diagnostics.emit(UnknownAuthProperty {
+					key: property.ident,
+					expected: allowed
+						.iter()
+						.map(Symbol::as_str)
+						.collect::<Vec<_>>()
+						.join(", "),
+				});
+			} else if let Some(previous) = seen.insert(property.ident.symbol, property.ident.span) {
+				diagnostics.emit(DuplicateProperty {
+					key: property.ident,
+					previous,
+				});
+			}
+		}
+	}
+
+	/// Value of a string property, reported when missing.
+	fn required_property(
+		&self,
+		name: Ident,
+		properties: &[PropertyDef<'_>],
+		key: Symbol,
+	) -> Option<Symbol> {
+		if !properties.iter().any(|prop| prop.ident.symbol == key) {
+			self.lcx.session.diagnostics.emit(MissingAuthProperty {
+				name,
+				property: key,
+			});
+		}
+
+		self.optional_property(properties, key)
+	}
+
+	/// Value of a string property, reported when it is not a string.
+	fn optional_property(&self, properties: &[PropertyDef<'_>], key: Symbol) -> Option<Symbol> {
+		let property = properties.iter().find(|prop| prop.ident.symbol == key)?;
+
+		let value = property.expr.str_literal();
+		if value.is_none() {
+			self.lcx.session.diagnostics.emit(InvalidAuthValue {
+				span: property.expr.span,
+				key,
+				expected: "a string".into(),
+			});
+		}
+
+		value
+	}
+}
+
+fn property_span(properties: &[PropertyDef<'_>], key: Symbol) -> Span {
+	properties
+		.iter()
+		.find(|prop| prop.ident.symbol == key)
+		.map_or(Span::DUMMY, |prop| prop.expr.span)
+}
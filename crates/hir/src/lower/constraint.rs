@@ -0,0 +1,151 @@
+use crate::{
+	HirLowerer,
+	errors::lower::{
+		ConflictingConstraints, DuplicateProperty, InvalidConstraintValue, InvalidFormat,
+		UnexpectedConstraint, UnexpectedFormat,
+	},
+	types::{
+		AttrKind, Attribute, Constraint, ConstraintTarget, ConstraintValue, Expr, ExprKind,
+		MetaAttr, Res, Ty, TyKind,
+	},
+};
+use dapic_lexer::rich::LiteralKind;
+use dapic_session::{JsonTy, PrimTy, Span, symbols::attrs};
+
+impl<'tcx> HirLowerer<'_, 'tcx> {
+	/// Parses the constraint attributes and checks them against the type.
+	pub(super) fn lower_constraints(
+		&self,
+		attrs: &[Attribute<'_>],
+		ty: &Ty<'_>,
+	) -> &'tcx [Constraint] {
+		let diagnostics = &self.lcx.session.diagnostics;
+		let target = ConstraintTarget::of(ty);
+		let integer = matches!(
+			ty.non_null().kind,
+			TyKind::Path(_, Res::PrimTy(prim)) if prim.json_ty() == Some(JsonTy::Integer)
+		);
+
+		let mut constraints: Vec<(Constraint, Span)> = Vec::new();
+		for attr in attrs {
+			let AttrKind::Meta(MetaAttr { ident, expr }) = attr.kind else {
+				continue;
+			};
+			let Some((applies_to, expected)) = Constraint::signature(ident.symbol) else {
+				continue;
+			};
+
+			if let Some((_, previous)) = constraints
+				.iter()
+				.find(|(constraint, _)| constraint.name() == ident.symbol)
+			{
+				diagnostics.emit(DuplicateProperty {
+					key: ident,
+					previous: *previous,
+				});
+				continue;
+			}
+
+			let found = match target {
+				Some(Ok(target)) if target != applies_to => Some(target.noun()),
+				Some(Err(found)) => Some(found),
+				_ => None,
+			};
+			if let Some(found) = found {
+				diagnostics.emit(UnexpectedConstraint {
+					span: attr.span,
+					name: ident.symbol,
+					target: applies_to,
+					found,
+				});
+				continue;
+			}
+
+			let value = match (expected, expr.map(|expr| &expr.kind)) {
+				(ConstraintValue::None, None) => Some(None),
+				(
+					ConstraintValue::Number | ConstraintValue::Count,
+					Some(ExprKind::Literal(LiteralKind::Number, value)),
+				)
+				| (ConstraintValue::Str, Some(ExprKind::Literal(LiteralKind::Str, value))) => {
+					Some(Some(*value))
+				}
+				_ => None,
+			};
+			// Bounds of integers are emitted as integers
+			let value = value.filter(|value| {
+				!(integer && value.is_some_and(|value| value.as_str().contains('.')))
+			});
+
+			match value.and_then(|value| Constraint::from_value(ident.symbol, value)) {
+				Some(constraint) => constraints.push((constraint, attr.span)),
+				None => diagnostics.emit(InvalidConstraintValue {
+					span: expr.map_or(attr.span, |expr| expr.span),
+					name: ident.symbol,
+					expected: if integer && expected == ConstraintValue::Number {
+						"an integer"
+					} else {
+						expected.expected()
+					},
+				}),
+			}
+		}
+
+		for (lower, lower_span) in &constraints {
+			for (upper, upper_span) in &constraints {
+				let conflicting = match (lower, upper) {
+					(Constraint::Min(lower), Constraint::Max(upper)) => lower > upper,
+					(Constraint::MinLength(lower), Constraint::MaxLength(upper))
+					| (Constraint::MinItems(lower), Constraint::MaxItems(upper)) => lower > upper,
+					_ => false,
+				};
+
+				if conflicting {
+					diagnostics.emit(ConflictingConstraints {
+						span: *upper_span,
+						previous: *lower_span,
+						lower: lower.name(),
+						upper: upper.name(),
+					});
+				}
+			}
+		}
+
+		let arena = self.lcx.arena;
+		arena.alloc_slice_fill_iter(constraints.into_iter().map(|(constraint, _)| constraint))
+	}
+
+	/// Checks `@format` attributes against the formats allowed by the primitive.
+	pub(super) fn check_format(&self, attrs: &[Attribute<'_>], prim: PrimTy) {
+		let formats = attrs.iter().filter_map(|attr| match attr.kind {
+			AttrKind::Meta(MetaAttr {
+				ident,
+				expr:
+					Some(Expr {
+						kind: ExprKind::Literal(LiteralKind::Str, format),
+						span,
+						..
+					}),
+			}) if ident.symbol == attrs::format => Some((*format, *span)),
+			_ => None,
+		});
+
+		for (format, span) in formats {
+			let allowed = prim.allowed_formats();
+
+			if allowed.is_empty() {
+				self.lcx
+					.session
+					.diagnostics
+					.emit(UnexpectedFormat { span, prim });
+			} else if !allowed.contains(&format.as_str()) {
+				self.lcx.session.diagnostics.emit(InvalidFormat {
+					span,
+					format,
+					prim,
+					allowed: allowed.join(", "),
+				});
+			}
+		}
+	}
+}
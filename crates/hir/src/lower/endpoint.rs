@@ -0,0 +1,223 @@
+use crate::{
+	HirLowerer,
+	errors::lower::{
+		DefaultPathParam, DuplicateOperation, DuplicateOperationId, DuplicatePathParam,
+		DuplicatePathVariable, MisplacedPathVariable, OptionalPathParam, UnusedPathParam,
+	},
+	types::{
+		FieldDef, Headers, Item, ItemKind, Path, PathKind, PathSegment, Query, Res, Root, Ty,
+		TyKind,
+	},
+};
+use dapic_ast::types::{self as ast, P};
+use dapic_session::{Ident, PrimTy, Span};
+use std::{collections::HashMap, slice};
+
+impl<'tcx> HirLowerer<'_, 'tcx> {
+	pub(super) fn lower_params(
+		&mut self,
+		properties: &[P<ast::FieldDef>],
+	) -> &'tcx [FieldDef<'tcx>] {
+		let properties = self.lower_field_defs(properties);
+
+		// Path variables are always part of the url
+		for param in properties {
+			if param.optional {
+				self.lcx
+					.session
+					.diagnostics
+					.emit(OptionalPathParam { name: param.ident });
+			}
+			if param.default.is_some() {
+				self.lcx
+					.session
+					.diagnostics
+					.emit(DefaultPathParam { name: param.ident });
+			}
+		}
+
+		properties
+	}
+
+	pub(super) fn lower_path_kind(&mut self, kind: &ast::PathKind) -> PathKind<'tcx> {
+		match kind {
+			ast::PathKind::Simple(ident) => PathKind::Simple(*ident),
+			ast::PathKind::Variable(ident) => PathKind::Variable(*ident),
+			ast::PathKind::Current => PathKind::Current,
+			ast::PathKind::Complex(parts) => {
+				let arena = self.lcx.arena;
+				PathKind::Complex(
+					arena
+						.alloc_slice_fill_iter(parts.iter().map(|part| self.lower_path_kind(part))),
+				)
+			}
+		}
+	}
+
+	/// Variables of a path, as `string` parameters.
+	pub(super) fn lower_path_variables(&mut self, kind: &PathKind<'_>) -> &'tcx [FieldDef<'tcx>] {
+		fn collect(kind: &PathKind<'_>, variables: &mut Vec<Ident>) {
+			match kind {
+				PathKind::Variable(ident) => variables.push(*ident),
+				PathKind::Complex(kinds) => kinds.iter().for_each(|kind| collect(kind, variables)),
+				PathKind::Simple(_) | PathKind::Current => {}
+			}
+		}
+
+		let mut variables = Vec::new();
+		collect(kind, &mut variables);
+
+		let arena = self.lcx.arena;
+		arena.alloc_slice_fill_iter(variables.into_iter().map(|ident| {
+			let segments = slice::from_ref(arena.alloc(PathSegment {
+				ident: Ident::new(PrimTy::String.symbol(), ident.span),
+				id: self.lcx.next_id(),
+			}));
+			let path = arena.alloc(Path {
+				segments,
+				span: ident.span,
+			});
+
+			FieldDef {
+				attrs: &[],
+				ident,
+				optional: false,
+				ty: arena.alloc(Ty {
+					kind: TyKind::Path(path, Res::PrimTy(PrimTy::String)),
+					id: self.lcx.next_id(),
+					span: ident.span,
+				}),
+				default: None,
+				example: None,
+				constraints: &[],
+				id: self.lcx.next_id(),
+				span: ident.span,
+			}
+		}))
+	}
+
+	/// Reports path variables defined twice along a path, `params` fields
+	/// matching no variable of the enclosing paths and variables defined as
+	/// query parameters or headers.
+	///
+	/// `params` blocks apply to the variables of their path and the enclosing
+	/// ones, blocks of a path are checked before the nested paths and verbs.
+	pub(super) fn check_path_params(
+		&self,
+		items: &[Item<'_>],
+		variables: &mut Vec<Ident>,
+		params: &mut Vec<Ident>,
+	) {
+		let diagnostics = &self.lcx.session.diagnostics;
+		let find = |idents: &[Ident], name: Ident| {
+			idents
+				.iter()
+				.find(|ident| ident.symbol == name.symbol)
+				.copied()
+		};
+		let items = items.iter().filter(|item| !item.is_shared_block());
+
+		for item in items.clone() {
+			match &item.kind {
+				ItemKind::Params(block) => {
+					for field in block.properties {
+						if find(variables, field.ident).is_none() {
+							diagnostics.emit(UnusedPathParam { name: field.ident });
+						} else if let Some(previous) = find(params, field.ident) {
+							diagnostics.emit(DuplicatePathParam {
+								name: field.ident,
+								previous: previous.span,
+							});
+						} else {
+							params.push(field.ident);
+						}
+					}
+				}
+				ItemKind::Query(Query { fields, .. })
+				| ItemKind::Headers(Headers {
+					headers: fields, ..
+				}) => {
+					let location = if matches!(item.kind, ItemKind::Query(_)) {
+						"query parameter"
+					} else {
+						"header"
+					};
+					for field in *fields {
+						if let Some(variable) = find(variables, field.ident) {
+							diagnostics.emit(MisplacedPathVariable {
+								name: field.ident,
+								variable: variable.span,
+								location,
+							});
+						}
+					}
+				}
+				_ => {}
+			}
+		}
+
+		for item in items {
+			let (variables_len, params_len) = (variables.len(), params.len());
+
+			match &item.kind {
+				ItemKind::Scope(scope) => self.check_path_params(scope.items, variables, params),
+				ItemKind::Path(path) => {
+					for variable in path.variables {
+						if let Some(previous) = find(variables, variable.ident) {
+							diagnostics.emit(DuplicatePathVariable {
+								name: variable.ident,
+								previous: previous.span,
+							});
+						}
+						variables.push(variable.ident);
+					}
+					self.check_path_params(path.items, variables, params);
+				}
+				ItemKind::Verb(verb) => self.check_path_params(verb.items, variables, params),
+				_ => {}
+			}
+
+			variables.truncate(variables_len);
+			params.truncate(params_len);
+		}
+	}
+
+	/// Reports operations with the same method and path, e.g. in two scopes,
+	/// and operations whose ids collide, e.g. `pet-store` and `pet_store`.
+	/// Generators key operations by both.
+	pub(super) fn check_operations(&self, root: &Root<'tcx>) {
+		let diagnostics = &self.lcx.session.diagnostics;
+		let mut operations = HashMap::new();
+		let mut ids = HashMap::<_, (Span, String)>::new();
+
+		for endpoint in root.endpoints() {
+			let method = endpoint.verb.method;
+			let path = endpoint.path();
+
+			if let Some(&previous) = operations.get(&(method.symbol, path.clone())) {
+				// Their ids are the same too
+				diagnostics.emit(DuplicateOperation {
+					method,
+					previous,
+					path,
+				});
+				continue;
+			}
+			operations.insert((method.symbol, path.clone()), method.span);
+
+			let id = endpoint.operation_id();
+			let operation = format!("{method} {path}");
+			if let Some((previous, previous_operation)) = ids.get(&id) {
+				diagnostics.emit(DuplicateOperationId {
+					span: method.span,
+					previous: *previous,
+					id,
+					operation,
+					previous_operation: previous_operation.clone(),
+				});
+			} else {
+				ids.insert(id, (method.span, operation));
+			}
+		}
+	}
+}
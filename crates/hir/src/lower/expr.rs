@@ -0,0 +1,410 @@
+use crate::{
+	HirLowerer,
+	errors::lower::{InvalidExample, InvalidMapKey, MismatchedValue},
+	lower::mismatched_def_kind,
+	types::{
+		AttrKind, Attribute, DefId, DefKind, Definition, Expr, ExprKind, FieldDef, ItemKind,
+		MetaAttr, NormalAttr, Path, PathSegment, PropertyDef, Res, Ty, TyKind,
+	},
+};
+use dapic_ast::types::{self as ast, P};
+use dapic_lexer::rich::{Delimiter, LiteralKind, OpKind, Token, TokenKind};
+use dapic_session::{
+	IndexVec, JsonTy, Span, Symbol,
+	symbols::{attrs, kw},
+};
+use std::slice;
+
+impl<'tcx> HirLowerer<'_, 'tcx> {
+	// --- Definitions ---
+	pub(super) fn lower_field_defs(
+		&mut self,
+		fields: &[P<ast::FieldDef>],
+	) -> &'tcx [FieldDef<'tcx>] {
+		let arena = self.lcx.arena;
+		arena.alloc_slice_fill_iter(fields.iter().map(|field| self.lower_field_def(field)))
+	}
+
+	fn lower_field_def(&mut self, field: &ast::FieldDef) -> FieldDef<'tcx> {
+		let attrs = self.lower_attrs(&field.attrs);
+		let ty = self.lower_ty(&field.ty);
+
+		if let TyKind::Path(_, Res::PrimTy(prim)) = ty.non_null().kind {
+			self.check_format(attrs, prim);
+		}
+
+		let default = field.default.as_ref().map(|expr| self.lower_expr(expr));
+		let example = self.lower_example(attrs);
+		self.values
+			.extend(default.into_iter().chain(example).map(|expr| (expr, ty)));
+
+		FieldDef {
+			attrs,
+			ident: field.ident,
+			optional: field.optional,
+			ty,
+			default,
+			example,
+			constraints: self.lower_constraints(attrs, ty),
+			id: self.lcx.next_id(),
+			span: field.span,
+		}
+	}
+
+	pub(super) fn lower_property_defs(
+		&mut self,
+		properties: &[P<ast::PropertyDef>],
+	) -> &'tcx [PropertyDef<'tcx>] {
+		let arena = self.lcx.arena;
+		arena.alloc_slice_fill_iter(properties.iter().map(|prop| self.lower_property_def(prop)))
+	}
+
+	fn lower_property_def(&mut self, property: &ast::PropertyDef) -> PropertyDef<'tcx> {
+		PropertyDef {
+			attrs: self.lower_attrs(&property.attrs),
+			ident: property.ident,
+			expr: self.lower_expr(&property.expr),
+			id: self.lcx.next_id(),
+			span: property.span,
+		}
+	}
+
+	// --- Types ---
+	pub(super) fn lower_ty(&mut self, ty: &ast::Ty) -> &'tcx Ty<'tcx> {
+		let ty = self.lower_ty_inner(ty);
+		self.lcx.arena.alloc(ty)
+	}
+
+	fn lower_ty_inner(&mut self, ty: &ast::Ty) -> Ty<'tcx> {
+		let kind = match &ty.kind {
+			ast::TyKind::Path(path) => self.lower_ty_path(path),
+			ast::TyKind::Array(ty) => TyKind::Array(self.lower_ty(ty)),
+			ast::TyKind::Tuple(tys) => TyKind::Tuple(self.lower_tys(tys.iter())),
+			// Parentheses only matter for parsing
+			ast::TyKind::Paren(ty) => return self.lower_ty_inner(ty),
+			ast::TyKind::InlineModel(fields) => TyKind::InlineModel(self.lower_field_defs(fields)),
+			ast::TyKind::Map(key, value) => {
+				let key = self.lower_ty(key);
+
+				let valid_key = match key.kind {
+					TyKind::Path(_, Res::PrimTy(prim)) => prim.json_ty() == Some(JsonTy::String),
+					TyKind::Path(
+						_,
+						Res::Def(DefKind::Enum | DefKind::TypeAlias, _) | Res::TyParam | Res::Err,
+					) => true,
+					_ => false,
+				};
+				if !valid_key {
+					self.lcx
+						.session
+						.diagnostics
+						.emit(InvalidMapKey { span: key.span });
+				}
+
+				TyKind::Map(key, self.lower_ty(value))
+			}
+			ast::TyKind::Nullable(ty) => TyKind::Nullable(self.lower_ty(ty)),
+			ast::TyKind::Union(tys) => {
+				// `T | null` is the same as `T?`
+				let (nulls, variants): (Vec<_>, Vec<_>) = tys.iter().partition(|ty| is_null(ty));
+
+				if nulls.is_empty() || variants.is_empty() {
+					TyKind::Union(self.lower_tys(tys.iter()))
+				} else {
+					let ty = match variants[..] {
+						[variant] => self.lower_ty(variant),
+						_ => self.lcx.arena.alloc(Ty {
+							kind: TyKind::Union(self.lower_tys(variants.into_iter())),
+							id: self.lcx.next_id(),
+							span: ty.span,
+						}),
+					};
+
+					TyKind::Nullable(ty)
+				}
+			}
+		};
+
+		Ty {
+			kind,
+			id: self.lcx.next_id(),
+			span: ty.span,
+		}
+	}
+
+	pub(super) fn lower_ty_path(&mut self, path: &ast::Path) -> TyKind<'tcx> {
+		let Some(last) = path.segments.last() else {
+			unreachable!("paths always have at least one segment")
+		};
+
+		// Type parameters shadow every other type
+		if let [segment] = &path.segments[..]
+			&& segment.args.is_empty()
+			&& let Some(arg) = self.ty_params.get(&segment.ident.symbol)
+		{
+			let arg = arg.clone();
+			return arg.unwrap_or_else(|| TyKind::Path(self.lower_path(path), Res::TyParam));
+		}
+
+		let res = match self.resolver.resolve_path(self.current_module, path) {
+			Ok(res) => res,
+			Err(diag) => {
+				self.lcx.session.diagnostics.emit_diagnostic(&diag);
+				Res::Err
+			}
+		};
+		let args = self.lower_tys(last.args.iter());
+
+		TyKind::Path(
+			self.lower_path(path),
+			self.instantiate(res, args, path.span),
+		)
+	}
+
+	fn lower_tys<'ast>(
+		&mut self,
+		tys: impl ExactSizeIterator<Item = &'ast P<ast::Ty>>,
+	) -> &'tcx [Ty<'tcx>] {
+		let arena = self.lcx.arena;
+		arena.alloc_slice_fill_iter(tys.map(|ty| self.lower_ty_inner(ty)))
+	}
+
+	fn lower_path(&mut self, path: &ast::Path) -> &'tcx Path<'tcx> {
+		let arena = self.lcx.arena;
+		let segments =
+			arena.alloc_slice_fill_iter(path.segments.iter().map(|segment| PathSegment {
+				ident: segment.ident,
+				id: self.lcx.next_id(),
+			}));
+
+		arena.alloc(Path {
+			segments,
+			span: path.span,
+		})
+	}
+
+	// --- Expressions ---
+	pub(super) fn lower_expr(&mut self, expr: &ast::Expr) -> &'tcx Expr<'tcx> {
+		let expr = self.lower_expr_inner(expr);
+		self.lcx.arena.alloc(expr)
+	}
+
+	fn lower_expr_inner(&mut self, expr: &ast::Expr) -> Expr<'tcx> {
+		let kind =
+			match &expr.kind {
+				ast::ExprKind::Literal(kind, symbol) => ExprKind::Literal(*kind, *symbol),
+				ast::ExprKind::Path(path) => ExprKind::Path(self.lower_path(path)),
+				ast::ExprKind::Template(template) => ExprKind::Template(*template),
+				ast::ExprKind::Array(exprs) => {
+					let arena = self.lcx.arena;
+					ExprKind::Array(arena.alloc_slice_fill_iter(
+						exprs.iter().map(|expr| self.lower_expr_inner(expr)),
+					))
+				}
+				ast::ExprKind::Field(expr, ident) => ExprKind::Field(self.lower_expr(expr), *ident),
+			};
+
+		Expr {
+			kind,
+			id: self.lcx.next_id(),
+			span: expr.span,
+		}
+	}
+
+	/// Reads the `@example: <expr>` or `@example(<tokens>)` attribute.
+	fn lower_example(&mut self, attrs: &[Attribute<'tcx>]) -> Option<&'tcx Expr<'tcx>> {
+		let attr = attrs.iter().find(|attr| match &attr.kind {
+			AttrKind::Meta(MetaAttr { ident, .. }) => ident.symbol == attrs::example,
+			AttrKind::Normal(NormalAttr { path, .. }) => path.symbol == attrs::example,
+			AttrKind::DocComment(_) => false,
+		})?;
+
+		let example = match &attr.kind {
+			AttrKind::Meta(MetaAttr { expr, .. }) => *expr,
+			AttrKind::Normal(NormalAttr { tokens, .. }) => {
+				let mut tokens = tokens.iter();
+				self.lower_token_expr(&mut tokens)
+					.filter(|_| tokens.next().is_none())
+					.map(|expr| &*self.lcx.arena.alloc(expr))
+			}
+			AttrKind::DocComment(_) => None,
+		};
+
+		if example.is_none() {
+			self.lcx
+				.session
+				.diagnostics
+				.emit(InvalidExample { span: attr.span });
+		}
+
+		example
+	}
+
+	/// Reads a literal or an array of literals from the tokens of an attribute.
+	fn lower_token_expr(&mut self, tokens: &mut slice::Iter<'_, Token>) -> Option<Expr<'tcx>> {
+		let token = tokens.next()?;
+
+		let (kind, span) = match token.kind {
+			TokenKind::Literal(kind, symbol) => (ExprKind::Literal(kind, symbol), token.span),
+			TokenKind::Ident(symbol @ (kw::True | kw::False)) => {
+				(ExprKind::Literal(LiteralKind::Bool, symbol), token.span)
+			}
+			TokenKind::Op(OpKind::Minus) => {
+				let number = tokens.next()?;
+				let TokenKind::Literal(LiteralKind::Number, symbol) = number.kind else {
+					return None;
+				};
+
+				let negative = Symbol::intern(&format!("-{symbol}"));
+				(
+					ExprKind::Literal(LiteralKind::Number, negative),
+					token.span.to(number.span),
+				)
+			}
+			TokenKind::OpenDelim(Delimiter::Bracket) => {
+				let mut exprs = Vec::new();
+				let close = loop {
+					if let Some(close) = tokens
+						.clone()
+						.next()
+						.filter(|token| token.kind == TokenKind::CloseDelim(Delimiter::Bracket))
+					{
+						tokens.next();
+						break close;
+					}
+					exprs.push(self.lower_token_expr(tokens)?);
+				};
+
+				let exprs = self.lcx.arena.alloc_slice_fill_iter(exprs);
+				(ExprKind::Array(exprs), token.span.to(close.span))
+			}
+			_ => return None,
+		};
+
+		Some(Expr {
+			kind,
+			id: self.lcx.next_id(),
+			span,
+		})
+	}
+
+	/// Reports default and example values that don't match their type.
+	pub(super) fn check_value(
+		&self,
+		defs: &IndexVec<DefId, Definition<'tcx>>,
+		expr: &Expr<'_>,
+		ty: &Ty<'_>,
+	) {
+		if let Some((span, expected)) = value_mismatch(defs, expr, ty) {
+			self.lcx
+				.session
+				.diagnostics
+				.emit(MismatchedValue { span, expected });
+		}
+	}
+}
+
+/// Span and expectation of the first part of a default or example not matching
+/// its type.
+fn value_mismatch(
+	defs: &IndexVec<DefId, Definition<'_>>,
+	expr: &Expr<'_>,
+	ty: &Ty<'_>,
+) -> Option<(Span, String)> {
+	let expected = match (&ty.kind, &expr.kind) {
+		(TyKind::Nullable(ty), _) => return value_mismatch(defs, expr, ty),
+		(TyKind::Path(_, Res::Def(DefKind::TypeAlias, def_id)), _) => {
+			let ItemKind::TypeAlias(alias) = &defs[*def_id].item.kind else {
+				mismatched_def_kind()
+			};
+			return value_mismatch(defs, expr, alias.ty);
+		}
+		(TyKind::Array(ty), ExprKind::Array(exprs)) => {
+			return exprs.iter().find_map(|expr| value_mismatch(defs, expr, ty));
+		}
+		(TyKind::Tuple(tys), ExprKind::Array(exprs)) if tys.len() == exprs.len() => {
+			return exprs
+				.iter()
+				.zip(*tys)
+				.find_map(|(expr, ty)| value_mismatch(defs, expr, ty));
+		}
+		(TyKind::Union(tys), _) => {
+			let expected = tys
+				.iter()
+				.map(|ty| value_mismatch(defs, expr, ty).map(|(_, expected)| expected))
+				.collect::<Option<Vec<_>>>()?;
+			expected.join(" or ")
+		}
+		(TyKind::Path(_, Res::PrimTy(prim)), _) => {
+			let json_ty = prim.json_ty()?;
+
+			match (json_ty, &expr.kind) {
+				(JsonTy::Boolean, ExprKind::Literal(LiteralKind::Bool, _))
+				| (JsonTy::Number, ExprKind::Literal(LiteralKind::Number, _))
+				| (JsonTy::String, ExprKind::Literal(LiteralKind::Str, _)) => return None,
+				(JsonTy::Integer, ExprKind::Literal(LiteralKind::Number, number))
+					if !number.as_str().contains('.') =>
+				{
+					return None;
+				}
+				(JsonTy::Boolean, _) => "a boolean".to_owned(),
+				(JsonTy::Integer, _) => "an integer".to_owned(),
+				(JsonTy::Number, _) => "a number".to_owned(),
+				(JsonTy::String, _) => "a string".to_owned(),
+			}
+		}
+		(TyKind::Path(_, Res::Def(DefKind::Enum, def_id)), _) => {
+			let ItemKind::Enum(enum_) = &defs[*def_id].item.kind else {
+				mismatched_def_kind()
+			};
+			if enum_
+				.variants
+				.iter()
+				.any(|variant| variant.expr.kind == expr.kind)
+			{
+				return None;
+			}
+
+			let values = enum_
+				.variants
+				.iter()
+				.filter_map(|variant| match variant.expr.kind {
+					ExprKind::Literal(LiteralKind::Str, value) => Some(format!("\"{value}\"")),
+					ExprKind::Literal(_, value) => Some(value.to_string()),
+					_ => None,
+				})
+				.collect::<Vec<_>>();
+			format!("one of: {}", values.join(", "))
+		}
+		(
+			TyKind::Path(_, Res::Def(DefKind::Model | DefKind::Union, _)) | TyKind::InlineModel(_),
+			_,
+		) => "no value, models cannot have defaults or examples".to_owned(),
+		(TyKind::Map(..), _) => "no value, maps cannot have defaults or examples".to_owned(),
+		(
+			TyKind::Path(
+				_,
+				Res::Def(
+					DefKind::Auth
+					| DefKind::Headers
+					| DefKind::Query
+					| DefKind::Params
+					| DefKind::Response,
+					_,
+				)
+				| Res::TyParam
+				| Res::Err,
+			),
+			_,
+		) => return None,
+		(TyKind::Array(_), _) => "an array".to_owned(),
+		(TyKind::Tuple(tys), _) => format!("an array of {} values", tys.len()),
+	};
+
+	Some((expr.span, expected))
+}
+
+/// Whether the type is the `null` of `T | null`.
+fn is_null(ty: &ast::Ty) -> bool {
+	matches!(&ty.kind, ast::TyKind::Path(path) if matches!(&path.segments[..], [segment] if segment.ident.symbol == kw::Null))
+}
@@ -0,0 +1,186 @@
+use crate::{
+	HirLowerer,
+	errors::lower::{RecursiveInstantiation, WrongTypeArgCount},
+	resolve::{DefData, ModuleId},
+	types::{DefKind, Item, ItemKind, Res, Ty, TyKind},
+};
+use dapic_ast::types::{self as ast};
+use dapic_session::{Ident, Span, Symbol};
+use std::mem;
+
+/// Nested instantiations after which a generic model is considered to
+/// instantiate itself endlessly, e.g. `model Nested<T> { inner Nested<[T]> }`.
+const INSTANCE_DEPTH_LIMIT: usize = 32;
+
+impl<'tcx> HirLowerer<'_, 'tcx> {
+	/// Lowers the instance of a generic model for the given arguments, once
+	/// per distinct arguments.
+	pub(super) fn instantiate(&mut self, res: Res, args: &'tcx [Ty<'tcx>], span: Span) -> Res {
+		let template = match res {
+			Res::Def(DefKind::Model, def_id) => self.resolver.templates.get(&def_id).cloned(),
+			_ => None,
+		};
+		let expected = template
+			.as_ref()
+			.map_or(0, |template| match &template.kind {
+				ast::ItemKind::Model(model) => model.generics.len(),
+				_ => unreachable!("only models are generic"),
+			});
+
+		if args.len() != expected {
+			if res != Res::Err {
+				self.lcx.session.diagnostics.emit(WrongTypeArgCount {
+					span,
+					expected,
+					found: args.len(),
+				});
+			}
+			return Res::Err;
+		}
+
+		// Generic models are only instantiated with concrete types
+		let (Some(template), Res::Def(_, def_id)) = (template, res) else {
+			return res;
+		};
+		if args.iter().any(has_ty_params) {
+			return res;
+		}
+
+		let key = (
+			def_id,
+			args.iter().map(ty_key).collect::<Vec<_>>().join(", "),
+		);
+		if let Some(&instance) = self.instances.get(&key) {
+			return Res::Def(DefKind::Model, instance);
+		}
+
+		if self.instance_depth == INSTANCE_DEPTH_LIMIT {
+			self.lcx.session.diagnostics.emit(RecursiveInstantiation {
+				span,
+				name: template.ident,
+			});
+			return Res::Err;
+		}
+
+		let module = self.resolver.defs[def_id].module;
+		let ident = Ident::new(
+			self.instance_name(template.ident, args, module),
+			template.ident.span,
+		);
+		let instance = self.resolver.defs.push(DefData {
+			kind: DefKind::Model,
+			ident,
+			module,
+		});
+		self.def_items.push(None);
+		self.instances.insert(key, instance);
+
+		let ast::ItemKind::Model(model) = &template.kind else {
+			unreachable!("only models are generic")
+		};
+		let parent = mem::replace(&mut self.current_module, module);
+		self.instance_depth += 1;
+
+		let attrs = self.lower_attrs(&template.attrs);
+		let model = self.lower_model(model, Some(args));
+		let item = self.lcx.arena.alloc(Item {
+			attrs,
+			kind: ItemKind::Model(model),
+			ident,
+			id: self.lcx.next_id(),
+			span: template.span,
+		});
+		self.def_items[instance] = Some(item);
+
+		self.instance_depth -= 1;
+		self.current_module = parent;
+
+		Res::Def(DefKind::Model, instance)
+	}
+
+	/// Name of an instance after its arguments, e.g. `PageOfPet`, unique in
+	/// the scope of the generic model.
+	fn instance_name(&self, generic: Ident, args: &[Ty<'_>], module: ModuleId) -> Symbol {
+		let args = args
+			.iter()
+			.map(|arg| self.ty_name(arg))
+			.collect::<Vec<_>>()
+			.join("And");
+		let base = format!("{generic}Of{args}");
+
+		let taken = |name: &str| {
+			self.resolver
+				.defs
+				.iter()
+				.any(|def| def.module == module && def.ident.symbol.as_str() == name)
+		};
+		let mut name = base.clone();
+		let mut idx = 2;
+		while taken(&name) {
+			name = format!("{base}{idx}");
+			idx += 1;
+		}
+
+		Symbol::intern(&name)
+	}
+
+	fn ty_name(&self, ty: &Ty<'_>) -> String {
+		match ty.kind {
+			TyKind::Path(_, Res::Def(_, def_id)) => self.resolver.defs[def_id].ident.to_string(),
+			TyKind::Path(_, Res::PrimTy(prim)) => {
+				let name = prim.to_string();
+				let mut chars = name.chars();
+				chars.next().map_or_else(String::new, |first| {
+					first.to_uppercase().chain(chars).collect()
+				})
+			}
+			TyKind::Path(path, Res::TyParam | Res::Err) => path
+				.segments
+				.last()
+				.map_or_else(String::new, |segment| segment.ident.to_string()),
+			TyKind::Array(ty) => format!("{}List", self.ty_name(ty)),
+			TyKind::Nullable(ty) => format!("Nullable{}", self.ty_name(ty)),
+			TyKind::Map(_, value) => format!("{}Map", self.ty_name(value)),
+			TyKind::Tuple(tys) => format!(
+				"{}Tuple",
+				tys.iter().map(|ty| self.ty_name(ty)).collect::<String>()
+			),
+			TyKind::Union(tys) => tys
+				.iter()
+				.map(|ty| self.ty_name(ty))
+				.collect::<Vec<_>>()
+				.join("Or"),
+			TyKind::InlineModel(_) => "Object".to_owned(),
+		}
+	}
+}
+
+/// Whether the type refers to a type parameter, such arguments leave the
+/// generic model uninstantiated.
+fn has_ty_params(ty: &Ty<'_>) -> bool {
+	match ty.kind {
+		TyKind::Path(_, res) => res == Res::TyParam,
+		TyKind::Array(ty) | TyKind::Nullable(ty) => has_ty_params(ty),
+		TyKind::Map(key, value) => has_ty_params(key) || has_ty_params(value),
+		TyKind::Tuple(tys) | TyKind::Union(tys) => tys.iter().any(has_ty_params),
+		TyKind::InlineModel(fields) => fields.iter().any(|field| has_ty_params(field.ty)),
+	}
+}
+
+/// Identifies the arguments of an instance, inline models are distinct in
+/// every instantiation.
+fn ty_key(ty: &Ty<'_>) -> String {
+	let join = |tys: &[Ty<'_>], sep| tys.iter().map(ty_key).collect::<Vec<_>>().join(sep);
+
+	match ty.kind {
+		TyKind::Path(_, Res::Def(_, def_id)) => format!("{def_id:?}"),
+		TyKind::Path(_, Res::PrimTy(prim)) => prim.to_string(),
+		TyKind::Path(_, Res::TyParam | Res::Err) => "_".to_owned(),
+		TyKind::Array(ty) => format!("[{}]", ty_key(ty)),
+		TyKind::Nullable(ty) => format!("({})?", ty_key(ty)),
+		TyKind::Map(key, value) => format!("{{[{}]: {}}}", ty_key(key), ty_key(value)),
+		TyKind::Tuple(tys) => format!("({})", join(tys, ", ")),
+		TyKind::Union(tys) => format!("({})", join(tys, " | ")),
+		TyKind::InlineModel(_) => format!("{{{:?}}}", ty.id),
+	}
+}
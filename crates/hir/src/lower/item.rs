@@ -0,0 +1,657 @@
+use crate::{
+	HirLowerer,
+	errors::lower::{
+		ConflictingField, DuplicateVariant, DuplicateVariantValue, InvalidBase,
+		InvalidDiscriminator, InvalidEnumType, InvalidUnionVariant, MismatchedValue,
+		MissingDiscriminator, RecursiveBlock, RecursiveModel, RecursiveTypeAlias,
+	},
+	lower::mismatched_def_kind,
+	types::{
+		Attribute, Body, DefId, DefKind, Definition, Enum, ExprKind, FieldDef, Headers, Item,
+		ItemKind, Metadata, Model, Params, PathItem, PropertyDef, Query, Res, Response, Scope,
+		StatusCode, Ty, TyKind, TypeAlias, Union, Verb,
+	},
+};
+use dapic_ast::types::{self as ast, P};
+use dapic_lexer::rich::LiteralKind;
+use dapic_session::{Ident, IndexVec, JsonTy, PrimTy};
+use std::{
+	collections::{HashMap, HashSet},
+	mem,
+};
+use tracing::instrument;
+
+impl<'tcx> HirLowerer<'_, 'tcx> {
+	pub(super) fn lower_items(&mut self, items: &[P<ast::Item>]) -> &'tcx [Item<'tcx>] {
+		let arena = self.lcx.arena;
+		let lowered: &'tcx [Item<'tcx>] = arena.alloc_slice_fill_iter(items.iter().map(|item| {
+			// Shared blocks are lowered when first used, which may be before
+			// their definition
+			self.resolver
+				.def_of(item.id)
+				.and_then(|def_id| self.def_items[def_id])
+				.map_or_else(|| self.lower_item(item), Clone::clone)
+		}));
+
+		for (item, lowered) in items.iter().zip(lowered) {
+			if let Some(def_id) = self.resolver.def_of(item.id) {
+				self.def_items[def_id] = Some(lowered);
+			}
+		}
+
+		lowered
+	}
+
+	#[instrument(level = "DEBUG", skip_all, fields(ident = %item.ident))]
+	fn lower_item(&mut self, item: &ast::Item) -> Item<'tcx> {
+		let attrs = self.lower_attrs(&item.attrs);
+
+		let kind = match &item.kind {
+			ast::ItemKind::Meta(ast::Metadata { fields }) => {
+				let fields = self.lower_property_defs(fields);
+				self.check_meta(fields);
+
+				ItemKind::Meta(Metadata { fields })
+			}
+			ast::ItemKind::Auth(auth) => ItemKind::Auth(self.lower_auth(item.ident, auth)),
+			ast::ItemKind::Scope(scope) => {
+				let module = self.resolver.module_of(item.id);
+				let parent = mem::replace(&mut self.current_module, module);
+				let scope = self.lower_scope(scope);
+				self.current_module = parent;
+
+				ItemKind::Scope(scope)
+			}
+			ast::ItemKind::Path(ast::PathItem { kind, items }) => {
+				let kind = self.lower_path_kind(kind);
+				ItemKind::Path(PathItem {
+					variables: self.lower_path_variables(&kind),
+					kind,
+					items: self.lower_items(items),
+				})
+			}
+			ast::ItemKind::Model(model) => ItemKind::Model(self.lower_model(model, None)),
+			ast::ItemKind::Enum(enum_) => ItemKind::Enum(self.lower_enum(enum_)),
+			ast::ItemKind::TypeAlias(ast::TypeAlias { ty }) => {
+				ItemKind::TypeAlias(self.lower_type_alias(attrs, ty))
+			}
+			ast::ItemKind::Union(ast::Union {
+				discriminator,
+				variants,
+			}) => ItemKind::Union(Union {
+				discriminator: *discriminator,
+				variants: self.lower_field_defs(variants),
+			}),
+			ast::ItemKind::Query(ast::Query { fields }) => ItemKind::Query(Query {
+				fields: self.lower_field_defs(fields),
+				def: None,
+			}),
+			ast::ItemKind::Headers(ast::Headers { headers }) => ItemKind::Headers(Headers {
+				headers: self.lower_field_defs(headers),
+				def: None,
+			}),
+			ast::ItemKind::Response(ast::Response { items }) => {
+				let items = self.lower_items(items);
+				self.check_bodies(items);
+
+				ItemKind::Response(Response { items })
+			}
+			ast::ItemKind::Use(use_) => self.lower_use(item.ident, use_),
+			ast::ItemKind::Verb(ast::Verb { method, items }) => {
+				let items = self.lower_items(items);
+				self.check_responses(items);
+				self.check_bodies(items);
+
+				ItemKind::Verb(Verb {
+					method: *method,
+					items,
+				})
+			}
+			ast::ItemKind::StatusCode(ast::StatusCode { code, items }) => {
+				let code = self.lower_expr(code);
+				let items = self.lower_items(items);
+				self.check_bodies(items);
+
+				ItemKind::StatusCode(StatusCode {
+					code,
+					status: self.lower_status(code),
+					items,
+					def: None,
+				})
+			}
+			ast::ItemKind::Body(ast::Body { ty }) => {
+				let ty = self.lower_ty(ty);
+
+				ItemKind::Body(Body {
+					ty,
+					media_types: self.lower_media_types(attrs),
+					constraints: self.lower_constraints(attrs, ty),
+				})
+			}
+			ast::ItemKind::Params(ast::Params { properties }) => ItemKind::Params(Params {
+				properties: self.lower_params(properties),
+				def: None,
+			}),
+		};
+
+		Item {
+			attrs,
+			kind,
+			ident: item.ident,
+			id: self.lcx.next_id(),
+			span: item.span,
+		}
+	}
+
+	fn lower_enum(&mut self, enum_: &ast::Enum) -> Enum<'tcx> {
+		let ty = enum_.ty.as_ref().map_or(PrimTy::String, |ty| {
+			let ty = self.lower_ty(ty);
+			match ty.kind {
+				TyKind::Path(_, Res::PrimTy(prim))
+					if matches!(prim.json_ty(), Some(JsonTy::String | JsonTy::Integer)) =>
+				{
+					prim
+				}
+				TyKind::Path(_, Res::Err) => PrimTy::String,
+				_ => {
+					self.lcx
+						.session
+						.diagnostics
+						.emit(InvalidEnumType { span: ty.span });
+					PrimTy::String
+				}
+			}
+		});
+
+		let variants = self.lower_property_defs(&enum_.variants);
+		self.check_variants(ty, variants);
+
+		Enum { ty, variants }
+	}
+
+	/// Variant values must match the type of the enum, names and values must
+	/// be unique.
+	fn check_variants(&self, ty: PrimTy, variants: &[PropertyDef<'_>]) {
+		let diagnostics = &self.lcx.session.diagnostics;
+		let mut names = HashMap::new();
+		let mut values = HashMap::new();
+
+		for variant in variants {
+			if let Some(previous) = names.insert(variant.ident.symbol, variant.ident.span) {
+				diagnostics.emit(DuplicateVariant {
+					name: variant.ident,
+					previous,
+				});
+			}
+
+			let value = match (ty.json_ty(), &variant.expr.kind) {
+				(Some(JsonTy::String), ExprKind::Literal(LiteralKind::Str, value)) => {
+					value.to_string()
+				}
+				// `1_000` and `1000` are the same integer
+				(Some(JsonTy::Integer), ExprKind::Literal(LiteralKind::Number, value))
+					if !value.as_str().contains('.') =>
+				{
+					value.as_str().replace('_', "")
+				}
+				(Some(JsonTy::Integer), _) => {
+					diagnostics.emit(MismatchedValue {
+						span: variant.expr.span,
+						expected: "an integer".to_owned(),
+					});
+					continue;
+				}
+				_ => {
+					diagnostics.emit(MismatchedValue {
+						span: variant.expr.span,
+						expected: "a string".to_owned(),
+					});
+					continue;
+				}
+			};
+
+			if let Some(previous) = values.insert(value, variant.expr.span) {
+				diagnostics.emit(DuplicateVariantValue {
+					span: variant.expr.span,
+					previous,
+				});
+			}
+		}
+	}
+
+	/// Lowers `headers Name;`, `query Name;`, `params Name;` and `code 404 Name;`
+	/// to the items of the shared block.
+	fn lower_use(&mut self, name: Ident, use_: &ast::Use) -> ItemKind<'tcx> {
+		let kind = match use_ {
+			ast::Use::Headers => DefKind::Headers,
+			ast::Use::Query => DefKind::Query,
+			ast::Use::Params => DefKind::Params,
+			ast::Use::Response { .. } => DefKind::Response,
+		};
+
+		let shared = match self.resolver.resolve_block(self.current_module, name, kind) {
+			Ok(def_id) => self
+				.lower_block(def_id, name)
+				.map(|item| (def_id, &item.kind)),
+			Err(diag) => {
+				self.lcx.session.diagnostics.emit_diagnostic(&diag);
+				None
+			}
+		};
+		let def = shared.map(|(def_id, _)| def_id);
+		let block = shared.map(|(_, kind)| kind);
+
+		match use_ {
+			ast::Use::Headers => ItemKind::Headers(Headers {
+				headers: match block {
+					Some(ItemKind::Headers(headers)) => headers.headers,
+					_ => &[],
+				},
+				def,
+			}),
+			ast::Use::Query => ItemKind::Query(Query {
+				fields: match block {
+					Some(ItemKind::Query(query)) => query.fields,
+					_ => &[],
+				},
+				def,
+			}),
+			ast::Use::Params => ItemKind::Params(Params {
+				properties: match block {
+					Some(ItemKind::Params(params)) => params.properties,
+					_ => &[],
+				},
+				def,
+			}),
+			ast::Use::Response { code } => {
+				let code = self.lower_expr(code);
+
+				ItemKind::StatusCode(StatusCode {
+					code,
+					status: self.lower_status(code),
+					items: match block {
+						Some(ItemKind::Response(response)) => response.items,
+						_ => &[],
+					},
+					def,
+				})
+			}
+		}
+	}
+
+	/// Lowered item of a shared block, which is lowered now if it is used
+	/// before its definition.
+	fn lower_block(&mut self, def_id: DefId, name: Ident) -> Option<&'tcx Item<'tcx>> {
+		if let Some(item) = self.def_items[def_id] {
+			return Some(item);
+		}
+
+		// Blocks are taken out while they are lowered
+		let Some(block) = self.resolver.blocks.remove(&def_id) else {
+			self.lcx.session.diagnostics.emit(RecursiveBlock { name });
+			return None;
+		};
+
+		let module = self.resolver.defs[def_id].module;
+		let parent = mem::replace(&mut self.current_module, module);
+		let item = self.lower_item(&block);
+		self.current_module = parent;
+
+		let item = self.lcx.arena.alloc(item);
+		self.def_items[def_id] = Some(item);
+		Some(item)
+	}
+
+	/// Lowers a model, or an instance of a generic model when `args` are given.
+	pub(super) fn lower_model(
+		&mut self,
+		model: &ast::Model,
+		args: Option<&[Ty<'tcx>]>,
+	) -> Model<'tcx> {
+		let arena = self.lcx.arena;
+
+		let ty_params = model
+			.generics
+			.iter()
+			.enumerate()
+			.map(|(idx, param)| (param.symbol, args.map(|args| args[idx].kind.clone())))
+			.collect();
+		let outer = mem::replace(&mut self.ty_params, ty_params);
+
+		let bases = arena.alloc_slice_fill_iter(model.bases.iter().map(|path| Ty {
+			kind: self.lower_ty_path(path),
+			id: self.lcx.next_id(),
+			span: path.span,
+		}));
+		let fields = self.lower_field_defs(&model.fields);
+
+		self.ty_params = outer;
+
+		Model {
+			generics: match args {
+				Some(_) => &[],
+				None => arena.alloc_slice_copy(&model.generics),
+			},
+			bases,
+			fields,
+		}
+	}
+
+	fn lower_scope(&mut self, scope: &ast::ScopeKind) -> Scope<'tcx> {
+		match scope {
+			ast::ScopeKind::Loaded {
+				items,
+				inline,
+				span,
+			} => Scope {
+				items: self.lower_items(items),
+				inline: *inline,
+				span: *span,
+			},
+			ast::ScopeKind::Unloaded => {
+				unreachable!("every scope is loaded during the expansion phase")
+			}
+		}
+	}
+
+	/// Attributes of an alias apply to its type, like the ones of a field.
+	fn lower_type_alias(&mut self, attrs: &[Attribute<'_>], ty: &ast::Ty) -> TypeAlias<'tcx> {
+		let ty = self.lower_ty(ty);
+		if let TyKind::Path(_, Res::PrimTy(prim)) = ty.non_null().kind {
+			self.check_format(attrs, prim);
+		}
+
+		TypeAlias {
+			ty,
+			constraints: self.lower_constraints(attrs, ty),
+		}
+	}
+
+	/// Reports aliases referring to themselves, returns whether there was one.
+	pub(super) fn check_type_aliases(&self, defs: &IndexVec<DefId, Definition<'tcx>>) -> bool {
+		let mut recursive = false;
+
+		for (def_id, def) in defs.iter_enumerated() {
+			let ItemKind::TypeAlias(alias) = &def.item.kind else {
+				continue;
+			};
+
+			if reaches_alias(defs, alias.ty, def_id, &mut HashSet::new()) {
+				self.lcx.session.diagnostics.emit(RecursiveTypeAlias {
+					name: def.item.ident,
+				});
+				recursive = true;
+			}
+		}
+
+		recursive
+	}
+
+	/// Reports invalid and recursive bases, and fields inherited from several
+	/// bases or redefined by the model. Returns whether a model inherits from
+	/// itself.
+	pub(super) fn check_models(&self, defs: &IndexVec<DefId, Definition<'tcx>>) -> bool {
+		let mut recursive = false;
+
+		for (def_id, def) in defs.iter_enumerated() {
+			let ItemKind::Model(model) = &def.item.kind else {
+				continue;
+			};
+
+			for base in model.bases {
+				if !matches!(
+					base.kind,
+					TyKind::Path(_, Res::Def(DefKind::Model, _) | Res::Err)
+				) {
+					self.lcx
+						.session
+						.diagnostics
+						.emit(InvalidBase { span: base.span });
+				}
+			}
+
+			if reaches_model(defs, model, def_id, &mut HashSet::new()) {
+				self.lcx.session.diagnostics.emit(RecursiveModel {
+					name: def.item.ident,
+				});
+				recursive = true;
+			}
+		}
+
+		if recursive {
+			return true;
+		}
+
+		for def in defs.iter() {
+			let ItemKind::Model(model) = &def.item.kind else {
+				continue;
+			};
+
+			let mut seen: Vec<&FieldDef<'_>> = Vec::new();
+			let inherited = model.bases.iter().filter_map(|base| match base.kind {
+				TyKind::Path(_, Res::Def(DefKind::Model, def_id)) => {
+					match &defs[def_id].item.kind {
+						ItemKind::Model(base) => Some(base.all_fields(defs)),
+						_ => mismatched_def_kind(),
+					}
+				}
+				_ => None,
+			});
+
+			for field in inherited.flatten().chain(model.fields) {
+				let previous = seen
+					.iter()
+					.find(|previous| previous.ident.symbol == field.ident.symbol);
+
+				match previous {
+					// The same base reached through several paths
+					Some(previous) if previous.id == field.id => {}
+					Some(previous) => self.lcx.session.diagnostics.emit(ConflictingField {
+						name: field.ident,
+						previous: previous.ident.span,
+					}),
+					None => seen.push(field),
+				}
+			}
+		}
+
+		false
+	}
+
+	/// Variants of a tagged union must be models with a required string
+	/// discriminator.
+	pub(super) fn check_union(&self, defs: &IndexVec<DefId, Definition<'tcx>>, union: &Union<'_>) {
+		let discriminator = union.discriminator;
+
+		for variant in union.variants {
+			if variant.optional || variant.default.is_some() {
+				self.lcx.session.diagnostics.emit(InvalidUnionVariant {
+					span: variant.span,
+					name: variant.ident,
+					reason: "variants cannot be optional or have a default",
+				});
+			}
+
+			let def_id = match variant.ty.kind {
+				TyKind::Path(_, Res::Def(DefKind::Model, def_id)) => def_id,
+				TyKind::Path(_, Res::Err) => continue,
+				_ => {
+					self.lcx.session.diagnostics.emit(InvalidUnionVariant {
+						span: variant.ty.span,
+						name: variant.ident,
+						reason: "variants must be models",
+					});
+					continue;
+				}
+			};
+
+			let model = defs[def_id].item;
+			let ItemKind::Model(variant_model) = &model.kind else {
+				mismatched_def_kind()
+			};
+
+			let Some(field) = variant_model
+				.all_fields(defs)
+				.into_iter()
+				.find(|field| field.ident.symbol == discriminator.symbol)
+			else {
+				self.lcx.session.diagnostics.emit(MissingDiscriminator {
+					span: variant.ty.span,
+					model: model.ident,
+					discriminator,
+				});
+				continue;
+			};
+
+			let is_string = match field.ty.kind {
+				TyKind::Path(_, Res::PrimTy(prim)) => prim
+					.json_ty()
+					.is_none_or(|json_ty| json_ty == JsonTy::String),
+				TyKind::Path(_, Res::Def(DefKind::Enum, def_id)) => match &defs[def_id].item.kind {
+					ItemKind::Enum(enum_) => enum_.ty.json_ty() == Some(JsonTy::String),
+					_ => mismatched_def_kind(),
+				},
+				TyKind::Path(_, Res::Err) => true,
+				_ => false,
+			};
+			if field.optional || !is_string {
+				self.lcx.session.diagnostics.emit(InvalidDiscriminator {
+					span: field.span,
+					discriminator,
+				});
+			}
+		}
+	}
+}
+
+/// Whether `ty` refers to the alias `target` without going through a named
+/// model, enum or union.
+fn reaches_alias(
+	defs: &IndexVec<DefId, Definition<'_>>,
+	ty: &Ty<'_>,
+	target: DefId,
+	visited: &mut HashSet<DefId>,
+) -> bool {
+	match ty.kind {
+		TyKind::Path(_, Res::Def(DefKind::TypeAlias, def_id)) => {
+			let ItemKind::TypeAlias(alias) = &defs[def_id].item.kind else {
+				mismatched_def_kind()
+			};
+			def_id == target
+				|| (visited.insert(def_id) && reaches_alias(defs, alias.ty, target, visited))
+		}
+		TyKind::Path(..) => false,
+		TyKind::Array(ty) | TyKind::Nullable(ty) => reaches_alias(defs, ty, target, visited),
+		TyKind::Map(key, value) => {
+			reaches_alias(defs, key, target, visited) || reaches_alias(defs, value, target, visited)
+		}
+		TyKind::Tuple(tys) | TyKind::Union(tys) => tys
+			.iter()
+			.any(|ty| reaches_alias(defs, ty, target, visited)),
+		TyKind::InlineModel(fields) => fields
+			.iter()
+			.any(|field| reaches_alias(defs, field.ty, target, visited)),
+	}
+}
+
+/// Whether `model` inherits from the model `target`.
+fn reaches_model(
+	defs: &IndexVec<DefId, Definition<'_>>,
+	model: &Model<'_>,
+	target: DefId,
+	visited: &mut HashSet<DefId>,
+) -> bool {
+	model.bases.iter().any(|base| match base.kind {
+		TyKind::Path(_, Res::Def(DefKind::Model, def_id)) => {
+			let ItemKind::Model(base) = &defs[def_id].item.kind else {
+				mismatched_def_kind()
+			};
+			def_id == target
+				|| (visited.insert(def_id) && reaches_model(defs, base, target, visited))
+		}
+		_ => false,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		tests::with_hir,
+		types::{Item, ItemKind},
+	};
+
+	/// Names of the kinds of `items` and of their nested items, depth first.
+	fn item_kinds(items: &[Item<'_>], kinds: &mut Vec<&'static str>) {
+		for item in items {
+			let (kind, children): (_, &[Item<'_>]) = match &item.kind {
+				ItemKind::Meta(_) => ("meta", &[]),
+				ItemKind::Auth(_) => ("auth", &[]),
+				ItemKind::Scope(scope) => ("scope", scope.items),
+				ItemKind::Path(path) => ("path", path.items),
+				ItemKind::Model(_) => ("model", &[]),
+				ItemKind::Enum(_) => ("enum", &[]),
+				ItemKind::Union(_) => ("union", &[]),
+				ItemKind::TypeAlias(_) => ("type", &[]),
+				ItemKind::Query(_) => ("query", &[]),
+				ItemKind::Headers(_) => ("headers", &[]),
+				ItemKind::Verb(verb) => ("verb", verb.items),
+				ItemKind::StatusCode(code) => ("code", code.items),
+				ItemKind::Body(_) => ("body", &[]),
+				ItemKind::Params(_) => ("params", &[]),
+				ItemKind::Response(response) => ("response", response.items),
+			};
+			kinds.push(kind);
+			item_kinds(children, kinds);
+		}
+	}
+
+	#[test]
+	fn lower_item_kinds() {
+		let (kinds, errors) = with_hir(
+			r#"
+			meta { name "Petstore" version "1.0.0" }
+
+			auth ApiKey {
+				type "apiKey"
+				in "header"
+				name "api_key"
+			}
+
+			model Dog { kind string }
+			enum Status { Sold "sold" }
+			union Animal on kind { dog Dog }
+			type Names = [string]
+
+			response NotFound { body string }
+
+			scope pet {
+				path {petId} {
+					params { petId int }
+
+					verb PUT {
+						auth ApiKey;
+						query { dryRun? bool }
+						headers { X-Request-Id string }
+						body Dog
+						code 200 { body Animal }
+					}
+				}
+			}
+			"#,
+			|hir| {
+				let mut kinds = Vec::new();
+				item_kinds(hir.items(), &mut kinds);
+				kinds
+			},
+		);
+
+		assert!(!errors);
+		assert_eq!(
+			kinds,
+			[
+				"meta", "auth", "model", "enum", "union", "type", "response", "body", "scope",
+				"path", "params", "verb", "auth", "query", "headers", "body", "code", "body",
+			]
+		);
+	}
+}
@@ -0,0 +1,86 @@
+use crate::{
+	HirLowerer,
+	errors::lower::{DuplicateMediaType, InvalidMediaType, UnknownMediaType},
+	types::{Attribute, ExprKind, Item, ItemKind, MediaType},
+};
+use dapic_lexer::rich::LiteralKind;
+use dapic_session::symbols::kw;
+use std::{collections::HashMap, slice};
+
+impl<'tcx> HirLowerer<'_, 'tcx> {
+	/// Collects the media types of the `@type` attributes of a `body`.
+	pub(super) fn lower_media_types(&self, attrs: &[Attribute<'_>]) -> &'tcx [MediaType] {
+		let diagnostics = &self.lcx.session.diagnostics;
+		let mut media_types = Vec::new();
+
+		for expr in attrs.iter().filter_map(|attr| attr.meta_value(kw::Type)) {
+			let values = match expr.kind {
+				ExprKind::Literal(LiteralKind::Str, _) => slice::from_ref(expr),
+				ExprKind::Array(exprs) => exprs,
+				_ => &[],
+			};
+			if values.is_empty() {
+				diagnostics.emit(InvalidMediaType { span: expr.span });
+			}
+
+			for value in values {
+				let Some(symbol) = value.str_literal() else {
+					diagnostics.emit(InvalidMediaType { span: value.span });
+					continue;
+				};
+
+				match MediaType::parse(symbol) {
+					Some(media_type) => media_types.push((media_type, value.span)),
+					None => diagnostics.emit(UnknownMediaType {
+						span: value.span,
+						media_type: symbol,
+						expected: MediaType::SHORTHANDS
+							.iter()
+							.filter_map(|media_type| media_type.shorthand())
+							.collect::<Vec<_>>()
+							.join(", "),
+					}),
+				}
+			}
+		}
+
+		let mut seen = HashMap::new();
+		for (media_type, span) in &media_types {
+			if let Some(previous) = seen.insert(*media_type, *span) {
+				diagnostics.emit(DuplicateMediaType {
+					span: *span,
+					previous,
+					media_type: *media_type,
+				});
+			}
+		}
+
+		if media_types.is_empty() {
+			return &[MediaType::Json];
+		}
+		self.lcx
+			.arena
+			.alloc_slice_fill_iter(media_types.into_iter().map(|(media_type, _)| media_type))
+	}
+
+	/// Reports `body` items of a verb or a response sharing a media type.
+	pub(super) fn check_bodies(&self, items: &[Item<'_>]) {
+		let mut seen = HashMap::new();
+
+		for item in items {
+			let ItemKind::Body(body) = &item.kind else {
+				continue;
+			};
+
+			for media_type in body.media_types {
+				if let Some(previous) = seen.insert(*media_type, item.span) {
+					self.lcx.session.diagnostics.emit(DuplicateMediaType {
+						span: item.span,
+						previous,
+						media_type: *media_type,
+					});
+				}
+			}
+		}
+	}
+}
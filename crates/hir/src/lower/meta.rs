@@ -0,0 +1,75 @@
+use crate::{
+	HirLowerer,
+	errors::lower::{DuplicateProperty, InvalidMetaValue, MissingMetaProperty, UnknownMetaKey},
+	types::{Item, ItemKind, MetaKey, PropertyDef},
+};
+use std::collections::HashMap;
+
+impl HirLowerer<'_, '_> {
+	/// Reports unknown, duplicated and mistyped `meta` properties.
+	pub(super) fn check_meta(&self, fields: &[PropertyDef<'_>]) {
+		let diagnostics = &self.lcx.session.diagnostics;
+		let mut seen = HashMap::new();
+
+		for field in fields {
+			let Some(key) = MetaKey::from_symbol(field.ident.symbol) else {
+				diagnostics.emit(UnknownMetaKey {
+					key: field.ident,
+					expected: MetaKey::ALL
+						.iter()
+						.map(ToString::to_string)
+						.collect::<Vec<_>>()
+						.join(", "),
+				});
+				continue;
+			};
+
+			if let Some(previous) = seen.insert(field.ident.symbol, field.ident.span) {
+				diagnostics.emit(DuplicateProperty {
+					key: field.ident,
+					previous,
+				});
+			}
+
+			let valid = match key {
+				MetaKey::Urls => field.expr.str_array().is_some(),
+				_ => field.expr.str_literal().is_some(),
+			};
+			if !valid {
+				diagnostics.emit(InvalidMetaValue {
+					span: field.expr.span,
+					key,
+					expected: key.expected(),
+				});
+			}
+		}
+	}
+
+	/// Reports a root `meta` block without a `name` or a `version`, which
+	/// describe the api in every generated output.
+	pub(super) fn check_required_meta(&self, items: &[Item<'_>]) {
+		// The parser always starts the root with a `meta` block
+		let Some(first) = items
+			.iter()
+			.find(|item| matches!(item.kind, ItemKind::Meta(_)))
+		else {
+			return;
+		};
+		let fields = items.iter().flat_map(|item| match &item.kind {
+			ItemKind::Meta(metadata) => metadata.fields,
+			_ => &[],
+		});
+
+		for key in [MetaKey::Name, MetaKey::Version] {
+			let defined = fields
+				.clone()
+				.any(|field| field.ident.symbol == key.symbol());
+			if !defined {
+				self.lcx.session.diagnostics.emit(MissingMetaProperty {
+					span: first.span,
+					key,
+				});
+			}
+		}
+	}
+}
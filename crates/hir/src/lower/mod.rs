@@ -0,0 +1,89 @@
+//! Lowering from the AST, split by concern like [`types`](crate::types).
+
+use crate::{
+	HirLowerer,
+	types::{Definition, ItemKind, Root},
+};
+use dapic_ast::types as ast;
+use dapic_session::IndexVec;
+use std::mem;
+use tracing::instrument;
+
+mod attr;
+mod auth;
+mod constraint;
+mod endpoint;
+mod expr;
+mod generic;
+mod item;
+mod media;
+mod meta;
+mod status;
+
+impl<'tcx> HirLowerer<'_, 'tcx> {
+	/// # Panics
+	/// If the `AST` was not expanded, as scopes and definitions are looked up by [`NodeId`](ast::NodeId)
+	#[instrument(level = "DEBUG", skip_all)]
+	pub fn lower_root(&mut self, crate_: &ast::Root) -> Root<'tcx> {
+		let ast::Root {
+			attrs,
+			items,
+			id: _,
+			span,
+		} = crate_;
+
+		let attrs = self.lower_attrs(attrs);
+		let items = self.lower_items(items);
+		self.check_required_meta(items);
+		self.check_path_params(items, &mut Vec::new(), &mut Vec::new());
+
+		let arena = self.lcx.arena;
+		let defs: IndexVec<_, _> = mem::take(&mut self.def_items)
+			.into_iter_enumerated()
+			.map(|(def_id, item)| {
+				let item = item.expect("every definition has been lowered");
+				let def = &self.resolver.defs[def_id];
+				let module = &self.resolver.modules[def.module];
+
+				Definition {
+					kind: def.kind,
+					mod_path: arena.alloc_slice_copy(&module.mod_path),
+					item,
+				}
+			})
+			.collect();
+
+		// Values are checked through aliases, which must not loop
+		let values = mem::take(&mut self.values);
+		if !self.check_type_aliases(&defs) {
+			for (expr, ty) in values {
+				self.check_value(&defs, expr, ty);
+			}
+		}
+		// Fields are looked up through bases, which must not loop either
+		if !self.check_models(&defs) {
+			for def in defs.iter() {
+				if let ItemKind::Union(union) = &def.item.kind {
+					self.check_union(&defs, union);
+				}
+			}
+		}
+
+		let root = Root {
+			attrs,
+			items,
+			defs,
+			id: self.lcx.next_id(),
+			span: *span,
+		};
+		self.check_operations(&root);
+		root
+	}
+}
+
+/// Items found through a [`Res::Def`](crate::types::Res::Def) are of the kind it
+/// names, as definitions are collected along with their kind.
+#[track_caller]
+fn mismatched_def_kind() -> ! {
+	unreachable!("definition kinds always match their item")
+}
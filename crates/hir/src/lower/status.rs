@@ -0,0 +1,84 @@
+use crate::{
+	HirLowerer,
+	errors::lower::{
+		DuplicateStatusCode, ExpectedStatusCode, InvalidStatusCode, InvalidStatusRange,
+		UnknownStatusName,
+	},
+	types::{Expr, ExprKind, Item, ItemKind, Path, Status},
+};
+use dapic_lexer::rich::LiteralKind;
+use dapic_session::symbols::kw;
+use std::collections::HashMap;
+
+impl HirLowerer<'_, '_> {
+	/// Checks the code of a `code` item, names are looked up in the IANA registry.
+	pub(super) fn lower_status(&self, code: &Expr<'_>) -> Status {
+		let diagnostics = &self.lcx.session.diagnostics;
+
+		match code.kind {
+			ExprKind::Literal(LiteralKind::Number, number) => {
+				if let Ok(code @ 100..=599) = number.as_str().parse() {
+					Status::Code(code)
+				} else {
+					diagnostics.emit(InvalidStatusCode {
+						span: code.span,
+						code: number,
+					});
+					Status::Err
+				}
+			}
+			ExprKind::Template(range) => {
+				if let [class @ b'1'..=b'5', b'x' | b'X', b'x' | b'X'] = range.as_str().as_bytes() {
+					Status::Range(class - b'0')
+				} else {
+					diagnostics.emit(InvalidStatusRange {
+						span: code.span,
+						range,
+					});
+					Status::Err
+				}
+			}
+			ExprKind::Path(Path {
+				segments: [segment],
+				..
+			}) => {
+				if segment.ident.symbol == kw::Default {
+					Status::Default
+				} else if let Some(code) = Status::code_of(segment.ident.symbol.as_str()) {
+					Status::Code(code)
+				} else {
+					diagnostics.emit(UnknownStatusName {
+						name: segment.ident,
+					});
+					Status::Err
+				}
+			}
+			_ => {
+				diagnostics.emit(ExpectedStatusCode { span: code.span });
+				Status::Err
+			}
+		}
+	}
+
+	/// Reports `code` items of a verb that describe the same status.
+	pub(super) fn check_responses(&self, items: &[Item<'_>]) {
+		let mut seen = HashMap::new();
+
+		for item in items {
+			let ItemKind::StatusCode(code) = &item.kind else {
+				continue;
+			};
+			if code.status == Status::Err {
+				continue;
+			}
+
+			if let Some(previous) = seen.insert(code.status, code.code.span) {
+				self.lcx.session.diagnostics.emit(DuplicateStatusCode {
+					span: code.code.span,
+					previous,
+					status: code.status,
+				});
+			}
+		}
+	}
+}
@@ -0,0 +1,590 @@
+//! Name resolution
+//!
+//! [`DefCollector`] walks the `AST` before lowering to build a symbol table for
+//! every `scope`. The [`Resolver`] it produces is then queried while lowering
+//! to resolve type paths to their [`DefId`].
+
+use crate::{
+	errors::resolve::{
+		AmbiguousType, DuplicateDefinition, ShadowedPrimitive, UnknownAuth, UnknownBlock,
+		UnknownScope, UnknownType,
+	},
+	types::{DefId, DefKind, Res},
+};
+use dapic_ast::{
+	types::{Auth, Item, ItemKind, NodeId, P, Path, Root},
+	visit::{Visitor, noop},
+};
+use dapic_session::{
+	Diagnostic, Ident, IndexVec, PrimTy, Session, Span, Symbol, new_index_ty, symbols::kw,
+};
+use std::{collections::HashMap, fmt::Write, mem};
+
+new_index_ty! {
+	/// Identifies a `scope`, the root of the api being the first one.
+	pub(crate) struct ModuleId;
+}
+
+impl ModuleId {
+	pub(crate) const ROOT: Self = Self(0);
+}
+
+/// Types and auth schemes are looked up separately, a model and an auth scheme
+/// can share a name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Namespace {
+	Type,
+	Auth,
+	/// Each kind of shared block has its own names
+	Block(DefKind),
+}
+
+impl Namespace {
+	const fn of(kind: DefKind) -> Self {
+		match kind {
+			DefKind::Model | DefKind::Enum | DefKind::Union | DefKind::TypeAlias => Self::Type,
+			DefKind::Auth => Self::Auth,
+			DefKind::Headers | DefKind::Query | DefKind::Params | DefKind::Response => {
+				Self::Block(kind)
+			}
+		}
+	}
+}
+
+#[derive(Debug)]
+pub(crate) struct Module {
+	parent: Option<ModuleId>,
+	/// Same convention as the expansion phase: the first segment is [`kw::PathRoot`].
+	pub(crate) mod_path: Vec<Ident>,
+
+	children: HashMap<Symbol, ModuleId>,
+	defs: HashMap<(Namespace, Symbol), DefId>,
+}
+
+#[derive(Debug)]
+pub(crate) struct DefData {
+	pub(crate) kind: DefKind,
+	pub(crate) ident: Ident,
+	pub(crate) module: ModuleId,
+}
+
+#[derive(Debug)]
+pub(crate) struct Resolver {
+	pub(crate) modules: IndexVec<ModuleId, Module>,
+	pub(crate) defs: IndexVec<DefId, DefData>,
+	/// Generic models, lowered again for every instantiation.
+	pub(crate) templates: HashMap<DefId, P<Item>>,
+	/// Shared blocks, lowered when first used.
+	pub(crate) blocks: HashMap<DefId, P<Item>>,
+
+	node_modules: HashMap<NodeId, ModuleId>,
+	node_defs: HashMap<NodeId, DefId>,
+}
+
+impl Default for Resolver {
+	fn default() -> Self {
+		let mut modules = IndexVec::default();
+		modules.push(Module {
+			parent: None,
+			mod_path: vec![Ident::new(kw::PathRoot, Span::DUMMY)],
+			children: HashMap::default(),
+			defs: HashMap::default(),
+		});
+
+		Self {
+			modules,
+			defs: IndexVec::default(),
+			templates: HashMap::default(),
+			blocks: HashMap::default(),
+			node_modules: HashMap::default(),
+			node_defs: HashMap::default(),
+		}
+	}
+}
+
+impl Resolver {
+	/// Returns the module introduced by the `scope` item with the given id.
+	///
+	/// # Panics
+	/// If the item was not visited by the [`DefCollector`]
+	pub(crate) fn module_of(&self, id: NodeId) -> ModuleId {
+		self.node_modules[&id]
+	}
+
+	pub(crate) fn def_of(&self, id: NodeId) -> Option<DefId> {
+		self.node_defs.get(&id).copied()
+	}
+
+	/// Resolves a type path from the given module.
+	///
+	/// Single segment paths are first checked against built-in primitives, then
+	/// looked up in the current scope and its parents. If no candidate is found, every scope of the api is searched
+	/// and the name resolves if it is defined in exactly one of them.
+	///
+	/// Qualified paths (e.g. `pet::Status`) start from a scope reachable from
+	/// the current one, like a sibling, and go down the scope hierarchy.
+	pub(crate) fn resolve_path(&self, module: ModuleId, path: &Path) -> Result<Res, Diagnostic> {
+		let Some((last, scopes)) = path.segments.split_last() else {
+			unreachable!("paths always have at least one segment")
+		};
+
+		if scopes.is_empty()
+			&& let Some(prim) = PrimTy::from_symbol(last.ident.symbol)
+		{
+			return Ok(Res::PrimTy(prim));
+		}
+
+		let def_id = if let Some((first, scopes)) = scopes.split_first() {
+			let mut module = self
+				.ancestors(module)
+				.find_map(|module| self.modules[module].children.get(&first.ident.symbol))
+				.copied()
+				.ok_or_else(|| UnknownScope { scope: first.ident }.into())?;
+
+			for segment in scopes {
+				module = *self.modules[module]
+					.children
+					.get(&segment.ident.symbol)
+					.ok_or_else(|| {
+						UnknownScope {
+							scope: segment.ident,
+						}
+						.into()
+					})?;
+			}
+
+			self.modules[module]
+				.defs
+				.get(&(Namespace::Type, last.ident.symbol))
+				.copied()
+				.ok_or_else(|| unknown_type(path))?
+		} else {
+			self.lookup(module, last.ident, Namespace::Type)?
+				.ok_or_else(|| unknown_type(path))?
+		};
+
+		Ok(Res::Def(self.defs[def_id].kind, def_id))
+	}
+
+	/// Resolves the name of an `auth Name;` use, the same way as single segment
+	/// type paths.
+	pub(crate) fn resolve_auth(&self, module: ModuleId, name: Ident) -> Result<Res, Diagnostic> {
+		let def_id = self
+			.lookup(module, name, Namespace::Auth)?
+			.ok_or_else(|| UnknownAuth { name }.into())?;
+
+		Ok(Res::Def(DefKind::Auth, def_id))
+	}
+
+	/// Resolves the name of a shared block use (e.g. `headers Name;`), the same
+	/// way as single segment type paths.
+	pub(crate) fn resolve_block(
+		&self,
+		module: ModuleId,
+		name: Ident,
+		kind: DefKind,
+	) -> Result<DefId, Diagnostic> {
+		self.lookup(module, name, Namespace::Block(kind))?
+			.ok_or_else(|| UnknownBlock { name, kind }.into())
+	}
+
+	/// Looks up a name in the given module and its parents, then in every
+	/// scope of the api if it is defined in exactly one of them.
+	fn lookup(
+		&self,
+		module: ModuleId,
+		name: Ident,
+		ns: Namespace,
+	) -> Result<Option<DefId>, Diagnostic> {
+		let key = (ns, name.symbol);
+
+		if let Some(def_id) = self
+			.ancestors(module)
+			.find_map(|module| self.modules[module].defs.get(&key))
+		{
+			return Ok(Some(*def_id));
+		}
+
+		let candidates = self
+			.modules
+			.iter()
+			.filter_map(|module| module.defs.get(&key))
+			.copied()
+			.collect::<Vec<_>>();
+
+		match *candidates.as_slice() {
+			[def_id] => Ok(Some(def_id)),
+			[] => Ok(None),
+			_ => Err(AmbiguousType {
+				name,
+				candidates: candidates.iter().enumerate().fold(
+					String::new(),
+					|mut s, (idx, def_id)| {
+						if idx != 0 {
+							s.push_str(", ");
+						}
+						write!(s, "`{}`", self.def_path_str(*def_id))
+							.expect("writing to a string never fails");
+						s
+					},
+				),
+			}
+			.into()),
+		}
+	}
+
+	/// Iterates over the given module and its parents, up to the root.
+	fn ancestors(&self, module: ModuleId) -> impl Iterator<Item = ModuleId> + '_ {
+		std::iter::successors(Some(module), |module| self.modules[*module].parent)
+	}
+
+	/// Formats the fully qualified path of a definition, e.g. `pet::Status`.
+	pub(crate) fn def_path_str(&self, def_id: DefId) -> String {
+		let def = &self.defs[def_id];
+		self.modules[def.module]
+			.mod_path
+			.iter()
+			.skip(1)
+			.chain([&def.ident])
+			.map(|ident| ident.symbol.as_str())
+			.collect::<Vec<_>>()
+			.join("::")
+	}
+}
+
+fn unknown_type(path: &Path) -> Diagnostic {
+	UnknownType {
+		span: path.span,
+		path: path
+			.segments
+			.iter()
+			.map(|segment| segment.ident.symbol.as_str())
+			.collect::<Vec<_>>()
+			.join("::"),
+	}
+	.into()
+}
+
+/// Collects every scope and definition of the api into a [`Resolver`].
+pub(crate) struct DefCollector<'a> {
+	session: &'a Session,
+	resolver: Resolver,
+	current: ModuleId,
+}
+
+impl<'a> DefCollector<'a> {
+	pub(crate) fn collect(session: &'a Session, ast: &Root) -> Resolver {
+		let mut collector = Self {
+			session,
+			resolver: Resolver::default(),
+			current: ModuleId::ROOT,
+		};
+
+		collector.visit_root(ast);
+		collector.resolver
+	}
+
+	fn define(&mut self, item: &Item, kind: DefKind) {
+		let ns = Namespace::of(kind);
+
+		if ns == Namespace::Type
+			&& let Some(prim) = PrimTy::from_symbol(item.ident.symbol)
+		{
+			self.session.diagnostics.emit(ShadowedPrimitive {
+				name: item.ident,
+				kind,
+				prim,
+			});
+		}
+
+		let def_id = self.resolver.defs.push(DefData {
+			kind,
+			ident: item.ident,
+			module: self.current,
+		});
+		self.resolver.node_defs.insert(item.id, def_id);
+
+		let module = &mut self.resolver.modules[self.current];
+		if let Some(&previous) = module.defs.get(&(ns, item.ident.symbol)) {
+			self.session.diagnostics.emit(DuplicateDefinition {
+				name: item.ident,
+				previous: self.resolver.defs[previous].ident.span,
+			});
+		} else {
+			module.defs.insert((ns, item.ident.symbol), def_id);
+		}
+	}
+
+	fn enter_module(&mut self, item: &Item) -> ModuleId {
+		let parent = &self.resolver.modules[self.current];
+
+		let mut mod_path = parent.mod_path.clone();
+		mod_path.push(item.ident);
+
+		let module = self.resolver.modules.push(Module {
+			parent: Some(self.current),
+			mod_path,
+			children: HashMap::default(),
+			defs: HashMap::default(),
+		});
+		self.resolver.node_modules.insert(item.id, module);
+
+		let parent = &mut self.resolver.modules[self.current];
+		if let Some(&previous) = parent.children.get(&item.ident.symbol) {
+			self.session.diagnostics.emit(DuplicateDefinition {
+				name: item.ident,
+				previous: self.resolver.modules[previous]
+					.mod_path
+					.last()
+					.map_or(Span::DUMMY, |ident| ident.span),
+			});
+		} else {
+			parent.children.insert(item.ident.symbol, module);
+		}
+
+		mem::replace(&mut self.current, module)
+	}
+}
+
+impl Visitor for DefCollector<'_> {
+	fn visit_item(&mut self, item: &P<Item>) {
+		match &item.kind {
+			ItemKind::Scope(_) => {
+				let parent = self.enter_module(item);
+				noop::visit_item(self, item);
+				self.current = parent;
+				return;
+			}
+			ItemKind::Model(model) => {
+				self.define(item, DefKind::Model);
+				if !model.generics.is_empty() {
+					let def_id = self.resolver.node_defs[&item.id];
+					self.resolver.templates.insert(def_id, item.clone());
+				}
+			}
+			ItemKind::Enum(_) => self.define(item, DefKind::Enum),
+			ItemKind::Union(_) => self.define(item, DefKind::Union),
+			ItemKind::TypeAlias(_) => self.define(item, DefKind::TypeAlias),
+			ItemKind::Auth(Auth::Define(_)) => self.define(item, DefKind::Auth),
+			ItemKind::Headers(_)
+			| ItemKind::Query(_)
+			| ItemKind::Params(_)
+			| ItemKind::Response(_)
+				if item.ident.symbol != kw::Empty =>
+			{
+				let kind = match item.kind {
+					ItemKind::Headers(_) => DefKind::Headers,
+					ItemKind::Query(_) => DefKind::Query,
+					ItemKind::Params(_) => DefKind::Params,
+					_ => DefKind::Response,
+				};
+				self.define(item, kind);
+
+				let def_id = self.resolver.node_defs[&item.id];
+				self.resolver.blocks.insert(def_id, item.clone());
+			}
+			_ => {}
+		}
+
+		noop::visit_item(self, item);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		tests::{META, assert_reports, with_hir},
+		types::{DefKind, ItemKind, Res, TyKind},
+	};
+
+	/// Compiles `src` and returns the resolution of every model field type,
+	/// formatted as `<field>: <definition path>`, and whether errors were emitted.
+	fn resolve_fields(src: &str) -> (Vec<String>, bool) {
+		with_hir(src, |hir| {
+			hir.defs()
+				.filter_map(|(_, def)| match &def.item.kind {
+					ItemKind::Model(model) => Some(model.fields),
+					_ => None,
+				})
+				.flatten()
+				.map(|field| {
+					let res = match field.ty.kind {
+						TyKind::Path(_, Res::Def(_, def_id)) => {
+							let def = hir.def(def_id);
+							def.mod_path
+								.iter()
+								.skip(1)
+								.chain([&def.item.ident])
+								.map(ToString::to_string)
+								.collect::<Vec<_>>()
+								.join("::")
+						}
+						TyKind::Path(_, Res::PrimTy(prim)) => format!("prim {prim}"),
+						TyKind::Path(_, Res::Err) => "{error}".to_owned(),
+						_ => "{other}".to_owned(),
+					};
+					format!("{}: {res}", field.ident)
+				})
+				.collect()
+		})
+	}
+
+	#[test]
+	fn resolve_lexical_scopes() {
+		let (fields, errors) = resolve_fields(
+			r#"
+			meta { name "Test" version "1" }
+
+			model Category {}
+
+			scope pet {
+				enum Status {
+					Available "available"
+				}
+
+				model Pet {
+					category Category
+					status Status
+				}
+			}
+			"#,
+		);
+
+		assert!(!errors);
+		assert_eq!(fields, ["category: Category", "status: pet::Status"]);
+	}
+
+	#[test]
+	fn resolve_qualified_and_unique_paths() {
+		let (fields, errors) = resolve_fields(
+			r#"
+			meta { name "Test" version "1" }
+
+			scope pet {
+				enum Status {}
+			}
+
+			scope store {
+				enum Status {}
+
+				model Order {
+					pet pet::Status
+					store Status
+				}
+			}
+
+			scope user {
+				model User {
+					order Order
+				}
+			}
+			"#,
+		);
+
+		assert!(!errors);
+		assert_eq!(
+			fields,
+			[
+				"pet: pet::Status",
+				"store: store::Status",
+				"order: store::Order"
+			]
+		);
+	}
+
+	#[test]
+	fn report_unknown_and_ambiguous_paths() {
+		let (fields, errors) = resolve_fields(
+			r#"
+			meta { name "Test" version "1" }
+
+			scope pet {
+				enum Status {}
+			}
+
+			scope store {
+				enum Status {}
+			}
+
+			model Order {
+				status Status
+				pet store::Pet
+				user user::User
+				unknown Unknown
+			}
+			"#,
+		);
+
+		assert!(errors);
+		assert_eq!(
+			fields,
+			[
+				"status: {error}",
+				"pet: {error}",
+				"user: {error}",
+				"unknown: {error}"
+			]
+		);
+	}
+
+	#[test]
+	fn report_duplicate_definitions() {
+		assert_reports([(
+			format!("{META} model Pet {{}} enum Pet {{}}"),
+			"DuplicateDefinition",
+		)]);
+	}
+
+	#[test]
+	fn resolve_primitives_first() {
+		let (fields, errors) = resolve_fields(
+			r#"
+			meta { name "Test" version "1" }
+
+			scope text {
+				model string {}
+			}
+
+			model Pet {
+				id int |@format: "int64"|
+				name string
+				born date-time
+				shadowed text::string
+			}
+			"#,
+		);
+
+		assert!(!errors);
+		assert_eq!(
+			fields,
+			[
+				"id: prim int",
+				"name: prim string",
+				"born: prim date-time",
+				"shadowed: text::string"
+			]
+		);
+	}
+
+	#[test]
+	fn report_invalid_formats() {
+		assert_reports(
+			[
+				(r#"id int |@format: "email"|"#, "InvalidFormat"),
+				(r#"alive bool |@format: "int32"|"#, "UnexpectedFormat"),
+			]
+			.map(|(field, code)| (format!("{META} model Pet {{ {field} }}"), code)),
+		);
+	}
+
+	#[test]
+	fn def_kinds() {
+		let (kinds, errors) = with_hir(
+			&format!("{META}\nmodel Pet {{}}\nenum Status {{}}"),
+			|hir| hir.defs().map(|(_, def)| def.kind).collect::<Vec<_>>(),
+		);
+
+		assert!(!errors);
+		assert_eq!(kinds, [DefKind::Model, DefKind::Enum]);
+	}
+}
@@ -0,0 +1,93 @@
+use crate::types::{Expr, HirId};
+use dapic_ast::types::AttrStyle;
+use dapic_lexer::rich::{Delimiter, Token};
+use dapic_session::{Ident, Span, Symbol, symbols::attrs};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attribute<'tcx> {
+	pub kind: AttrKind<'tcx>,
+	pub style: AttrStyle,
+
+	pub id: HirId,
+	pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttrKind<'tcx> {
+	/// A normal attribute. Tokens are kept as is to be processed by the
+	/// consumers that know about them.
+	Normal(NormalAttr<'tcx>),
+
+	/// A simple key-value attribute (e.g. `@key: "value"` where "value" can be any expr).
+	Meta(MetaAttr<'tcx>),
+
+	/// A doc comment (e.g. `## ...`, `##! ...`).
+	DocComment(Symbol),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NormalAttr<'tcx> {
+	pub path: Ident,
+	pub delim: Delimiter,
+	pub tokens: &'tcx [Token],
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetaAttr<'tcx> {
+	pub ident: Ident,
+	pub expr: Option<&'tcx Expr<'tcx>>,
+}
+
+impl<'tcx> Attribute<'tcx> {
+	/// Returns the value of a `@name: <expr>` attribute.
+	#[must_use]
+	pub fn meta_value(&self, name: Symbol) -> Option<&'tcx Expr<'tcx>> {
+		match self.kind {
+			AttrKind::Meta(MetaAttr { ident, expr }) if ident.symbol == name => expr,
+			_ => None,
+		}
+	}
+}
+
+/// Joins every doc comment line, `None` if there is none.
+///
+/// The space following `##` is stripped, the rest of the line is kept verbatim.
+#[must_use]
+pub fn docs(attrs: &[Attribute<'_>]) -> Option<String> {
+	let lines = attrs
+		.iter()
+		.filter_map(|attr| match attr.kind {
+			AttrKind::DocComment(line) => {
+				let line = line.as_str();
+				Some(line.strip_prefix(' ').unwrap_or(line).to_owned())
+			}
+			_ => None,
+		})
+		.collect::<Vec<_>>();
+
+	(!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+/// Uses the `@description` attribute (or its string sugar) and falls back to
+/// doc comments.
+#[must_use]
+pub fn description(attrs: &[Attribute<'_>]) -> Option<String> {
+	find_meta(attrs, attrs::description)
+		.and_then(Expr::str_literal)
+		.map(|description| description.to_string())
+		.or_else(|| docs(attrs))
+}
+
+/// Whether there is a `@name` (or `@name: <expr>`) attribute.
+#[must_use]
+pub fn has_attr(attrs: &[Attribute<'_>], name: Symbol) -> bool {
+	attrs.iter().any(
+		|attr| matches!(attr.kind, AttrKind::Meta(MetaAttr { ident, .. }) if ident.symbol == name),
+	)
+}
+
+/// Returns the value of the first `@name: <expr>` attribute.
+#[must_use]
+pub fn find_meta<'tcx>(attrs: &[Attribute<'tcx>], name: Symbol) -> Option<&'tcx Expr<'tcx>> {
+	attrs.iter().find_map(|attr| attr.meta_value(name))
+}
@@ -0,0 +1,279 @@
+use crate::types::{Attribute, Res};
+use dapic_session::{Span, Symbol};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Auth<'tcx> {
+	/// `auth Name;`, secures the enclosing items with the resolved scheme.
+	Use {
+		res: Res,
+		/// `OAuth2` or `OpenID Connect` scopes required by the items
+		scopes: &'tcx [Symbol],
+	},
+	/// `auth Name { <properties> }`
+	Define(AuthScheme<'tcx>),
+}
+
+/// A security scheme, mirrors the `OpenAPI` ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthScheme<'tcx> {
+	/// `type "apiKey"`
+	ApiKey {
+		/// Name of the header, query parameter or cookie
+		name: Symbol,
+		location: ApiKeyLocation,
+	},
+	/// `type "http"`
+	Http {
+		/// e.g. `basic` or `bearer`
+		scheme: Symbol,
+		/// e.g. `JWT`
+		bearer_format: Option<Symbol>,
+	},
+	/// `type "oauth2"`
+	OAuth2 { flows: &'tcx [AuthFlow<'tcx>] },
+	/// `type "openIdConnect"`
+	OpenIdConnect {
+		/// `OpenID Connect` discovery url
+		url: Symbol,
+	},
+
+	/// The definition is invalid, an error was already reported.
+	Err,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiKeyLocation {
+	Header,
+	Query,
+	Cookie,
+}
+
+/// `flow <kind> { <properties> scopes { <scopes> } }`
+///
+/// Flows are only lowered when their required urls are present.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthFlow<'tcx> {
+	pub kind: AuthFlowKind,
+	/// Required by the implicit and authorization code flows
+	pub authorization_url: Option<Symbol>,
+	/// Required by every flow but the implicit one
+	pub token_url: Option<Symbol>,
+	pub refresh_url: Option<Symbol>,
+	pub scopes: &'tcx [AuthScope<'tcx>],
+
+	pub span: Span,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthFlowKind {
+	Implicit,
+	Password,
+	ClientCredentials,
+	AuthorizationCode,
+}
+
+/// An `OAuth2` scope, documented with doc comments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthScope<'tcx> {
+	pub attrs: &'tcx [Attribute<'tcx>],
+	pub name: Symbol,
+
+	pub span: Span,
+}
+
+impl ApiKeyLocation {
+	pub const ALL: &[Self] = &[Self::Header, Self::Query, Self::Cookie];
+
+	#[must_use]
+	pub fn from_name(location: &str) -> Option<Self> {
+		Self::ALL
+			.iter()
+			.copied()
+			.find(|loc| loc.as_str() == location)
+	}
+
+	#[must_use]
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			Self::Header => "header",
+			Self::Query => "query",
+			Self::Cookie => "cookie",
+		}
+	}
+}
+
+impl AuthFlowKind {
+	pub const ALL: &[Self] = &[
+		Self::Implicit,
+		Self::Password,
+		Self::ClientCredentials,
+		Self::AuthorizationCode,
+	];
+
+	#[must_use]
+	pub fn from_name(kind: &str) -> Option<Self> {
+		Self::ALL.iter().copied().find(|flow| flow.as_str() == kind)
+	}
+
+	#[must_use]
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			Self::Implicit => "implicit",
+			Self::Password => "password",
+			Self::ClientCredentials => "clientCredentials",
+			Self::AuthorizationCode => "authorizationCode",
+		}
+	}
+
+	#[must_use]
+	pub const fn requires_authorization_url(self) -> bool {
+		matches!(self, Self::Implicit | Self::AuthorizationCode)
+	}
+
+	#[must_use]
+	pub const fn requires_token_url(self) -> bool {
+		!matches!(self, Self::Implicit)
+	}
+}
+
+impl fmt::Display for ApiKeyLocation {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.as_str())
+	}
+}
+
+impl fmt::Display for AuthFlowKind {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.as_str())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		tests::{META, assert_reports, with_hir},
+		types::{ApiKeyLocation, Auth, AuthFlowKind, AuthScheme, ItemKind},
+	};
+
+	#[test]
+	fn resolve_auth_uses() {
+		let (auths, errors) = with_hir(
+			r#"
+			meta { name "Test" version "1" }
+
+			scope pet {
+				auth BasicApiKey {
+					type "apiKey"
+					in "header"
+					name "api_key"
+				}
+
+				auth PetstoreAuth {
+					type "oauth2"
+					flow implicit {
+						authorizationUrl "https://petstore.swagger.io/oauth/authorize"
+						scopes {
+							## Modify pets in your account
+							"write:pets"
+							"read:pets"
+						}
+					}
+				}
+
+				auth BasicApiKey;
+
+				path pet {
+					verb GET {}
+
+					verb DELETE {
+						auth PetstoreAuth ["write:pets"];
+					}
+				}
+			}
+			"#,
+			|hir| {
+				let schemes = hir
+					.defs()
+					.map(|(_, def)| match &def.item.kind {
+						ItemKind::Auth(Auth::Define(scheme)) => scheme.clone(),
+						_ => unreachable!(),
+					})
+					.collect::<Vec<_>>();
+
+				assert!(matches!(
+					schemes[0],
+					AuthScheme::ApiKey {
+						location: ApiKeyLocation::Header,
+						..
+					}
+				));
+				let AuthScheme::OAuth2 { flows } = schemes[1] else {
+					panic!("expected an oauth2 scheme");
+				};
+				assert_eq!(flows[0].kind, AuthFlowKind::Implicit);
+				assert_eq!(flows[0].scopes.len(), 2);
+
+				hir.endpoints()
+					.iter()
+					.map(|endpoint| {
+						endpoint
+							.auths()
+							.map(|(def_id, scopes)| {
+								let scopes =
+									scopes.iter().map(ToString::to_string).collect::<Vec<_>>();
+								format!("{}{scopes:?}", hir.def(def_id).item.ident)
+							})
+							.collect::<Vec<_>>()
+							.join(", ")
+					})
+					.collect::<Vec<_>>()
+			},
+		);
+
+		assert!(!errors);
+		assert_eq!(
+			auths,
+			[
+				"BasicApiKey[]",
+				r#"BasicApiKey[], PetstoreAuth["write:pets"]"#
+			]
+		);
+	}
+
+	#[test]
+	fn report_invalid_auth() {
+		assert_reports(
+			[
+				(r"auth Unknown;", "UnknownAuth"),
+				(
+					r#"auth Key { type "apiKey" name "api_key" }"#,
+					"MissingAuthProperty",
+				),
+				(
+					r#"auth Key { type "apiKey" in "body" name "api_key" }"#,
+					"InvalidAuthValue",
+				),
+				(
+					r#"auth Basic { type "http" scheme "basic" in "header" }"#,
+					"UnknownAuthProperty",
+				),
+				(r#"auth Basic { type "digest" }"#, "InvalidAuthValue"),
+				(r#"auth OAuth { type "oauth2" }"#, "MissingAuthProperty"),
+				(
+					r#"auth OAuth { type "oauth2" flow implicit { tokenUrl "https://a.example.com" } }"#,
+					"MissingAuthFlowProperty",
+				),
+				(
+					r#"auth OAuth { type "oauth2" flow device { tokenUrl "https://a.example.com" } }"#,
+					"UnknownAuthFlow",
+				),
+				(
+					r#"auth Basic { type "http" scheme "basic" flow implicit {} }"#,
+					"UnexpectedAuthFlow",
+				),
+			]
+			.map(|(src, code)| (format!("{META} {src}"), code)),
+		);
+	}
+}
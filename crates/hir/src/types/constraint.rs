@@ -0,0 +1,260 @@
+use crate::types::{DefKind, Res, Ty, TyKind};
+use dapic_session::{JsonTy, Symbol, symbols::attrs};
+use std::fmt;
+
+/// A validation rule of a field or a body, e.g. `@min: 0` or `@unique`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Constraint {
+	Min(f64),
+	Max(f64),
+	MultipleOf(f64),
+	MinLength(u64),
+	MaxLength(u64),
+	/// An ECMA-262 regular expression, not checked by the compiler
+	Pattern(Symbol),
+	MinItems(u64),
+	MaxItems(u64),
+	/// Items of the array are all different
+	Unique,
+}
+
+// Numbers are parsed from literals and are never NaN
+impl Eq for Constraint {}
+
+/// Kind of value a constraint applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstraintTarget {
+	Number,
+	String,
+	Array,
+}
+
+/// Kind of value expected by a constraint attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstraintValue {
+	/// e.g. `@min: -1.5`
+	Number,
+	/// e.g. `@minLength: 1`
+	Count,
+	/// e.g. `@pattern: "^[a-z]+$"`
+	Str,
+	/// e.g. `@unique`
+	None,
+}
+
+impl Constraint {
+	#[must_use]
+	pub const fn name(self) -> Symbol {
+		match self {
+			Self::Min(_) => attrs::min,
+			Self::Max(_) => attrs::max,
+			Self::MultipleOf(_) => attrs::multipleOf,
+			Self::MinLength(_) => attrs::minLength,
+			Self::MaxLength(_) => attrs::maxLength,
+			Self::Pattern(_) => attrs::pattern,
+			Self::MinItems(_) => attrs::minItems,
+			Self::MaxItems(_) => attrs::maxItems,
+			Self::Unique => attrs::unique,
+		}
+	}
+
+	/// Target and value of the constraint attribute `name`.
+	#[must_use]
+	pub const fn signature(name: Symbol) -> Option<(ConstraintTarget, ConstraintValue)> {
+		use ConstraintTarget as T;
+		use ConstraintValue as V;
+
+		Some(match name {
+			attrs::min | attrs::max | attrs::multipleOf => (T::Number, V::Number),
+			attrs::minLength | attrs::maxLength => (T::String, V::Count),
+			attrs::pattern => (T::String, V::Str),
+			attrs::minItems | attrs::maxItems => (T::Array, V::Count),
+			attrs::unique => (T::Array, V::None),
+			_ => return None,
+		})
+	}
+
+	/// Builds the constraint from the literal of the attribute, `None` if it
+	/// is not a valid value.
+	///
+	/// # Panics
+	/// If `name` is not a constraint.
+	#[must_use]
+	pub fn from_value(name: Symbol, value: Option<Symbol>) -> Option<Self> {
+		let number = || {
+			value?
+				.as_str()
+				.replace('_', "")
+				.parse::<f64>()
+				.ok()
+				.filter(|number| number.is_finite())
+		};
+		let count = || value?.as_str().replace('_', "").parse::<u64>().ok();
+
+		Some(match name {
+			attrs::min => Self::Min(number()?),
+			attrs::max => Self::Max(number()?),
+			attrs::multipleOf => Self::MultipleOf(number().filter(|number| *number > 0.)?),
+			attrs::minLength => Self::MinLength(count()?),
+			attrs::maxLength => Self::MaxLength(count()?),
+			attrs::pattern => Self::Pattern(value?),
+			attrs::minItems => Self::MinItems(count()?),
+			attrs::maxItems => Self::MaxItems(count()?),
+			attrs::unique => Self::Unique,
+			_ => unreachable!("`{name}` is not a constraint"),
+		})
+	}
+}
+
+impl ConstraintTarget {
+	/// Kind of values described by a type, `None` when constraints can't be
+	/// checked (e.g. `any`).
+	#[must_use]
+	pub fn of(ty: &Ty<'_>) -> Option<Result<Self, &'static str>> {
+		Some(match ty.kind {
+			TyKind::Path(_, Res::PrimTy(prim)) => match prim.json_ty()? {
+				JsonTy::Integer | JsonTy::Number => Ok(Self::Number),
+				JsonTy::String => Ok(Self::String),
+				JsonTy::Boolean => Err("a boolean"),
+			},
+			TyKind::Path(_, Res::Def(DefKind::Model, _)) | TyKind::InlineModel(_) => Err("a model"),
+			TyKind::Path(_, Res::Def(DefKind::Enum, _)) => Err("an enum"),
+			TyKind::Path(_, Res::Def(DefKind::Union, _)) | TyKind::Union(_) => Err("a union"),
+			// Aliases may be lowered after their uses, their constraints are
+			// checked on their own type
+			TyKind::Path(
+				_,
+				Res::Def(
+					DefKind::Auth
+					| DefKind::TypeAlias
+					| DefKind::Headers
+					| DefKind::Query
+					| DefKind::Params
+					| DefKind::Response,
+					_,
+				)
+				| Res::TyParam
+				| Res::Err,
+			) => {
+				return None;
+			}
+			TyKind::Array(_) => Ok(Self::Array),
+			TyKind::Tuple(_) => Err("a tuple"),
+			TyKind::Map(..) => Err("a map"),
+			TyKind::Nullable(ty) => return Self::of(ty),
+		})
+	}
+
+	#[must_use]
+	pub const fn noun(self) -> &'static str {
+		match self {
+			Self::Number => "a number",
+			Self::String => "a string",
+			Self::Array => "an array",
+		}
+	}
+}
+
+impl fmt::Display for ConstraintTarget {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Number => write!(f, "numbers"),
+			Self::String => write!(f, "strings"),
+			Self::Array => write!(f, "arrays"),
+		}
+	}
+}
+
+impl ConstraintValue {
+	/// Kind of value expected, for diagnostics.
+	#[must_use]
+	pub const fn expected(self) -> &'static str {
+		match self {
+			Self::Number => "a number",
+			Self::Count => "a non-negative integer",
+			Self::Str => "a string",
+			Self::None => "no value",
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		tests::{META, assert_reports, with_hir},
+		types::{Constraint, ItemKind},
+	};
+	use dapic_session::sym;
+
+	#[test]
+	fn lower_constraints() {
+		let (constraints, errors) = with_hir(
+			r#"
+			meta { name "Test" version "1" }
+
+			model Pet {
+				@@min: -1.5
+				@@max: 10
+				weight double
+
+				@@pattern: "^[a-z]+$"
+				@@maxLength: 64
+				name string?
+
+				@@unique
+				@@minItems: 1
+				tags [string]
+			}
+			"#,
+			|hir| {
+				hir.defs()
+					.flat_map(|(_, def)| match &def.item.kind {
+						ItemKind::Model(model) => model.fields,
+						_ => &[],
+					})
+					.flat_map(|field| field.constraints)
+					.copied()
+					.collect::<Vec<_>>()
+			},
+		);
+
+		assert!(!errors);
+		assert_eq!(
+			constraints,
+			[
+				Constraint::Min(-1.5),
+				Constraint::Max(10.),
+				Constraint::Pattern(sym!("^[a-z]+$")),
+				Constraint::MaxLength(64),
+				Constraint::Unique,
+				Constraint::MinItems(1),
+			]
+		);
+	}
+
+	#[test]
+	fn report_invalid_constraints() {
+		assert_reports(
+			[
+				(r#"@@pattern: "[0-9]+" id int"#, "UnexpectedConstraint"),
+				(r"@@unique id int", "UnexpectedConstraint"),
+				(r"@@min: 1 tags [int]", "UnexpectedConstraint"),
+				(r"@@minLength: 1 pet Pet", "UnexpectedConstraint"),
+				(r"@@minLength: -1 name string", "InvalidConstraintValue"),
+				(r"@@multipleOf: 0 weight double", "InvalidConstraintValue"),
+				(r"@@max: 1.5 id int", "InvalidConstraintValue"),
+				(r#"@@min: "1" weight double"#, "InvalidConstraintValue"),
+				(r"@@unique: true tags [int]", "InvalidConstraintValue"),
+				(
+					r"@@min: 10 @@max: 1 weight double",
+					"ConflictingConstraints",
+				),
+				(
+					r"@@minItems: 2 @@maxItems: 1 tags [int]",
+					"ConflictingConstraints",
+				),
+			]
+			.map(|(field, code)| (format!("{META} model Pet {{ {field} }}"), code)),
+		);
+	}
+}
@@ -0,0 +1,649 @@
+use crate::types::{
+	Auth, Body, DefId, FieldDef, Item, ItemKind, PathKind, Res, Response, Root, StatusCode, Verb,
+};
+use dapic_session::{Ident, Symbol};
+
+/// A `verb` flattened with every `path` item it is nested in.
+///
+/// This is the view generators want: one value per operation, independent of
+/// how the api author chose to nest paths.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoint<'tcx> {
+	/// Scopes the endpoint is declared in, without the api root.
+	pub mod_path: Vec<Ident>,
+	/// Segments of the full URL path, `.` segments are already dropped.
+	pub segments: Vec<EndpointSegment>,
+	/// Inferred `string` parameters of the variable segments, in order.
+	pub variables: Vec<&'tcx FieldDef<'tcx>>,
+	/// `params` items of the enclosing `path` items, `query` and `headers` items
+	/// and `auth` uses of the enclosing scopes and paths, outermost first.
+	pub inherited: Vec<&'tcx Item<'tcx>>,
+
+	pub verb: &'tcx Verb<'tcx>,
+	/// The `verb` item itself, to access its attributes.
+	pub item: &'tcx Item<'tcx>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndpointSegment {
+	/// e.g. `pet`
+	Literal(Ident),
+	/// e.g. `{petId}`
+	Variable(Ident),
+}
+
+impl<'tcx> Root<'tcx> {
+	/// Every `verb` of the api, in declaration order.
+	#[must_use]
+	pub fn endpoints(&self) -> Vec<Endpoint<'tcx>> {
+		let mut collector = EndpointCollector::default();
+		collector.collect(self.items);
+		collector.endpoints
+	}
+}
+
+impl<'tcx> Endpoint<'tcx> {
+	/// OpenAPI-style path template, e.g. `/pet/{petId}`.
+	#[must_use]
+	pub fn path(&self) -> String {
+		if self.segments.is_empty() {
+			return "/".into();
+		}
+
+		let mut path = String::new();
+		for segment in &self.segments {
+			match segment {
+				EndpointSegment::Literal(ident) => {
+					path.push('/');
+					path.push_str(ident.symbol.as_str());
+				}
+				EndpointSegment::Variable(ident) => {
+					path.push_str("/{");
+					path.push_str(ident.symbol.as_str());
+					path.push('}');
+				}
+			}
+		}
+
+		path
+	}
+
+	/// A name derived from the method and the path, e.g. `getPetByPetId` for
+	/// `GET /pet/{petId}`. It is unique across the api, collisions are
+	/// reported during lowering.
+	#[must_use]
+	pub fn operation_id(&self) -> String {
+		let mut id = self.verb.method.symbol.as_str().to_lowercase();
+
+		for segment in &self.segments {
+			match segment {
+				EndpointSegment::Literal(ident) => push_pascal_case(&mut id, ident.symbol.as_str()),
+				EndpointSegment::Variable(ident) => {
+					id.push_str("By");
+					push_pascal_case(&mut id, ident.symbol.as_str());
+				}
+			}
+		}
+
+		id
+	}
+
+	/// Inherited items followed by the `verb` ones, without shared blocks.
+	pub fn items(&self) -> impl Iterator<Item = &'tcx Item<'tcx>> + '_ {
+		self.inherited
+			.iter()
+			.copied()
+			.chain(self.verb.items)
+			.filter(|item| !item.is_shared_block())
+	}
+
+	/// Path parameters in the order of the variable segments, from the
+	/// `params` blocks or inferred as `string`.
+	pub fn params(&self) -> impl Iterator<Item = &'tcx FieldDef<'tcx>> + '_ {
+		self.path_params().map(|(_, field)| field)
+	}
+
+	/// Path parameters along with the shared `params` block defining them.
+	pub fn path_params(&self) -> impl Iterator<Item = (Option<DefId>, &'tcx FieldDef<'tcx>)> + '_ {
+		self.variables.iter().map(|variable| {
+			self.items()
+				.find_map(|item| match &item.kind {
+					ItemKind::Params(params) => params
+						.properties
+						.iter()
+						.find(|field| field.ident.symbol == variable.ident.symbol)
+						.map(|field| (params.def, field)),
+					_ => None,
+				})
+				.unwrap_or((None, variable))
+		})
+	}
+
+	/// Query parameters, from every `query` block.
+	pub fn query(&self) -> impl Iterator<Item = &'tcx FieldDef<'tcx>> + '_ {
+		self.items().flat_map(|item| match &item.kind {
+			ItemKind::Query(query) => query.fields,
+			_ => &[],
+		})
+	}
+
+	/// Request headers, from every `headers` block outside of `code` items.
+	pub fn headers(&self) -> impl Iterator<Item = &'tcx FieldDef<'tcx>> + '_ {
+		self.items().flat_map(|item| match &item.kind {
+			ItemKind::Headers(headers) => headers.headers,
+			_ => &[],
+		})
+	}
+
+	/// Auth schemes securing the endpoint along with the required scopes,
+	/// from every `auth` use.
+	pub fn auths(&self) -> impl Iterator<Item = (DefId, &'tcx [Symbol])> + '_ {
+		self.items().filter_map(|item| match item.kind {
+			ItemKind::Auth(Auth::Use {
+				res: Res::Def(_, def_id),
+				scopes,
+			}) => Some((def_id, scopes)),
+			_ => None,
+		})
+	}
+
+	/// The request `body` items, one per media type.
+	pub fn bodies(&self) -> impl Iterator<Item = (&'tcx Item<'tcx>, &'tcx Body<'tcx>)> {
+		bodies_of(self.verb.items)
+	}
+
+	/// Every `code` item of the verb.
+	pub fn responses(&self) -> impl Iterator<Item = (&'tcx Item<'tcx>, &'tcx StatusCode<'tcx>)> {
+		self.verb.items.iter().filter_map(|item| match &item.kind {
+			ItemKind::StatusCode(code) => Some((item, code)),
+			_ => None,
+		})
+	}
+}
+
+impl<'tcx> StatusCode<'tcx> {
+	/// The response `body` items, one per media type.
+	pub fn bodies(&self) -> impl Iterator<Item = (&'tcx Item<'tcx>, &'tcx Body<'tcx>)> {
+		bodies_of(self.items)
+	}
+
+	/// Response headers, from every `headers` block.
+	pub fn headers(&self) -> impl Iterator<Item = &'tcx FieldDef<'tcx>> {
+		headers_of(self.items)
+	}
+}
+
+impl<'tcx> Response<'tcx> {
+	/// The response `body` items, one per media type.
+	pub fn bodies(&self) -> impl Iterator<Item = (&'tcx Item<'tcx>, &'tcx Body<'tcx>)> {
+		bodies_of(self.items)
+	}
+
+	/// Response headers, from every `headers` block.
+	pub fn headers(&self) -> impl Iterator<Item = &'tcx FieldDef<'tcx>> {
+		headers_of(self.items)
+	}
+}
+
+fn headers_of<'tcx>(items: &'tcx [Item<'tcx>]) -> impl Iterator<Item = &'tcx FieldDef<'tcx>> {
+	items.iter().flat_map(|item| match &item.kind {
+		ItemKind::Headers(headers) if !item.is_shared_block() => headers.headers,
+		_ => &[],
+	})
+}
+
+fn bodies_of<'tcx>(
+	items: &'tcx [Item<'tcx>],
+) -> impl Iterator<Item = (&'tcx Item<'tcx>, &'tcx Body<'tcx>)> {
+	items.iter().filter_map(|item| match &item.kind {
+		ItemKind::Body(body) => Some((item, body)),
+		_ => None,
+	})
+}
+
+/// Appends `word` in `PascalCase`, `-` and `_` being word separators.
+fn push_pascal_case(buf: &mut String, word: &str) {
+	for part in word.split(['-', '_']) {
+		let mut chars = part.chars();
+		if let Some(first) = chars.next() {
+			buf.extend(first.to_uppercase());
+			buf.push_str(chars.as_str());
+		}
+	}
+}
+
+#[derive(Debug, Default)]
+struct EndpointCollector<'tcx> {
+	mod_path: Vec<Ident>,
+	segments: Vec<EndpointSegment>,
+	variables: Vec<&'tcx FieldDef<'tcx>>,
+	inherited: Vec<&'tcx Item<'tcx>>,
+
+	endpoints: Vec<Endpoint<'tcx>>,
+}
+
+impl<'tcx> EndpointCollector<'tcx> {
+	fn collect(&mut self, items: &'tcx [Item<'tcx>]) {
+		let inherited = self.inherited.len();
+		self.inherited.extend(items.iter().filter(|item| {
+			matches!(
+				item.kind,
+				ItemKind::Query(_) | ItemKind::Headers(_) | ItemKind::Auth(Auth::Use { .. })
+			) && !item.is_shared_block()
+		}));
+
+		for item in items {
+			match &item.kind {
+				ItemKind::Scope(scope) => {
+					self.mod_path.push(item.ident);
+					self.collect(scope.items);
+					self.mod_path.pop();
+				}
+				ItemKind::Path(path) => {
+					let (segments, variables, inherited) = (
+						self.segments.len(),
+						self.variables.len(),
+						self.inherited.len(),
+					);
+
+					self.push_path_kind(&path.kind);
+					self.variables.extend(path.variables);
+					self.inherited.extend(path.items.iter().filter(|item| {
+						matches!(item.kind, ItemKind::Params(_)) && !item.is_shared_block()
+					}));
+					self.collect(path.items);
+
+					self.segments.truncate(segments);
+					self.variables.truncate(variables);
+					self.inherited.truncate(inherited);
+				}
+				ItemKind::Verb(verb) => self.endpoints.push(Endpoint {
+					mod_path: self.mod_path.clone(),
+					segments: self.segments.clone(),
+					variables: self.variables.clone(),
+					inherited: self.inherited.clone(),
+					verb,
+					item,
+				}),
+				_ => {}
+			}
+		}
+
+		self.inherited.truncate(inherited);
+	}
+
+	fn push_path_kind(&mut self, kind: &PathKind<'_>) {
+		match kind {
+			PathKind::Simple(ident) => self.segments.push(EndpointSegment::Literal(*ident)),
+			PathKind::Variable(ident) => self.segments.push(EndpointSegment::Variable(*ident)),
+			PathKind::Complex(kinds) => {
+				for kind in *kinds {
+					self.push_path_kind(kind);
+				}
+			}
+			PathKind::Current => {}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		tests::{META, assert_reports, with_hir},
+		types::{Res, TyKind},
+	};
+
+	#[test]
+	fn flatten_nested_paths() {
+		let (endpoints, errors) = with_hir(
+			r#"
+			meta { name "Test" version "1" }
+
+			scope pet {
+				path pet {
+					headers {
+						Authorization string
+					}
+
+					path . {
+						verb POST {}
+					}
+
+					path {petId}/photos {
+						params {
+							petId int
+						}
+
+						verb GET {
+							query {
+								limit int
+							}
+						}
+					}
+				}
+			}
+			"#,
+			|hir| {
+				hir.endpoints()
+					.iter()
+					.map(|endpoint| {
+						let fields = endpoint
+							.params()
+							.chain(endpoint.query())
+							.chain(endpoint.headers())
+							.map(|field| field.ident.to_string())
+							.collect::<Vec<_>>();
+
+						format!(
+							"{} {} {} [{}]",
+							endpoint.mod_path[0],
+							endpoint.path(),
+							endpoint.operation_id(),
+							fields.join(", ")
+						)
+					})
+					.collect::<Vec<_>>()
+			},
+		);
+
+		assert!(!errors);
+		assert_eq!(
+			endpoints,
+			[
+				"pet /pet postPet [Authorization]",
+				"pet /pet/{petId}/photos getPetByPetIdPhotos [petId, limit, Authorization]"
+			]
+		);
+	}
+
+	#[test]
+	fn inherit_scope_blocks() {
+		let (fields, errors) = with_hir(
+			r#"
+			meta { name "Test" version "1" }
+
+			headers {
+				X-Request-Id uuid
+			}
+
+			scope pet {
+				query {
+					locale string
+				}
+
+				path pets {
+					verb GET {}
+				}
+			}
+
+			path store {
+				verb GET {}
+			}
+			"#,
+			|hir| {
+				hir.endpoints()
+					.iter()
+					.map(|endpoint| {
+						endpoint
+							.query()
+							.chain(endpoint.headers())
+							.map(|field| field.ident.to_string())
+							.collect::<Vec<_>>()
+					})
+					.collect::<Vec<_>>()
+			},
+		);
+
+		assert!(!errors);
+		assert_eq!(
+			fields,
+			[vec!["locale", "X-Request-Id"], vec!["X-Request-Id"]]
+		);
+	}
+
+	#[test]
+	fn report_optional_path_params() {
+		let (optional, errors) = with_hir(
+			r#"
+			meta { name "Test" version "1" }
+
+			path {petId} {
+				params {
+					petId? int
+				}
+
+				verb GET {
+					query {
+						limit? int?
+					}
+				}
+			}
+			"#,
+			|hir| {
+				let endpoint = &hir.endpoints()[0];
+				endpoint
+					.params()
+					.chain(endpoint.query())
+					.map(|field| field.optional)
+					.collect::<Vec<_>>()
+			},
+		);
+
+		assert!(errors);
+		assert_eq!(optional, [true, true]);
+	}
+
+	#[test]
+	fn resolve_shared_blocks() {
+		let (endpoint, errors) = with_hir(
+			r#"
+			meta { name "Test" version "1" }
+
+			headers Authorized {
+				Authorization string
+			}
+
+			scope pet {
+				path {petId} {
+					params PetId;
+					headers Authorized;
+
+					verb GET {
+						query Paging;
+						code 404 NotFound;
+					}
+				}
+
+				params PetId {
+					petId int
+				}
+			}
+
+			query Paging {
+				limit? int
+			}
+
+			response NotFound {
+				headers {
+					X-Trace string
+				}
+			}
+			"#,
+			|hir| {
+				let endpoint = &hir.endpoints()[0];
+				let fields = endpoint
+					.params()
+					.chain(endpoint.query())
+					.chain(endpoint.headers())
+					.map(|field| field.ident.to_string())
+					.collect::<Vec<_>>();
+				let responses = endpoint
+					.responses()
+					.map(|(_, code)| {
+						let name = code
+							.def
+							.map(|def_id| hir.def(def_id).item.ident.to_string());
+						let headers = code.headers().map(|field| field.ident.to_string());
+						(name, headers.collect::<Vec<_>>())
+					})
+					.collect::<Vec<_>>();
+
+				(fields, responses)
+			},
+		);
+
+		assert!(!errors);
+		assert_eq!(endpoint.0, ["petId", "limit", "Authorization"]);
+		assert_eq!(
+			endpoint.1,
+			[(Some("NotFound".to_owned()), vec!["X-Trace".to_owned()])]
+		);
+	}
+
+	#[test]
+	fn report_unknown_blocks() {
+		let (codes, errors) = with_hir(
+			r#"
+			meta { name "Test" version "1" }
+
+			headers Authorized {
+				Authorization string
+			}
+
+			response NotFound {}
+
+			verb GET {
+				query Authorized;
+				code 404 NotFound;
+				code 410 Gone;
+			}
+			"#,
+			|hir| {
+				let endpoint = &hir.endpoints()[0];
+				let fields = endpoint.query().count();
+				let codes = endpoint.responses().map(|(_, code)| code.def.is_some());
+				(fields, codes.collect::<Vec<_>>())
+			},
+		);
+
+		assert!(errors);
+		assert_eq!(codes, (0, vec![true, false]));
+	}
+
+	#[test]
+	fn bind_path_params() {
+		let (params, errors) = with_hir(
+			r#"
+			meta { name "Test" version "1" }
+
+			path {owner}/pets {
+				path {petId} {
+					verb GET {
+						params {
+							petId int
+						}
+					}
+				}
+
+				params {
+					owner uuid
+				}
+			}
+
+			path {name} {
+				verb GET {}
+			}
+			"#,
+			|hir| {
+				hir.endpoints()
+					.iter()
+					.map(|endpoint| {
+						endpoint
+							.params()
+							.map(|field| {
+								let TyKind::Path(_, Res::PrimTy(prim)) = field.ty.kind else {
+									unreachable!()
+								};
+								format!("{} {}", field.ident, prim.symbol())
+							})
+							.collect::<Vec<_>>()
+					})
+					.collect::<Vec<_>>()
+			},
+		);
+
+		assert!(!errors);
+		assert_eq!(
+			params,
+			[vec!["owner uuid", "petId int"], vec!["name string"]]
+		);
+	}
+
+	#[test]
+	fn report_mismatched_path_params() {
+		assert_reports(
+			[
+				(
+					"path {id} { params { id int other int } }",
+					"UnusedPathParam",
+				),
+				(
+					"path {id} { params { id int } verb GET { params { id int } } }",
+					"DuplicatePathParam",
+				),
+				("path {id} { path {id} {} }", "DuplicatePathVariable"),
+				(
+					"path {id} { verb GET { query { id int } } }",
+					"MisplacedPathVariable",
+				),
+				(
+					"path {id} { headers { id string } }",
+					"MisplacedPathVariable",
+				),
+				(
+					"path a { params { id int } path {id} {} }",
+					"UnusedPathParam",
+				),
+			]
+			.map(|(src, code)| (format!("{META} {src}"), code)),
+		);
+	}
+
+	#[test]
+	fn report_duplicate_operations() {
+		assert_reports(
+			[
+				(
+					"scope pet { path pets { verb GET {} } } scope store { path pets { verb GET {} } }",
+					"DuplicateOperation",
+				),
+				(
+					"path pets { verb GET {} } path pets/. { verb GET {} }",
+					"DuplicateOperation",
+				),
+				(
+					"path pet-store { verb GET {} } path pet_store { verb GET {} }",
+					"DuplicateOperationId",
+				),
+			]
+			.map(|(paths, code)| (format!("{META} {paths}"), code)),
+		);
+
+		let ((), errors) = with_hir(
+			r#"
+			meta { name "Test" version "1" }
+
+			path pets {
+				verb GET {}
+				verb POST {}
+
+				path {petId} {
+					verb GET {}
+				}
+			}
+			"#,
+			|_| (),
+		);
+		assert!(!errors);
+	}
+}
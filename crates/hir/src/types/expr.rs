@@ -0,0 +1,350 @@
+use crate::types::{Attribute, Constraint, HirId, Res};
+use dapic_lexer::rich::LiteralKind;
+use dapic_session::{Ident, Span, Symbol};
+
+/// An expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expr<'tcx> {
+	pub kind: ExprKind<'tcx>,
+	pub id: HirId,
+	pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExprKind<'tcx> {
+	// -- Bases --
+	/// A literal (e.g., `1`, `"foo"`).
+	Literal(LiteralKind, Symbol),
+	/// A path (`path::to::model::Type`).
+	Path(&'tcx Path<'tcx>),
+	/// A template for status codes (e.g., `~2xx`), without the tilde.
+	Template(Symbol),
+
+	// -- Composables --
+	/// An array (`[a, b, c, d]`)
+	Array(&'tcx [Expr<'tcx>]),
+	/// Access of a named (e.g., `obj.foo`) field.
+	Field(&'tcx Expr<'tcx>, Ident),
+}
+
+impl Expr<'_> {
+	/// Value of a string literal, without the quotes.
+	#[must_use]
+	pub const fn str_literal(&self) -> Option<Symbol> {
+		match self.kind {
+			ExprKind::Literal(LiteralKind::Str, symbol) => Some(symbol),
+			_ => None,
+		}
+	}
+
+	/// Values of an array made only of string literals.
+	#[must_use]
+	pub fn str_array(&self) -> Option<Vec<Symbol>> {
+		match self.kind {
+			ExprKind::Array(exprs) => exprs.iter().map(Expr::str_literal).collect(),
+			_ => None,
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<'tcx> {
+	/// The segments in the path: the things separated by `::`.
+	pub segments: &'tcx [PathSegment],
+
+	pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathSegment {
+	pub ident: Ident,
+	pub id: HirId,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropertyDef<'tcx> {
+	pub attrs: &'tcx [Attribute<'tcx>],
+	pub ident: Ident,
+	pub expr: &'tcx Expr<'tcx>,
+
+	pub id: HirId,
+	pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ty<'tcx> {
+	pub kind: TyKind<'tcx>,
+	pub id: HirId,
+	pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TyKind<'tcx> {
+	/// The base type, either
+	/// - a path: `scope::Type`
+	/// - or a local type: `Type`
+	///
+	/// Along with what the path resolved to.
+	Path(&'tcx Path<'tcx>, Res),
+
+	/// An array of types: `[Type]`
+	Array(&'tcx Ty<'tcx>),
+
+	/// A tuple of types: `(Ty1, Ty2, Ty3)`
+	/// Can also define the unit type: `()`
+	Tuple(&'tcx [Ty<'tcx>]),
+
+	/// A model defined inlined
+	/// e.g. `{ error string }`
+	InlineModel(&'tcx [FieldDef<'tcx>]),
+
+	/// An object with arbitrary keys, keys are strings or enums
+	/// e.g. `{[string]: int}`
+	Map(&'tcx Ty<'tcx>, &'tcx Ty<'tcx>),
+
+	/// A type that also accepts `null`
+	/// e.g. `string?` or `string | null`
+	Nullable(&'tcx Ty<'tcx>),
+
+	/// A value of any of the types
+	/// e.g. `Pet | Error`
+	Union(&'tcx [Ty<'tcx>]),
+}
+
+impl<'tcx> Ty<'tcx> {
+	/// The type without its nullability, e.g. `string` for `string?`.
+	#[must_use]
+	pub const fn non_null(&'tcx self) -> &'tcx Self {
+		match self.kind {
+			TyKind::Nullable(ty) => ty,
+			_ => self,
+		}
+	}
+}
+
+/// Field definition in a model, a block of parameters or an inline model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDef<'tcx> {
+	pub attrs: &'tcx [Attribute<'tcx>],
+	pub ident: Ident,
+	/// Whether the field may be absent, e.g. `name? string`
+	pub optional: bool,
+	pub ty: &'tcx Ty<'tcx>,
+	/// Value used when the field is absent, e.g. `limit int = 20`
+	pub default: Option<&'tcx Expr<'tcx>>,
+	/// Value of the `@example` attribute
+	pub example: Option<&'tcx Expr<'tcx>>,
+	/// Validation rules of the constraint attributes, checked against `ty`.
+	pub constraints: &'tcx [Constraint],
+
+	pub id: HirId,
+	pub span: Span,
+}
+
+impl FieldDef<'_> {
+	/// Whether the field must be given, fields with a default can be omitted.
+	#[must_use]
+	pub const fn required(&self) -> bool {
+		!self.optional && self.default.is_none()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		tests::{META, assert_reports, with_hir},
+		types::{Constraint, DefKind, ItemKind, Res, Ty, TyKind},
+	};
+
+	#[test]
+	fn lower_defaults_and_examples() {
+		let (values, errors) = with_hir(
+			r#"
+			meta { name "Test" version "1" }
+
+			enum Status {
+				Available "available"
+				Sold "sold"
+			}
+
+			model Query {
+				limit int = 20
+				ratio double? = 0.5
+				status Status = "sold"
+
+				@@example(["dog" "cat"])
+				tags [string] = []
+			}
+			"#,
+			|hir| {
+				hir.defs()
+					.flat_map(|(_, def)| match &def.item.kind {
+						ItemKind::Model(model) => model.fields,
+						_ => &[],
+					})
+					.map(|field| (field.default.is_some(), field.example.is_some()))
+					.collect::<Vec<_>>()
+			},
+		);
+
+		assert!(!errors);
+		assert_eq!(
+			values,
+			[(true, false), (true, false), (true, false), (true, true)]
+		);
+	}
+
+	#[test]
+	fn lower_unions() {
+		let (kinds, errors) = with_hir(
+			r#"
+			meta { name "Test" version "1" }
+
+			model Cat { petType string }
+			model Dog { petType string }
+
+			union Pet on petType {
+				cat Cat
+				dog Dog
+			}
+
+			model Owner {
+				pet Cat | Dog
+				nick string | null
+				either Cat | Dog | null
+			}
+			"#,
+			|hir| {
+				hir.defs()
+					.flat_map(|(_, def)| match &def.item.kind {
+						ItemKind::Model(model) => model.fields,
+						_ => &[],
+					})
+					.filter(|field| field.ident.symbol.as_str() != "petType")
+					.map(|field| match field.ty.kind {
+						TyKind::Union(tys) => format!("union of {}", tys.len()),
+						TyKind::Nullable(Ty {
+							kind: TyKind::Union(tys),
+							..
+						}) => format!("nullable union of {}", tys.len()),
+						TyKind::Nullable(_) => "nullable".to_owned(),
+						_ => "other".to_owned(),
+					})
+					.collect::<Vec<_>>()
+			},
+		);
+
+		assert!(!errors);
+		assert_eq!(kinds, ["union of 2", "nullable", "nullable union of 2"]);
+	}
+
+	#[test]
+	fn report_invalid_unions() {
+		assert_reports(
+			[
+				("cat Cat dog string", "InvalidUnionVariant"),
+				("cat Cat bird Bird", "MissingDiscriminator"),
+				("cat Cat dog? Dog", "InvalidUnionVariant"),
+				("cat Cat fish Fish", "InvalidDiscriminator"),
+			]
+			.map(|(variants, code)| {
+				let src = format!(
+					"{META} model Cat {{ petType string }} model Dog {{ petType string }} \
+					model Bird {{ wings int }} model Fish {{ petType? string }} \
+					union Pet on petType {{ {variants} }}"
+				);
+				(src, code)
+			}),
+		);
+	}
+
+	#[test]
+	fn report_invalid_map_keys() {
+		let src = |key| {
+			format!(
+				r#"{META} enum Status {{ Sold "sold" }} model Pet {{ counts {{[{key}]: int}} }}"#
+			)
+		};
+
+		for key in ["string", "Status"] {
+			let ((), errors) = with_hir(&src(key), |_| ());
+			assert!(!errors, "{key}");
+		}
+		assert_reports(
+			[
+				("int", "InvalidMapKey"),
+				("[string]", "InvalidMapKey"),
+				("Pet", "InvalidMapKey"),
+				("string?", "InvalidMapKey"),
+			]
+			.map(|(key, code)| (src(key), code)),
+		);
+	}
+
+	#[test]
+	fn lower_type_aliases() {
+		let (aliases, errors) = with_hir(
+			r#"
+			meta { name "Test" version "1" }
+
+			type UserId = string |@format: "uuid" @maxLength: 36|
+
+			model User {
+				id UserId = "0"
+			}
+			"#,
+			|hir| {
+				hir.defs()
+					.filter_map(|(_, def)| match &def.item.kind {
+						ItemKind::TypeAlias(alias) => Some(alias.constraints.to_vec()),
+						ItemKind::Model(model) => match model.fields[0].ty.kind {
+							TyKind::Path(_, Res::Def(DefKind::TypeAlias, _)) => None,
+							_ => Some(vec![]),
+						},
+						_ => None,
+					})
+					.collect::<Vec<_>>()
+			},
+		);
+
+		assert!(!errors);
+		assert_eq!(aliases, [vec![Constraint::MaxLength(36)]]);
+	}
+
+	#[test]
+	fn report_invalid_type_aliases() {
+		assert_reports(
+			[
+				("type A = A", "RecursiveTypeAlias"),
+				("type A = [B] type B = A | string", "RecursiveTypeAlias"),
+				("type A = { a A }", "RecursiveTypeAlias"),
+				(r#"type A = int |@format: "uuid"|"#, "InvalidFormat"),
+				(r"type A = int model M { a A = true }", "MismatchedValue"),
+			]
+			.map(|(aliases, code)| (format!("{META} {aliases}"), code)),
+		);
+	}
+
+	#[test]
+	fn report_mismatched_values() {
+		assert_reports(
+			[
+				(r#"limit int = "20""#, "MismatchedValue"),
+				(r"limit int = 2.5", "MismatchedValue"),
+				(r"enabled bool = 1", "MismatchedValue"),
+				(r#"status Status = "lost""#, "MismatchedValue"),
+				(r"tags [string] = [1]", "MismatchedValue"),
+				(r"pet Pet = 1", "MismatchedValue"),
+				(r"id int | string = true", "MismatchedValue"),
+				(r"@@example(1 2) limit int", "InvalidExample"),
+				(r#"@@example: "dog" tags [string]"#, "MismatchedValue"),
+			]
+			.map(|(field, code)| {
+				(
+					format!(r#"{META} enum Status {{ Sold "sold" }} model Pet {{ {field} }}"#),
+					code,
+				)
+			}),
+		);
+	}
+}
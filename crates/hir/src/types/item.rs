@@ -0,0 +1,399 @@
+use crate::types::{
+	Attribute, Auth, Constraint, DefId, DefKind, Definition, Expr, FieldDef, HirId, MediaType,
+	PropertyDef, Res, Status, Ty, TyKind,
+};
+use dapic_session::{Ident, IndexVec, PrimTy, Span, symbols::kw};
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Item<'tcx> {
+	pub attrs: &'tcx [Attribute<'tcx>],
+	pub kind: ItemKind<'tcx>,
+	pub ident: Ident,
+
+	pub id: HirId,
+	pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ItemKind<'tcx> {
+	Meta(Metadata<'tcx>),
+
+	Auth(Auth<'tcx>),
+	Scope(Scope<'tcx>),
+	Path(PathItem<'tcx>),
+	Model(Model<'tcx>),
+	Enum(Enum<'tcx>),
+	Union(Union<'tcx>),
+	TypeAlias(TypeAlias<'tcx>),
+	Query(Query<'tcx>),
+	Headers(Headers<'tcx>),
+	Verb(Verb<'tcx>),
+	StatusCode(StatusCode<'tcx>),
+	Body(Body<'tcx>),
+	Params(Params<'tcx>),
+	Response(Response<'tcx>),
+}
+
+impl Item<'_> {
+	/// Whether the item is a named `headers`, `query`, `params` or `response`
+	/// block, which only applies where it is used.
+	#[must_use]
+	pub fn is_shared_block(&self) -> bool {
+		match &self.kind {
+			ItemKind::Headers(Headers { def: None, .. })
+			| ItemKind::Query(Query { def: None, .. })
+			| ItemKind::Params(Params { def: None, .. }) => self.ident.symbol != kw::Empty,
+			ItemKind::Response(_) => true,
+			_ => false,
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Body<'tcx> {
+	pub ty: &'tcx Ty<'tcx>,
+	/// Media types of the `@type` attributes, `json` when there is none.
+	pub media_types: &'tcx [MediaType],
+	/// Validation rules of the constraint attributes, checked against `ty`.
+	pub constraints: &'tcx [Constraint],
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params<'tcx> {
+	pub properties: &'tcx [FieldDef<'tcx>],
+	/// Shared block the properties come from, for `params Name;`
+	pub def: Option<DefId>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verb<'tcx> {
+	pub method: Ident,
+	pub items: &'tcx [Item<'tcx>],
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusCode<'tcx> {
+	pub code: &'tcx Expr<'tcx>,
+	/// Checked value of `code`.
+	pub status: Status,
+	pub items: &'tcx [Item<'tcx>],
+	/// Shared response the items come from, for `code 404 Name;`
+	pub def: Option<DefId>,
+}
+
+/// Contains information like name, description, licence or base server urls.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Metadata<'tcx> {
+	pub fields: &'tcx [PropertyDef<'tcx>],
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Headers<'tcx> {
+	pub headers: &'tcx [FieldDef<'tcx>],
+	/// Shared block the headers come from, for `headers Name;`
+	pub def: Option<DefId>,
+}
+
+/// `response Name { <items> }`, a response shared by several verbs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response<'tcx> {
+	pub items: &'tcx [Item<'tcx>],
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Model<'tcx> {
+	/// Type parameters, e.g. `T` in `model Page<T>`. Generic models are only
+	/// templates: each instantiation is lowered to a model of its own, named
+	/// after its arguments (e.g. `PageOfPet`).
+	pub generics: &'tcx [Ident],
+	/// Models whose fields are inherited, e.g. `model Pet : Base`
+	pub bases: &'tcx [Ty<'tcx>],
+	pub fields: &'tcx [FieldDef<'tcx>],
+}
+
+impl<'tcx> Model<'tcx> {
+	/// Inherited fields, in the order of the bases, followed by the fields of
+	/// the model. Fields with the name of a previous one are skipped, conflicts
+	/// were reported during lowering.
+	pub(crate) fn all_fields(
+		&self,
+		defs: &IndexVec<DefId, Definition<'tcx>>,
+	) -> Vec<&'tcx FieldDef<'tcx>> {
+		let mut fields = Vec::new();
+		self.collect_fields(defs, &mut HashSet::new(), &mut fields);
+		fields
+	}
+
+	fn collect_fields(
+		&self,
+		defs: &IndexVec<DefId, Definition<'tcx>>,
+		visited: &mut HashSet<DefId>,
+		fields: &mut Vec<&'tcx FieldDef<'tcx>>,
+	) {
+		for base in self.bases {
+			if let TyKind::Path(_, Res::Def(DefKind::Model, def_id)) = base.kind
+				&& visited.insert(def_id)
+				&& let ItemKind::Model(model) = &defs[def_id].item.kind
+			{
+				model.collect_fields(defs, visited, fields);
+			}
+		}
+
+		for field in self.fields {
+			if !fields
+				.iter()
+				.any(|previous| previous.ident.symbol == field.ident.symbol)
+			{
+				fields.push(field);
+			}
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Enum<'tcx> {
+	/// Type of the variant values, a string or integer primitive
+	pub ty: PrimTy,
+	/// Variants with their value, checked against `ty`
+	pub variants: &'tcx [PropertyDef<'tcx>],
+}
+
+/// A tagged union, every variant is a model with the discriminator field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Union<'tcx> {
+	pub discriminator: Ident,
+	/// The name of a variant is the value of its discriminator
+	pub variants: &'tcx [FieldDef<'tcx>],
+}
+
+/// A named type, generators emit it as its own schema unless it has the
+/// `@inline` attribute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeAlias<'tcx> {
+	pub ty: &'tcx Ty<'tcx>,
+	/// Validation rules of the constraint attributes, checked against `ty`.
+	pub constraints: &'tcx [Constraint],
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query<'tcx> {
+	pub fields: &'tcx [FieldDef<'tcx>],
+	/// Shared block the fields come from, for `query Name;`
+	pub def: Option<DefId>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathItem<'tcx> {
+	pub kind: PathKind<'tcx>,
+	pub items: &'tcx [Item<'tcx>],
+	/// A `string` path parameter for each variable of `kind`, used when no
+	/// `params` block defines the variable.
+	pub variables: &'tcx [FieldDef<'tcx>],
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathKind<'tcx> {
+	Simple(Ident),
+	Variable(Ident),
+	Complex(&'tcx [Self]),
+	Current,
+}
+
+/// A scope, external ones are already loaded at this point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scope<'tcx> {
+	pub items: &'tcx [Item<'tcx>],
+	/// Whether the scope was defined inline or in an external file.
+	pub inline: bool,
+	pub span: Span,
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		tests::{META, assert_reports, with_hir},
+		types::ItemKind,
+	};
+	use dapic_session::PrimTy;
+
+	#[test]
+	fn lower_model_bases() {
+		let (fields, errors) = with_hir(
+			r#"
+			meta { name "Test" version "1" }
+
+			model Base {
+				id string
+			}
+
+			model Timestamps : Base {
+				createdAt string
+			}
+
+			model Pet : Base, Timestamps {
+				name string
+			}
+			"#,
+			|hir| {
+				hir.defs()
+					.filter_map(|(_, def)| match &def.item.kind {
+						ItemKind::Model(model) if def.item.ident.symbol.as_str() == "Pet" => Some(
+							hir.all_fields(model)
+								.iter()
+								.map(|field| field.ident.to_string())
+								.collect::<Vec<_>>(),
+						),
+						_ => None,
+					})
+					.collect::<Vec<_>>()
+			},
+		);
+
+		assert!(!errors);
+		assert_eq!(fields, [["id", "createdAt", "name"]]);
+	}
+
+	#[test]
+	fn lower_generic_instances() {
+		let (models, errors) = with_hir(
+			r#"
+			meta { name "Test" version "1" }
+
+			model Pet {}
+
+			model Page<T> {
+				items [T]
+				total int
+			}
+
+			model Shelf {
+				pets Page<Pet>
+				more Page<Pet>
+				names Page<[string]>
+			}
+			"#,
+			|hir| {
+				hir.defs()
+					.filter_map(|(_, def)| match &def.item.kind {
+						ItemKind::Model(model) if model.generics.is_empty() => {
+							Some(def.item.ident.to_string())
+						}
+						_ => None,
+					})
+					.collect::<Vec<_>>()
+			},
+		);
+
+		assert!(!errors);
+		assert_eq!(models, ["Pet", "Shelf", "PageOfPet", "PageOfStringList"]);
+	}
+
+	#[test]
+	fn report_invalid_type_args() {
+		assert_reports(
+			[
+				("a Page", "WrongTypeArgCount"),
+				("a Page<int, int>", "WrongTypeArgCount"),
+				("a string<int>", "WrongTypeArgCount"),
+				("a Pet<int>", "WrongTypeArgCount"),
+				("a Nested<int>", "RecursiveInstantiation"),
+			]
+			.map(|(fields, code)| {
+				let src = format!(
+					"{META} model Pet {{}} model Page<T> {{ items [T] }} \
+					model Nested<T> {{ inner Nested<[T]> }} model Shelf {{ {fields} }}"
+				);
+				(src, code)
+			}),
+		);
+	}
+
+	#[test]
+	fn lower_typed_enums() {
+		let (tys, errors) = with_hir(
+			r#"
+			meta { name "Test" version "1" }
+
+			enum Status {
+				Sold "sold"
+			}
+
+			enum Code : int32 {
+				Ok 200
+				NotFound 404
+				Limit 1_000
+			}
+
+			model Response {
+				code Code = 404
+			}
+			"#,
+			|hir| {
+				hir.defs()
+					.filter_map(|(_, def)| match &def.item.kind {
+						ItemKind::Enum(enum_) => Some(enum_.ty),
+						_ => None,
+					})
+					.collect::<Vec<_>>()
+			},
+		);
+
+		assert!(!errors);
+		assert_eq!(tys, [PrimTy::String, PrimTy::Int32]);
+	}
+
+	#[test]
+	fn report_invalid_enums() {
+		assert_reports(
+			[
+				("enum A : bool { Yes true }", "InvalidEnumType"),
+				("enum A : Pet { }", "InvalidEnumType"),
+				("enum A : int { Ok \"ok\" }", "MismatchedValue"),
+				("enum A : int { Pi 3.14 }", "MismatchedValue"),
+				("enum A { Ok 200 }", "MismatchedValue"),
+				("enum A { Ok \"ok\" Ok \"fine\" }", "DuplicateVariant"),
+				("enum A { Ok \"ok\" Fine \"ok\" }", "DuplicateVariantValue"),
+				(
+					"enum A : int { Limit 1_000 Max 1000 }",
+					"DuplicateVariantValue",
+				),
+				(
+					"enum A : int { Ok 200 } model B { a A = 201 }",
+					"MismatchedValue",
+				),
+				(
+					"enum A : int { Ok 200 } union B on a { b Pet }",
+					"InvalidDiscriminator",
+				),
+			]
+			.map(|(enums, code)| (format!("{META} model Pet {{ a A }} {enums}"), code)),
+		);
+	}
+
+	#[test]
+	fn report_invalid_bases() {
+		assert_reports(
+			[
+				("model A : A {}", "RecursiveModel"),
+				("model A : B {} model B : A {}", "RecursiveModel"),
+				("model A : Status {}", "InvalidBase"),
+				("model A : string {}", "InvalidBase"),
+				(
+					"model A : B { id string } model B { id int }",
+					"ConflictingField",
+				),
+				(
+					"model A : B, C {} model B { id string } model C { id string }",
+					"ConflictingField",
+				),
+			]
+			.map(|(models, code)| {
+				(
+					format!(r#"{META} enum Status {{ Sold "sold" }} {models}"#),
+					code,
+				)
+			}),
+		);
+	}
+}
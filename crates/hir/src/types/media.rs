@@ -0,0 +1,183 @@
+use dapic_session::Symbol;
+use std::fmt;
+
+/// Media type of a `body`, set with `@type` attributes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MediaType {
+	/// `application/json`, used when a body has no `@type`
+	Json,
+	/// `application/x-www-form-urlencoded`
+	Form,
+	/// `multipart/form-data`
+	Multipart,
+	/// `text/plain`
+	Text,
+	/// `application/octet-stream`
+	Binary,
+	/// Any other MIME type, e.g. `application/xml`
+	Mime(Symbol),
+}
+
+/// Top-level types of the IANA media types registry.
+const TOP_LEVEL_TYPES: &[&str] = &[
+	"application",
+	"audio",
+	"example",
+	"font",
+	"haptics",
+	"image",
+	"message",
+	"model",
+	"multipart",
+	"text",
+	"video",
+];
+
+impl MediaType {
+	pub const SHORTHANDS: &[Self] = &[
+		Self::Json,
+		Self::Form,
+		Self::Multipart,
+		Self::Text,
+		Self::Binary,
+	];
+
+	/// Parses a shorthand (e.g. `json`) or a full MIME type (e.g.
+	/// `application/xml`), MIME types of a shorthand are normalized to it.
+	#[must_use]
+	pub fn parse(value: Symbol) -> Option<Self> {
+		let s = value.as_str();
+
+		if let Some(shorthand) = Self::SHORTHANDS
+			.iter()
+			.find(|shorthand| shorthand.shorthand() == Some(s) || shorthand.mime() == s)
+		{
+			return Some(*shorthand);
+		}
+
+		// Parameters (e.g. `; charset=utf-8`) are kept but not checked
+		let essence = s.split(';').next().unwrap_or_default().trim();
+		let (ty, subtype) = essence.split_once('/')?;
+
+		let valid_subtype = !subtype.is_empty()
+			&& subtype
+				.chars()
+				.all(|c| c.is_ascii_alphanumeric() || "!#$&-^_.+".contains(c));
+
+		(TOP_LEVEL_TYPES.contains(&ty.to_ascii_lowercase().as_str()) && valid_subtype)
+			.then_some(Self::Mime(value))
+	}
+
+	#[must_use]
+	pub const fn shorthand(self) -> Option<&'static str> {
+		match self {
+			Self::Json => Some("json"),
+			Self::Form => Some("form"),
+			Self::Multipart => Some("multipart"),
+			Self::Text => Some("text"),
+			Self::Binary => Some("binary"),
+			Self::Mime(_) => None,
+		}
+	}
+
+	#[must_use]
+	pub fn mime(&self) -> &str {
+		match self {
+			Self::Json => "application/json",
+			Self::Form => "application/x-www-form-urlencoded",
+			Self::Multipart => "multipart/form-data",
+			Self::Text => "text/plain",
+			Self::Binary => "application/octet-stream",
+			Self::Mime(mime) => mime.as_str(),
+		}
+	}
+}
+
+impl fmt::Display for MediaType {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.mime())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		tests::{META, assert_reports, with_hir},
+		types::MediaType,
+	};
+	use dapic_session::sym;
+
+	#[test]
+	fn parse_media_types() {
+		assert_eq!(MediaType::parse(sym!("form")), Some(MediaType::Form));
+		assert_eq!(
+			MediaType::parse(sym!("application/json")),
+			Some(MediaType::Json)
+		);
+		assert_eq!(
+			MediaType::parse(sym!("text/csv; charset=utf-8")),
+			Some(MediaType::Mime(sym!("text/csv; charset=utf-8")))
+		);
+		assert_eq!(MediaType::parse(sym!("jsn")), None);
+		assert_eq!(MediaType::parse(sym!("foo/bar")), None);
+		assert_eq!(MediaType::parse(sym!("image/")), None);
+	}
+
+	#[test]
+	fn lower_body_media_types() {
+		let (media_types, errors) = with_hir(
+			r#"
+			meta { name "Test" version "1" }
+
+			path pet {
+				verb GET {
+					code 200 {
+						body string
+
+						@@type: ["text" "application/xml"]
+						body string
+					}
+				}
+			}
+			"#,
+			|hir| {
+				hir.endpoints()[0]
+					.responses()
+					.flat_map(|(_, code)| code.bodies())
+					.flat_map(|(_, body)| body.media_types)
+					.map(ToString::to_string)
+					.collect::<Vec<_>>()
+			},
+		);
+
+		assert!(!errors);
+		assert_eq!(
+			media_types,
+			["application/json", "text/plain", "application/xml"]
+		);
+	}
+
+	#[test]
+	fn report_invalid_media_types() {
+		assert_reports(
+			[
+				(r#"@@type: "jsn" body string"#, "UnknownMediaType"),
+				(r"@@type: 1 body string", "InvalidMediaType"),
+				(
+					r#"body string @@type: "application/json" body int"#,
+					"DuplicateMediaType",
+				),
+				(
+					r#"@@type: ["text" "text/plain"] body string"#,
+					"DuplicateMediaType",
+				),
+			]
+			.map(|(bodies, code)| {
+				(
+					format!("{META} path pet {{ verb GET {{ code 200 {{ {bodies} }} }} }}"),
+					code,
+				)
+			}),
+		);
+	}
+}
@@ -0,0 +1,183 @@
+use crate::types::{ItemKind, Root};
+use dapic_session::{
+	Symbol,
+	symbols::{attrs, meta},
+};
+use std::fmt;
+
+/// A property accepted in the `meta` block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetaKey {
+	/// Name of the api
+	Name,
+	Description,
+	Version,
+	/// Name or SPDX identifier of the license
+	License,
+	/// e.g. `Jane Doe <jane@example.com>`
+	Contact,
+	/// Base urls the api is served from
+	Urls,
+}
+
+/// Typed view of the root `meta` block.
+///
+/// Properties with an invalid value were reported during lowering and are
+/// left empty.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Meta {
+	pub name: Option<Symbol>,
+	pub description: Option<Symbol>,
+	pub version: Option<Symbol>,
+	pub license: Option<Symbol>,
+	pub contact: Option<Symbol>,
+	pub urls: Vec<Symbol>,
+}
+
+impl MetaKey {
+	pub const ALL: &[Self] = &[
+		Self::Name,
+		Self::Description,
+		Self::Version,
+		Self::License,
+		Self::Contact,
+		Self::Urls,
+	];
+
+	#[must_use]
+	pub fn from_symbol(symbol: Symbol) -> Option<Self> {
+		Self::ALL.iter().copied().find(|key| key.symbol() == symbol)
+	}
+
+	#[must_use]
+	pub const fn symbol(self) -> Symbol {
+		match self {
+			Self::Name => meta::name,
+			Self::Description => attrs::description,
+			Self::Version => meta::version,
+			Self::License => meta::license,
+			Self::Contact => meta::contact,
+			Self::Urls => meta::urls,
+		}
+	}
+
+	/// Kind of value expected by the property, for diagnostics.
+	#[must_use]
+	pub const fn expected(self) -> &'static str {
+		match self {
+			Self::Urls => "an array of strings",
+			_ => "a string",
+		}
+	}
+}
+
+impl fmt::Display for MetaKey {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.symbol())
+	}
+}
+
+impl Root<'_> {
+	/// Properties of the `meta` blocks declared at the root of the api.
+	#[must_use]
+	pub fn meta(&self) -> Meta {
+		let mut meta = Meta::default();
+
+		let fields = self.items.iter().flat_map(|item| match &item.kind {
+			ItemKind::Meta(metadata) => metadata.fields,
+			_ => &[],
+		});
+
+		for field in fields {
+			let Some(key) = MetaKey::from_symbol(field.ident.symbol) else {
+				continue;
+			};
+
+			let slot = match key {
+				MetaKey::Name => &mut meta.name,
+				MetaKey::Description => &mut meta.description,
+				MetaKey::Version => &mut meta.version,
+				MetaKey::License => &mut meta.license,
+				MetaKey::Contact => &mut meta.contact,
+				MetaKey::Urls => {
+					meta.urls = field.expr.str_array().unwrap_or_default();
+					continue;
+				}
+			};
+			*slot = field.expr.str_literal();
+		}
+
+		meta
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::tests::{assert_reports, with_hir};
+	use dapic_session::sym;
+
+	#[test]
+	fn collect_meta() {
+		let (meta, errors) = with_hir(
+			r#"
+			meta {
+				name "Petstore"
+				version "1.0.0"
+				license "MIT"
+				urls ["https://a.example.com" "https://b.example.com"]
+			}
+			"#,
+			|hir| {
+				let meta = hir.meta();
+				(
+					meta.name,
+					meta.description,
+					meta.version,
+					meta.license,
+					meta.urls.len(),
+				)
+			},
+		);
+
+		assert!(!errors);
+		assert_eq!(
+			meta,
+			(
+				Some(sym!("Petstore")),
+				None,
+				Some(sym!("1.0.0")),
+				Some(sym!("MIT")),
+				2
+			)
+		);
+	}
+
+	#[test]
+	fn report_invalid_meta() {
+		let urls = r#"meta { name "a" version "1" urls "https://a.example.com" }"#;
+		assert_reports([
+			(
+				r#"meta { name "a" version "1" nmae "Petstore" }"#,
+				"UnknownMetaKey",
+			),
+			(
+				r#"meta { name "a" name "b" version "1" }"#,
+				"DuplicateProperty",
+			),
+			(urls, "InvalidMetaValue"),
+			(r#"meta { name "a" version 1 }"#, "InvalidMetaValue"),
+		]);
+
+		let (urls, _) = with_hir(urls, |hir| hir.meta().urls);
+		assert_eq!(urls, []);
+	}
+
+	#[test]
+	fn report_missing_meta() {
+		assert_reports([
+			("meta {}", "MissingMetaProperty"),
+			(r#"meta { name "Petstore" }"#, "MissingMetaProperty"),
+			(r#"meta { version "1.0.0" }"#, "MissingMetaProperty"),
+		]);
+	}
+}
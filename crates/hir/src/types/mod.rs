@@ -0,0 +1,118 @@
+use dapic_session::{Ident, IndexVec, Span, new_index_ty};
+
+mod attr;
+mod auth;
+mod constraint;
+mod endpoint;
+mod expr;
+mod item;
+mod media;
+mod meta;
+mod res;
+mod status;
+
+pub use attr::*;
+pub use auth::*;
+pub use constraint::*;
+pub use endpoint::*;
+pub use expr::*;
+pub use item::*;
+pub use media::*;
+pub use meta::*;
+pub use res::*;
+pub use status::*;
+
+new_index_ty! {
+	/// Identifies a HIR node.
+	///
+	/// Every lowered node receives a fresh one, they are unique across the whole api.
+	pub struct HirId;
+}
+
+new_index_ty! {
+	pub struct PathId;
+}
+
+new_index_ty! {
+	/// Identifies a definition that can be referred to by its name (e.g. a `model`).
+	pub struct DefId;
+}
+
+// TODO: openapi lib with spec types as defined in the OAS
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Root<'tcx> {
+	pub attrs: &'tcx [Attribute<'tcx>],
+	pub(crate) items: &'tcx [Item<'tcx>],
+	pub(crate) defs: IndexVec<DefId, Definition<'tcx>>,
+
+	pub id: HirId,
+	pub span: Span,
+}
+
+/// An item that can be referred to by name from other items.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Definition<'tcx> {
+	pub kind: DefKind,
+	/// Scopes the definition is nested in, starting with the api root.
+	pub mod_path: &'tcx [Ident],
+	pub item: &'tcx Item<'tcx>,
+}
+
+impl<'tcx> Root<'tcx> {
+	/// Items declared at the root of the api, nested ones are reachable
+	/// through `scope`, `path`, `verb` and `code` items.
+	#[must_use]
+	pub const fn items(&self) -> &'tcx [Item<'tcx>] {
+		self.items
+	}
+
+	#[must_use]
+	pub fn def(&self, def_id: DefId) -> &Definition<'tcx> {
+		&self.defs[def_id]
+	}
+
+	/// Every definition of the api, in declaration order.
+	pub fn defs(&self) -> impl Iterator<Item = (DefId, &Definition<'tcx>)> {
+		self.defs.iter_enumerated()
+	}
+
+	/// Fields of a model along with the ones of its bases, for generators
+	/// flattening models.
+	#[must_use]
+	pub fn all_fields(&self, model: &Model<'tcx>) -> Vec<&'tcx FieldDef<'tcx>> {
+		model.all_fields(&self.defs)
+	}
+}
+
+impl<'tcx> Definition<'tcx> {
+	/// Whether the definition is a type of its own, the ones generators
+	/// declare: models, enums, unions and type aliases.
+	#[must_use]
+	pub const fn is_concrete_type(&self) -> bool {
+		match &self.item.kind {
+			// Only the instances of generic models are used
+			ItemKind::Model(model) => model.generics.is_empty(),
+			ItemKind::Enum(_) | ItemKind::Union(_) | ItemKind::TypeAlias(_) => true,
+			_ => false,
+		}
+	}
+
+	/// Scopes the definition is declared in, without the api root.
+	#[must_use]
+	pub fn scopes(&self) -> &'tcx [Ident] {
+		&self.mod_path[1..]
+	}
+
+	/// Name of the definition prefixed with its scopes (e.g. `pet.Status`), so
+	/// that two scopes can define the same name.
+	#[must_use]
+	pub fn qualified_name(&self) -> String {
+		self.scopes()
+			.iter()
+			.chain([&self.item.ident])
+			.map(|ident| ident.symbol.as_str())
+			.collect::<Vec<_>>()
+			.join(".")
+	}
+}
@@ -0,0 +1,55 @@
+use crate::types::DefId;
+use dapic_session::PrimTy;
+use std::fmt;
+
+/// What kind of item a [`DefId`] points to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DefKind {
+	Model,
+	Enum,
+	/// A tagged union of models, `union Name on field { ... }`
+	Union,
+	/// `type Name = <ty>`
+	TypeAlias,
+	/// A security scheme, `auth Name { ... }`
+	Auth,
+	/// `headers Name { ... }`, used with `headers Name;`
+	Headers,
+	/// `query Name { ... }`, used with `query Name;`
+	Query,
+	/// `params Name { ... }`, used with `params Name;`
+	Params,
+	/// `response Name { ... }`, used with `code 404 Name;`
+	Response,
+}
+
+impl fmt::Display for DefKind {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Model => write!(f, "model"),
+			Self::Enum => write!(f, "enum"),
+			Self::Union => write!(f, "union"),
+			Self::TypeAlias => write!(f, "type alias"),
+			Self::Auth => write!(f, "auth scheme"),
+			Self::Headers => write!(f, "headers block"),
+			Self::Query => write!(f, "query block"),
+			Self::Params => write!(f, "params block"),
+			Self::Response => write!(f, "response"),
+		}
+	}
+}
+
+/// The resolution of a path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Res {
+	/// A user definition.
+	Def(DefKind, DefId),
+	/// A built-in type, always preferred over user definitions with the same name.
+	PrimTy(PrimTy),
+	/// A type parameter of a generic model, only left in the generic model
+	/// itself as its instances substitute their arguments.
+	TyParam,
+
+	/// The path could not be resolved, an error was already reported.
+	Err,
+}
@@ -0,0 +1,215 @@
+use std::fmt;
+
+/// Responses a `code` item applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Status {
+	/// e.g. `404` or `NOT_FOUND`
+	Code(u16),
+	/// e.g. `~2xx`, holds the class of the codes (`2`)
+	Range(u8),
+	/// Every code not covered by the other `code` items of the verb.
+	Default,
+	/// The code was invalid, this was reported during lowering.
+	Err,
+}
+
+impl Status {
+	/// Reason phrase registered for the code, used when a response has no
+	/// documentation.
+	#[must_use]
+	pub fn reason(self) -> Option<&'static str> {
+		match self {
+			Self::Code(code) => STATUS_CODES
+				.iter()
+				.find(|(known, _, _)| *known == code)
+				.map(|(_, _, reason)| *reason),
+			Self::Range(class) => Some(match class {
+				1 => "Informational",
+				2 => "Success",
+				3 => "Redirection",
+				4 => "Client Error",
+				_ => "Server Error",
+			}),
+			Self::Default => Some("Unexpected response"),
+			Self::Err => None,
+		}
+	}
+
+	/// Code of a status name from the IANA registry, e.g. `IM_A_TEAPOT`.
+	#[must_use]
+	pub fn code_of(name: &str) -> Option<u16> {
+		STATUS_CODES
+			.iter()
+			.find(|(_, known, _)| *known == name)
+			.map(|(code, _, _)| *code)
+	}
+}
+
+impl fmt::Display for Status {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Code(code) => write!(f, "{code}"),
+			Self::Range(class) => write!(f, "{class}XX"),
+			Self::Default => write!(f, "default"),
+			Self::Err => write!(f, "<error>"),
+		}
+	}
+}
+
+/// Status codes of the IANA HTTP status code registry, with the name they can
+/// be referred to by and their reason phrase.
+///
+/// Some codes are listed twice to accept both their current and former names.
+pub const STATUS_CODES: &[(u16, &str, &str)] = &[
+	(100, "CONTINUE", "Continue"),
+	(101, "SWITCHING_PROTOCOLS", "Switching Protocols"),
+	(102, "PROCESSING", "Processing"),
+	(103, "EARLY_HINTS", "Early Hints"),
+	(200, "OK", "OK"),
+	(201, "CREATED", "Created"),
+	(202, "ACCEPTED", "Accepted"),
+	(
+		203,
+		"NON_AUTHORITATIVE_INFORMATION",
+		"Non-Authoritative Information",
+	),
+	(204, "NO_CONTENT", "No Content"),
+	(205, "RESET_CONTENT", "Reset Content"),
+	(206, "PARTIAL_CONTENT", "Partial Content"),
+	(207, "MULTI_STATUS", "Multi-Status"),
+	(208, "ALREADY_REPORTED", "Already Reported"),
+	(226, "IM_USED", "IM Used"),
+	(300, "MULTIPLE_CHOICES", "Multiple Choices"),
+	(301, "MOVED_PERMANENTLY", "Moved Permanently"),
+	(302, "FOUND", "Found"),
+	(303, "SEE_OTHER", "See Other"),
+	(304, "NOT_MODIFIED", "Not Modified"),
+	(305, "USE_PROXY", "Use Proxy"),
+	(307, "TEMPORARY_REDIRECT", "Temporary Redirect"),
+	(308, "PERMANENT_REDIRECT", "Permanent Redirect"),
+	(400, "BAD_REQUEST", "Bad Request"),
+	(401, "UNAUTHORIZED", "Unauthorized"),
+	(402, "PAYMENT_REQUIRED", "Payment Required"),
+	(403, "FORBIDDEN", "Forbidden"),
+	(404, "NOT_FOUND", "Not Found"),
+	(405, "METHOD_NOT_ALLOWED", "Method Not Allowed"),
+	(406, "NOT_ACCEPTABLE", "Not Acceptable"),
+	(
+		407,
+		"PROXY_AUTHENTICATION_REQUIRED",
+		"Proxy Authentication Required",
+	),
+	(408, "REQUEST_TIMEOUT", "Request Timeout"),
+	(409, "CONFLICT", "Conflict"),
+	(410, "GONE", "Gone"),
+	(411, "LENGTH_REQUIRED", "Length Required"),
+	(412, "PRECONDITION_FAILED", "Precondition Failed"),
+	(413, "CONTENT_TOO_LARGE", "Content Too Large"),
+	(413, "PAYLOAD_TOO_LARGE", "Content Too Large"),
+	(414, "URI_TOO_LONG", "URI Too Long"),
+	(415, "UNSUPPORTED_MEDIA_TYPE", "Unsupported Media Type"),
+	(416, "RANGE_NOT_SATISFIABLE", "Range Not Satisfiable"),
+	(417, "EXPECTATION_FAILED", "Expectation Failed"),
+	(418, "IM_A_TEAPOT", "I'm a teapot"),
+	(421, "MISDIRECTED_REQUEST", "Misdirected Request"),
+	(422, "UNPROCESSABLE_CONTENT", "Unprocessable Content"),
+	(422, "UNPROCESSABLE_ENTITY", "Unprocessable Content"),
+	(423, "LOCKED", "Locked"),
+	(424, "FAILED_DEPENDENCY", "Failed Dependency"),
+	(425, "TOO_EARLY", "Too Early"),
+	(426, "UPGRADE_REQUIRED", "Upgrade Required"),
+	(428, "PRECONDITION_REQUIRED", "Precondition Required"),
+	(429, "TOO_MANY_REQUESTS", "Too Many Requests"),
+	(
+		431,
+		"REQUEST_HEADER_FIELDS_TOO_LARGE",
+		"Request Header Fields Too Large",
+	),
+	(
+		451,
+		"UNAVAILABLE_FOR_LEGAL_REASONS",
+		"Unavailable For Legal Reasons",
+	),
+	(500, "INTERNAL_SERVER_ERROR", "Internal Server Error"),
+	(501, "NOT_IMPLEMENTED", "Not Implemented"),
+	(502, "BAD_GATEWAY", "Bad Gateway"),
+	(503, "SERVICE_UNAVAILABLE", "Service Unavailable"),
+	(504, "GATEWAY_TIMEOUT", "Gateway Timeout"),
+	(
+		505,
+		"HTTP_VERSION_NOT_SUPPORTED",
+		"HTTP Version Not Supported",
+	),
+	(506, "VARIANT_ALSO_NEGOTIATES", "Variant Also Negotiates"),
+	(507, "INSUFFICIENT_STORAGE", "Insufficient Storage"),
+	(508, "LOOP_DETECTED", "Loop Detected"),
+	(
+		511,
+		"NETWORK_AUTHENTICATION_REQUIRED",
+		"Network Authentication Required",
+	),
+];
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		tests::{META, assert_reports, with_hir},
+		types::Status,
+	};
+
+	#[test]
+	fn lower_status_codes() {
+		let (statuses, errors) = with_hir(
+			r#"
+			meta { name "Test" version "1" }
+
+			path pet {
+				verb GET {
+					code 200 {}
+					code IM_A_TEAPOT {}
+					code ~4xx {}
+					code default {}
+				}
+			}
+			"#,
+			|hir| {
+				hir.endpoints()[0]
+					.responses()
+					.map(|(_, code)| code.status.to_string())
+					.collect::<Vec<_>>()
+			},
+		);
+
+		assert!(!errors);
+		assert_eq!(statuses, ["200", "418", "4XX", "default"]);
+	}
+
+	#[test]
+	fn report_invalid_status_codes() {
+		assert_reports(
+			[
+				("code 999 {}", "InvalidStatusCode"),
+				("code ~6xx {}", "InvalidStatusRange"),
+				("code ~2yy {}", "InvalidStatusRange"),
+				("code NOT_A_STATUS {}", "UnknownStatusName"),
+				(r#"code "200" {}"#, "ExpectedStatusCode"),
+				("code 200 {} code OK {}", "DuplicateStatusCode"),
+				("code default {} code default {}", "DuplicateStatusCode"),
+			]
+			.map(|(codes, code)| {
+				(
+					format!("{META} path pet {{ verb GET {{ {codes} }} }}"),
+					code,
+				)
+			}),
+		);
+	}
+
+	#[test]
+	fn reason_phrases() {
+		assert_eq!(Status::Code(418).reason(), Some("I'm a teapot"));
+		assert_eq!(Status::Range(5).reason(), Some("Server Error"));
+		assert_eq!(Status::code_of("UNPROCESSABLE_ENTITY"), Some(422));
+		assert_eq!(Status::Code(299).reason(), None);
+	}
+}
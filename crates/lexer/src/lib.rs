@@ -11,6 +11,8 @@ mod tests {
 
 	pub const ATTR: &str = "@format: date";
 
+	pub const NUMBERS: &str = "0 1_000 0.25 1.";
+
 	pub const URLS: &str = r#"urls [
 	"https://paradigm.lighton.ai/api/v1"
 	"https://paradigm-preprod.lighton.ai/api/v1"
@@ -37,7 +37,7 @@ impl<'a> Cursor<'a> {
 	}
 
 	/// Peeks the second symbol from the input stream without consuming it.
-	pub(super) fn _second(&self) -> char {
+	pub(super) fn second(&self) -> char {
 		let mut iter = self.chars.clone();
 		iter.next();
 		iter.next().unwrap_or(EOF_CHAR)
@@ -126,7 +126,7 @@ impl<'a> IntoIterator for Cursor<'a> {
 
 #[cfg(test)]
 mod tests {
-	use crate::tests::{ATTR, EXAMPLE, URLS};
+	use crate::tests::{ATTR, EXAMPLE, NUMBERS, URLS};
 
 	macro_rules! assert_tokenize {
 		($variant:literal, $src:ident) => {
@@ -145,4 +145,5 @@ mod tests {
 	assert_tokenize!("example", EXAMPLE);
 	assert_tokenize!("attribute", ATTR);
 	assert_tokenize!("urls", URLS);
+	assert_tokenize!("numbers", NUMBERS);
 }
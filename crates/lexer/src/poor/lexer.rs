@@ -159,6 +159,12 @@ impl Cursor<'_> {
 
 		self.eat_decimal_digits();
 
+		// Fractional part, a trailing `.` is not part of the number
+		if self.first() == '.' && self.second().is_ascii_digit() {
+			self.bump();
+			self.eat_decimal_digits();
+		}
+
 		Literal(Number)
 	}
 }
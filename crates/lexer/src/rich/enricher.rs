@@ -139,7 +139,7 @@ impl<'a> Enricher<'a> {
 
 	#[instrument(level = "DEBUG", skip(self))]
 	fn cook_literal(
-		&mut self,
+		&self,
 		start: BytePos,
 		end: BytePos,
 		kind: poor::LiteralKind,
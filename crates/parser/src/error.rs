@@ -48,3 +48,24 @@ pub struct ExpectedType {
 	#[label("expected a type here")]
 	pub span: Span,
 }
+
+#[derive(Debug, IntoDiagnostic)]
+#[message("we expected an identifier but found {parsed}")]
+pub struct ExpectedIdent {
+	#[label("expected an identifier")]
+	pub parsed: Token,
+}
+
+#[derive(Debug, IntoDiagnostic)]
+#[message("we expected an opening delimiter but found {parsed}")]
+pub struct ExpectedDelimiter {
+	#[label("expected one of `(`, `[` or `{{`")]
+	pub parsed: Token,
+}
+
+#[derive(Debug, IntoDiagnostic)]
+#[message("attributes must be followed by an item")]
+pub struct DanglingAttributes {
+	#[label("these attributes apply to nothing")]
+	pub span: Span,
+}
@@ -19,9 +19,9 @@ impl Parser<'_> {
 	/// Parse `|<inline_attrs>|`
 	#[instrument(level = "DEBUG", skip(self))]
 	pub(super) fn parse_inline_attrs(&mut self) -> PResult<Option<AttrVec>> {
-		if self.eat(&TokenKind::Op(OpKind::Or)) {
+		if self.eat(TokenKind::Op(OpKind::Or)) {
 			let attrs = self.parse_attrs(AttrStyle::Inline)?;
-			self.expect(&TokenKind::Op(OpKind::Or))?;
+			self.expect(TokenKind::Op(OpKind::Or))?;
 			Ok(Some(attrs))
 		} else {
 			Ok(None)
@@ -33,7 +33,7 @@ impl Parser<'_> {
 		let mut attrs = AttrVec::new();
 
 		loop {
-			let attr = if self.check(&TokenKind::At) {
+			let attr = if self.check(TokenKind::At) {
 				Some(self.parse_attr()?)
 			} else if let TokenKind::DocComment(style, sym) = self.token.kind {
 				let _span = debug_span!("parse_doc_attr").entered();
@@ -75,11 +75,11 @@ impl Parser<'_> {
 	fn parse_attr(&mut self) -> PResult<Attribute> {
 		let lo = self.token.span;
 
-		self.expect(&TokenKind::At)?;
+		self.expect(TokenKind::At)?;
 
-		let style = if self.eat(&TokenKind::Bang) {
+		let style = if self.eat(TokenKind::Bang) {
 			AttrStyle::Inner
-		} else if self.eat(&TokenKind::At) {
+		} else if self.eat(TokenKind::At) {
 			AttrStyle::Outer
 		} else {
 			AttrStyle::Inline
@@ -87,7 +87,7 @@ impl Parser<'_> {
 
 		let ident = self.parse_ident()?;
 
-		if self.eat(&TokenKind::Colon) {
+		if self.eat(TokenKind::Colon) {
 			// Parse `@key: <value>`
 			let expr = self.parse_expr()?;
 
@@ -1,8 +1,8 @@
 use crate::{PResult, Parser, error::UnexpectedToken};
-use dapic_ast::types::{AttrStyle, Expr, ExprKind, FieldDef, NodeId, P, PropertyDef};
-use dapic_lexer::rich::{Delimiter, LiteralKind, TokenKind};
+use dapic_ast::types::{AttrStyle, AttrVec, Expr, ExprKind, FieldDef, NodeId, P, PropertyDef};
+use dapic_lexer::rich::{Delimiter, LiteralKind, OpKind, TokenKind};
 use dapic_session::{
-	Ident, sym,
+	Ident, Symbol, sym,
 	symbols::{attrs, kw},
 };
 use thin_vec::{ThinVec, thin_vec};
@@ -20,7 +20,7 @@ impl Parser<'_> {
 
 	#[instrument(level = "DEBUG", skip(self))]
 	fn parse_expr_kind(&mut self) -> PResult<ExprKind> {
-		if self.check(&TokenKind::OpenDelim(Delimiter::Bracket)) {
+		if self.check(TokenKind::OpenDelim(Delimiter::Bracket)) {
 			self.parse_expr_array()
 		} else {
 			self.parse_expr_literal()
@@ -39,6 +39,19 @@ impl Parser<'_> {
 		{
 			self.bump();
 			Ok(ExprKind::Literal(LiteralKind::Bool, sym))
+		} else if self.eat(TokenKind::Op(OpKind::Minus)) {
+			// Negative numbers, e.g. `-90`
+			let TokenKind::Literal(LiteralKind::Number, sym) = self.token.kind else {
+				return Err(UnexpectedToken {
+					parsed: self.token.clone(),
+					expected: TokenKind::Literal(LiteralKind::Number, sym!("1")),
+				}
+				.into());
+			};
+			self.bump();
+
+			let negative = Symbol::intern(&format!("-{sym}"));
+			Ok(ExprKind::Literal(LiteralKind::Number, negative))
 		} else {
 			// TODO: recover properly
 			Err(UnexpectedToken {
@@ -53,8 +66,8 @@ impl Parser<'_> {
 	fn parse_expr_array(&mut self) -> PResult<ExprKind> {
 		let mut items = ThinVec::default();
 
-		self.expect(&TokenKind::OpenDelim(Delimiter::Bracket))?;
-		while !self.eat(&TokenKind::CloseDelim(Delimiter::Bracket)) {
+		self.expect(TokenKind::OpenDelim(Delimiter::Bracket))?;
+		while !self.eat(TokenKind::CloseDelim(Delimiter::Bracket)) {
 			items.push(self.parse_expr()?);
 		}
 
@@ -71,7 +84,7 @@ impl Parser<'_> {
 	}
 
 	#[instrument(level = "DEBUG", skip(self))]
-	fn parse_property_def(&mut self) -> PResult<Option<P<PropertyDef>>> {
+	pub(super) fn parse_property_def(&mut self) -> PResult<Option<P<PropertyDef>>> {
 		let lo = self.token.span;
 
 		let mut attrs = self.parse_outer_attrs()?;
@@ -111,9 +124,30 @@ impl Parser<'_> {
 		let Some(ident) = self.eat_ident() else {
 			return Ok(None);
 		};
+		let optional = self.eat(TokenKind::Question);
 		let ty = self.parse_ty()?;
+		let default = if self.eat(TokenKind::Eq) {
+			Some(self.parse_expr()?)
+		} else {
+			None
+		};
+
+		self.parse_trailing_attrs(&mut attrs)?;
+
+		Ok(Some(Self::make_field_def(
+			attrs,
+			ident,
+			optional,
+			ty,
+			default,
+			self.span(lo),
+		)))
+	}
 
-		// Extract the optional literal after the type and transform into sugar for a meta attr named `description`
+	/// Parse the optional description literal and `|<inline_attrs>|` following
+	/// a type, the literal is sugar for a meta attr named `description`
+	#[instrument(level = "DEBUG", skip(self))]
+	pub(super) fn parse_trailing_attrs(&mut self, attrs: &mut AttrVec) -> PResult<()> {
 		if let Ok(ExprKind::Literal(LiteralKind::Str, sym)) = self.parse_expr_literal() {
 			attrs.push(Self::make_meta_attr(
 				Ident::new(attrs::description, self.prev_token.span),
@@ -132,7 +166,7 @@ impl Parser<'_> {
 			attrs.extend(inline_attrs);
 		}
 
-		Ok(Some(Self::make_field_def(attrs, ident, ty, self.span(lo))))
+		Ok(())
 	}
 }
 
@@ -140,6 +174,7 @@ impl Parser<'_> {
 mod tests {
 	use crate::assert_tokenize;
 
+	assert_tokenize!(parse_expr, "numbers", "[-90 0.5 1_000]");
 	assert_tokenize!(
 		parse_field_defs,
 		r#"
@@ -152,4 +187,30 @@ mod tests {
 			X-Model string "The Model of the User"
 		"#
 	);
+	assert_tokenize!(
+		parse_field_defs,
+		"optional_nullable",
+		r"
+			name? string
+			tag string?
+			nickname? string?
+		"
+	);
+	assert_tokenize!(
+		parse_field_defs,
+		"default_example",
+		r#"
+			limit int = 20 "Maximum number of results"
+			@@example(["dog" "cat"])
+			tags [string] = []
+		"#
+	);
+	assert_tokenize!(
+		parse_field_defs,
+		"union_inline_attrs",
+		r"
+			pet Cat | Dog |@deprecated|
+			owner Person | null
+		"
+	);
 }
@@ -5,7 +5,7 @@ use dapic_ast::types::{
 };
 use dapic_lexer::rich::{Delimiter, Token};
 use dapic_session::{Ident, Span, Symbol};
-use thin_vec::{ThinVec, thin_vec};
+use thin_vec::ThinVec;
 
 impl Parser<'_> {
 	pub(super) fn span(&self, lo: Span) -> Span {
@@ -56,13 +56,17 @@ impl Parser<'_> {
 	pub(super) fn make_field_def(
 		attrs: AttrVec,
 		ident: Ident,
+		optional: bool,
 		ty: P<Ty>,
+		default: Option<P<Expr>>,
 		span: Span,
 	) -> P<FieldDef> {
 		P(FieldDef {
 			attrs,
 			ident,
+			optional,
 			ty,
+			default,
 			id: NodeId::DUMMY,
 			span,
 		})
@@ -122,13 +126,15 @@ impl Parser<'_> {
 		})
 	}
 
-	pub(super) fn make_ty_kind_single(ident: Ident, span: Span) -> TyKind {
-		TyKind::Path(Path {
-			segments: thin_vec![PathSegment {
-				ident,
-				id: NodeId::DUMMY,
-			}],
-			span,
-		})
+	pub(super) const fn make_path(segments: ThinVec<PathSegment>, span: Span) -> Path {
+		Path { segments, span }
+	}
+
+	pub(super) const fn make_path_segment(ident: Ident) -> PathSegment {
+		PathSegment {
+			ident,
+			id: NodeId::DUMMY,
+			args: ThinVec::new(),
+		}
 	}
 }
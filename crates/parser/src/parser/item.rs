@@ -1,16 +1,25 @@
-use crate::{PResult, Parser, error::InvalidVerb};
+use crate::{
+	PResult, Parser,
+	error::{DanglingAttributes, InvalidVerb, UnexpectedToken},
+	parser::Spacing,
+};
 use dapic_ast::types::{
-	AttrVec, Auth, Body, Enum, Headers, Item, ItemKind, Metadata, Model, NodeId, P, Params,
-	PathItem, PathKind, Query, Root, ScopeKind, StatusCode, Verb,
+	AttrVec, Auth, AuthDef, AuthFlow, Body, Enum, Expr, ExprKind, Headers, Item, ItemKind,
+	Metadata, Model, NodeId, P, Params, PathItem, PathKind, Query, Response, Root, ScopeKind,
+	StatusCode, TypeAlias, Union, Use, Verb,
 };
-use dapic_lexer::rich::{Delimiter, OpKind, TokenKind};
+use dapic_lexer::rich::{Delimiter, LiteralKind, OpKind, TokenKind};
 use dapic_session::{
-	Ident,
-	symbols::{kw, remarkable},
+	Ident, Symbol, sym,
+	symbols::{auth, kw, remarkable},
 };
 use thin_vec::{ThinVec, thin_vec};
 
 impl Parser<'_> {
+	/// Parses a whole source, which starts with its `meta` block.
+	///
+	/// # Errors
+	/// Returns the first syntax error of the source.
 	#[tracing::instrument(level = "DEBUG", skip(self))]
 	pub fn parse_root(&mut self) -> PResult<Root> {
 		let lo = self.token.span;
@@ -35,7 +44,7 @@ impl Parser<'_> {
 
 		let ident = self.parse_ident()?;
 
-		let scope = if self.eat(&TokenKind::Semi) {
+		let scope = if self.eat(TokenKind::Semi) {
 			ScopeKind::Unloaded
 		} else {
 			let lo = self.token.span;
@@ -51,6 +60,10 @@ impl Parser<'_> {
 		Ok((ident, scope))
 	}
 
+	/// Parses the items of a scope, its inner attributes are added to `attrs`.
+	///
+	/// # Errors
+	/// Returns the first syntax error of the scope.
 	#[tracing::instrument(level = "DEBUG", skip(self, attrs))]
 	pub fn parse_scope_content(
 		&mut self,
@@ -67,9 +80,7 @@ impl Parser<'_> {
 	fn parse_metadata(&mut self) -> PResult<P<Item>> {
 		let lo = self.token.span;
 
-		if !self.eat_keyword(kw::Meta) {
-			todo!("react accordingly")
-		}
+		self.expect_keyword(kw::Meta)?;
 
 		let fields = self.expect_braced(Self::parse_property_defs)?;
 
@@ -114,25 +125,34 @@ impl Parser<'_> {
 			let metadata = self.parse_metadata()?.kind.clone();
 			(None, metadata)
 		} else if self.check_keyword(kw::Headers) {
-			// `headers { <fields> }`
-			let headers = self.parse_headers()?;
-			(None, ItemKind::Headers(headers))
+			// `headers <ident>? { <fields> }` or `headers <ident>;`
+			self.parse_headers()?
 		} else if self.check_keyword(kw::Query) {
-			// `query { <fields> }`
-			let query = self.parse_query()?;
-			(None, ItemKind::Query(query))
+			// `query <ident>? { <fields> }` or `query <ident>;`
+			self.parse_query()?
 		} else if self.check_keyword(kw::Code) {
-			// `code <code lit> { <items> }`
-			let item = self.parse_code_item()?;
-			(None, ItemKind::StatusCode(item))
+			// `code <code lit> { <items> }` or `code <code lit> <ident>;`
+			self.parse_code_item()?
+		} else if self.check_keyword(kw::Response) {
+			// `response <ident> { <items> }`
+			let (ident, response) = self.parse_response()?;
+			(Some(ident), ItemKind::Response(response))
 		} else if self.check_keyword(kw::Model) {
-			// `model <ident> { <def_fields> }`
+			// `model <ident> (: <bases>)? { <def_fields> }`
 			let (ident, item) = self.parse_model()?;
 			(Some(ident), ItemKind::Model(item))
 		} else if self.check_keyword(kw::Enum) {
 			// `enum <ident> { <def_fields> }`
 			let (ident, item) = self.parse_enum()?;
 			(Some(ident), ItemKind::Enum(item))
+		} else if self.check_keyword(kw::Union) {
+			// `union <ident> on <ident> { <def_fields> }`
+			let (ident, item) = self.parse_union()?;
+			(Some(ident), ItemKind::Union(item))
+		} else if self.check_keyword(kw::Type) {
+			// `type <ident> = <ty>`
+			let (ident, item) = self.parse_type_alias(&mut attrs)?;
+			(Some(ident), ItemKind::TypeAlias(item))
 		} else if self.check_keyword(kw::Auth) {
 			// `auth <ident> { <auth_fields> }`
 			let (ident, item) = self.parse_auth()?;
@@ -146,15 +166,18 @@ impl Parser<'_> {
 			let body = self.parse_body()?;
 			(None, ItemKind::Body(body))
 		} else if self.check_keyword(kw::Params) {
-			// `params { <field_defs> }`
-			let params = self.parse_params()?;
-			(None, ItemKind::Params(params))
+			// `params <ident>? { <field_defs> }` or `params <ident>;`
+			self.parse_params()?
 		} else {
-			if attrs.is_empty() {
+			let Some(span) = attrs
+				.iter()
+				.map(|attr| attr.span)
+				.reduce(|lo, hi| lo.to(hi))
+			else {
 				return Ok(None);
-			}
+			};
 
-			todo!("error if attributes are parsed but there is not item to attach them")
+			return Err(DanglingAttributes { span }.into());
 		};
 
 		Ok(Some(Self::make_item(attrs, kind, ident, self.span(lo))))
@@ -170,11 +193,11 @@ impl Parser<'_> {
 
 	#[tracing::instrument(level = "DEBUG", skip(self))]
 	fn parse_path_item_kind(&mut self) -> PResult<PathKind> {
-		let kind = if self.eat(&TokenKind::OpenDelim(Delimiter::Brace)) {
+		let kind = if self.eat(TokenKind::OpenDelim(Delimiter::Brace)) {
 			let ident = self.parse_ident()?;
-			self.expect(&TokenKind::CloseDelim(Delimiter::Brace))?;
+			self.expect(TokenKind::CloseDelim(Delimiter::Brace))?;
 			PathKind::Variable(ident)
-		} else if self.eat(&TokenKind::Dot) {
+		} else if self.eat(TokenKind::Dot) {
 			PathKind::Current
 		} else {
 			let ident = self.parse_ident()?;
@@ -182,7 +205,7 @@ impl Parser<'_> {
 			PathKind::Simple(ident)
 		};
 
-		if self.eat(&TokenKind::Op(OpKind::Slash)) {
+		if self.eat(TokenKind::Op(OpKind::Slash)) {
 			let mut parts = thin_vec![kind];
 
 			match self.parse_path_item_kind()? {
@@ -199,25 +222,91 @@ impl Parser<'_> {
 	}
 
 	#[tracing::instrument(level = "DEBUG", skip(self))]
-	fn parse_headers(&mut self) -> PResult<Headers> {
+	fn parse_headers(&mut self) -> PResult<(Option<Ident>, ItemKind)> {
 		self.expect_keyword(kw::Headers)?;
+		let name = self.eat_ident();
+
+		if let Some(name) = name
+			&& self.eat(TokenKind::Semi)
+		{
+			return Ok((Some(name), ItemKind::Use(Use::Headers)));
+		}
+
 		let headers = self.expect_braced(Self::parse_field_defs)?;
-		Ok(Headers { headers })
+		Ok((name, ItemKind::Headers(Headers { headers })))
 	}
 
 	#[tracing::instrument(level = "DEBUG", skip(self))]
-	fn parse_query(&mut self) -> PResult<Query> {
+	fn parse_query(&mut self) -> PResult<(Option<Ident>, ItemKind)> {
 		self.expect_keyword(kw::Query)?;
+		let name = self.eat_ident();
+
+		if let Some(name) = name
+			&& self.eat(TokenKind::Semi)
+		{
+			return Ok((Some(name), ItemKind::Use(Use::Query)));
+		}
+
 		let fields = self.expect_braced(Self::parse_field_defs)?;
-		Ok(Query { fields })
+		Ok((name, ItemKind::Query(Query { fields })))
 	}
 
 	#[tracing::instrument(level = "DEBUG", skip(self))]
-	fn parse_code_item(&mut self) -> PResult<StatusCode> {
+	fn parse_code_item(&mut self) -> PResult<(Option<Ident>, ItemKind)> {
 		self.expect_keyword(kw::Code)?;
-		let code = self.parse_expr()?;
+		let code = self.parse_status_code()?;
+
+		if let Some(name) = self.eat_ident() {
+			self.expect(TokenKind::Semi)?;
+			return Ok((Some(name), ItemKind::Use(Use::Response { code })));
+		}
+
+		let items = self.expect_braced(Self::parse_items)?;
+		Ok((None, ItemKind::StatusCode(StatusCode { code, items })))
+	}
+
+	#[tracing::instrument(level = "DEBUG", skip(self))]
+	fn parse_response(&mut self) -> PResult<(Ident, Response)> {
+		self.expect_keyword(kw::Response)?;
+		let name = self.parse_ident()?;
 		let items = self.expect_braced(Self::parse_items)?;
-		Ok(StatusCode { code, items })
+		Ok((name, Response { items }))
+	}
+
+	/// Parses `200`, `~2xx`, `default` or a status name like `NOT_FOUND`.
+	///
+	/// Codes are only checked during lowering.
+	#[tracing::instrument(level = "DEBUG", skip(self))]
+	fn parse_status_code(&mut self) -> PResult<P<Expr>> {
+		let lo = self.token.span;
+
+		let kind = if self.eat(TokenKind::Tilde) {
+			// `~2xx` is lexed as `~`, `2` and `xx`
+			let TokenKind::Literal(LiteralKind::Number, class) = self.token.kind else {
+				return Err(UnexpectedToken {
+					parsed: self.token.clone(),
+					expected: TokenKind::Literal(LiteralKind::Number, sym!("2")),
+				}
+				.into());
+			};
+			self.bump();
+
+			let mut template = class.as_str().to_owned();
+			if self.token_spacing == Spacing::Joint
+				&& let Some(ident) = self.eat_ident()
+			{
+				template.push_str(ident.symbol.as_str());
+			}
+
+			ExprKind::Template(Symbol::intern(&template))
+		} else if let Some(ident) = self.eat_ident() {
+			let segments = thin_vec![Self::make_path_segment(ident)];
+			ExprKind::Path(Self::make_path(segments, ident.span))
+		} else {
+			return self.parse_expr();
+		};
+
+		Ok(Self::make_expr(ThinVec::default(), kind, self.span(lo)))
 	}
 
 	#[tracing::instrument(level = "DEBUG", skip(self))]
@@ -231,8 +320,10 @@ impl Parser<'_> {
 			// this should be in a lint passes
 			// also custom verbs are allowed
 
-			use remarkable::{Connect, Delete, Get, Head, Options, Post, Put, Trace};
-			if ![Connect, Delete, Get, Head, Options, Post, Put, Trace].contains(&method.symbol) {
+			use remarkable::{Connect, Delete, Get, Head, Options, Patch, Post, Put, Trace};
+			if ![Connect, Delete, Get, Head, Options, Patch, Post, Put, Trace]
+				.contains(&method.symbol)
+			{
 				self.session.diag.emit(InvalidVerb { found: method });
 			}
 		}
@@ -244,16 +335,81 @@ impl Parser<'_> {
 	fn parse_model(&mut self) -> PResult<(Ident, Model)> {
 		self.expect_keyword(kw::Model)?;
 		let name = self.parse_ident()?;
+
+		// `<<ident>(, <ident>)*>`
+		let mut generics = ThinVec::new();
+		if self.eat(TokenKind::Op(OpKind::Lt)) {
+			loop {
+				generics.push(self.parse_ident()?);
+				if !self.eat(TokenKind::Comma) {
+					break;
+				}
+			}
+			self.expect(TokenKind::Op(OpKind::Gt))?;
+		}
+
+		// `: <path>(, <path>)*`
+		let mut bases = ThinVec::new();
+		if self.eat(TokenKind::Colon) {
+			loop {
+				bases.push(self.parse_path()?);
+				if !self.eat(TokenKind::Comma) {
+					break;
+				}
+			}
+		}
+
 		let fields = self.expect_braced(Self::parse_field_defs)?;
-		Ok((name, Model { fields }))
+		Ok((
+			name,
+			Model {
+				generics,
+				bases,
+				fields,
+			},
+		))
 	}
 
 	#[tracing::instrument(level = "DEBUG", skip(self))]
 	fn parse_enum(&mut self) -> PResult<(Ident, Enum)> {
 		self.expect_keyword(kw::Enum)?;
 		let name = self.parse_ident()?;
+
+		// `: <ty>`
+		let ty = if self.eat(TokenKind::Colon) {
+			Some(self.parse_ty()?)
+		} else {
+			None
+		};
+
 		let variants = self.expect_braced(Self::parse_property_defs)?;
-		Ok((name, Enum { variants }))
+		Ok((name, Enum { ty, variants }))
+	}
+
+	#[tracing::instrument(level = "DEBUG", skip(self))]
+	fn parse_type_alias(&mut self, attrs: &mut AttrVec) -> PResult<(Ident, TypeAlias)> {
+		self.expect_keyword(kw::Type)?;
+		let name = self.parse_ident()?;
+		self.expect(TokenKind::Eq)?;
+		let ty = self.parse_ty()?;
+		self.parse_trailing_attrs(attrs)?;
+		Ok((name, TypeAlias { ty }))
+	}
+
+	#[tracing::instrument(level = "DEBUG", skip(self))]
+	fn parse_union(&mut self) -> PResult<(Ident, Union)> {
+		self.expect_keyword(kw::Union)?;
+		let name = self.parse_ident()?;
+		self.expect_keyword(kw::On)?;
+		let discriminator = self.parse_ident()?;
+		let variants = self.expect_braced(Self::parse_field_defs)?;
+		Ok((
+			name,
+			Union {
+				discriminator,
+				variants,
+			},
+		))
 	}
 
 	#[tracing::instrument(level = "DEBUG", skip(self))]
@@ -261,21 +417,93 @@ impl Parser<'_> {
 		self.expect_keyword(kw::Auth)?;
 		let auth_name = self.parse_ident()?;
 
-		let kind = if self.eat(&TokenKind::Semi) {
-			// `auth BasicAuth;`
-			Auth::Use
+		let kind = if self.check(TokenKind::OpenDelim(Delimiter::Brace)) {
+			// `auth BasicAuth { <properties> <flows> }`
+			Auth::Define(self.expect_braced(Self::parse_auth_def)?)
 		} else {
-			// `auth BasicAuth { <field_defs> }`
-
-			// TODO: define `auth` syntax and parse it
-			let _fields = self.expect_braced(Self::parse_field_defs)?;
-
-			Auth::Define {}
+			// `auth BasicAuth;` or `auth OAuth ["read:pets"];`
+			let scopes = if self.check(TokenKind::OpenDelim(Delimiter::Bracket)) {
+				Some(self.parse_expr()?)
+			} else {
+				None
+			};
+			self.expect(TokenKind::Semi)?;
+
+			Auth::Use { scopes }
 		};
 
 		Ok((auth_name, kind))
 	}
 
+	#[tracing::instrument(level = "DEBUG", skip(self))]
+	fn parse_auth_def(&mut self) -> PResult<AuthDef> {
+		let mut properties = ThinVec::new();
+		let mut flows = ThinVec::new();
+
+		loop {
+			if self.check_keyword(auth::flow) {
+				flows.push(self.parse_auth_flow()?);
+			} else if let Some(property) = self.parse_property_def()? {
+				properties.push(property);
+			} else {
+				break;
+			}
+		}
+
+		Ok(AuthDef { properties, flows })
+	}
+
+	#[tracing::instrument(level = "DEBUG", skip(self))]
+	fn parse_auth_flow(&mut self) -> PResult<AuthFlow> {
+		let lo = self.token.span;
+
+		self.expect_keyword(auth::flow)?;
+		let kind = self.parse_ident()?;
+
+		let (properties, scopes) = self.expect_braced(|p| {
+			let mut properties = ThinVec::new();
+			let mut scopes = ThinVec::new();
+
+			loop {
+				if p.eat_keyword(auth::scopes) {
+					scopes.extend(p.expect_braced(Self::parse_auth_scopes)?);
+				} else if let Some(property) = p.parse_property_def()? {
+					properties.push(property);
+				} else {
+					break;
+				}
+			}
+
+			Ok((properties, scopes))
+		})?;
+
+		Ok(AuthFlow {
+			kind,
+			properties,
+			scopes,
+			span: self.span(lo),
+		})
+	}
+
+	/// Parses string literals, each one preceded by its doc comments.
+	#[tracing::instrument(level = "DEBUG", skip(self))]
+	fn parse_auth_scopes(&mut self) -> PResult<ThinVec<P<Expr>>> {
+		let mut scopes = ThinVec::new();
+
+		loop {
+			let attrs = self.parse_outer_attrs()?;
+			if self.check(TokenKind::CloseDelim(Delimiter::Brace)) {
+				break;
+			}
+
+			let mut scope = self.parse_expr()?;
+			scope.attrs = attrs;
+			scopes.push(scope);
+		}
+
+		Ok(scopes)
+	}
+
 	#[tracing::instrument(level = "DEBUG", skip(self))]
 	fn parse_body(&mut self) -> PResult<Body> {
 		self.expect_keyword(kw::Body)?;
@@ -284,19 +512,109 @@ impl Parser<'_> {
 	}
 
 	#[tracing::instrument(level = "DEBUG", skip(self))]
-	fn parse_params(&mut self) -> PResult<Params> {
+	fn parse_params(&mut self) -> PResult<(Option<Ident>, ItemKind)> {
 		self.expect_keyword(kw::Params)?;
+		let name = self.eat_ident();
+
+		if let Some(name) = name
+			&& self.eat(TokenKind::Semi)
+		{
+			return Ok((Some(name), ItemKind::Use(Use::Params)));
+		}
+
 		let properties = self.expect_braced(Self::parse_field_defs)?;
-		Ok(Params { properties })
+		Ok((name, ItemKind::Params(Params { properties })))
 	}
 }
 
 #[cfg(test)]
 mod tests {
-	use crate::assert_tokenize;
+	use crate::{assert_tokenize, parser};
 
 	assert_tokenize!(parse_path_item_kind, "simple", "var");
 	assert_tokenize!(parse_path_item_kind, "variable", "{var}");
 	assert_tokenize!(parse_path_item_kind, "complex", "var1/{var2}");
 	assert_tokenize!(parse_path_item_kind, "long_complex", "var1/{var2}/{var3}");
+
+	assert_tokenize!(parse_status_code, "number", "404");
+	assert_tokenize!(parse_status_code, "template", "~2xx");
+	assert_tokenize!(parse_status_code, "name", "IM_A_TEAPOT");
+	assert_tokenize!(parse_code_item, "use", "code 404 NotFound;");
+
+	assert_tokenize!(
+		parse_headers,
+		"named",
+		"headers Authorized { Authorization string }"
+	);
+	assert_tokenize!(parse_headers, "use", "headers Authorized;");
+	assert_tokenize!(parse_response, "named", "response NotFound { body Error }");
+
+	assert_tokenize!(
+		parse_model,
+		"bases",
+		"model Pet : Base, common::Timestamps { name string }"
+	);
+	assert_tokenize!(
+		parse_model,
+		"generic",
+		"model Page<T> { items [T] total int }"
+	);
+	assert_tokenize!(
+		parse_enum,
+		"typed",
+		"enum Code : int { ## Everything went fine\n Ok 200 NotFound 404 }"
+	);
+	assert_tokenize!(
+		parse_union,
+		"tagged",
+		"union Pet on petType { cat Cat dog Dog }"
+	);
+
+	assert_tokenize!(
+		parse_item,
+		"type_alias",
+		r#"type UserId = string "Identifier of a user" |@format: "uuid"|"#
+	);
+
+	assert_tokenize!(parse_auth, "use", r#"auth PetstoreAuth ["read:pets"];"#);
+	assert_tokenize!(
+		parse_auth,
+		"define",
+		r#"auth PetstoreAuth {
+			type "oauth2"
+			flow implicit {
+				authorizationUrl "https://petstore.swagger.io/oauth/authorize"
+				scopes {
+					## Modify pets in your account
+					"write:pets"
+					"read:pets"
+				}
+			}
+		}"#
+	);
+
+	#[test]
+	fn report_missing_idents() {
+		for src in [
+			"union A on 1 {}",
+			"model Page<1> {}",
+			"auth x { flow 1 }",
+			"response 1",
+		] {
+			parser!(p; src);
+			assert!(p.parse_item().is_err(), "{src}");
+		}
+	}
+
+	#[test]
+	fn report_dangling_attributes() {
+		parser!(p; "model A {} @@deprecated");
+		assert!(p.parse_scope_content(None).is_err());
+	}
+
+	#[test]
+	fn report_missing_meta() {
+		parser!(p; "model A {}");
+		assert!(p.parse_root().is_err());
+	}
 }
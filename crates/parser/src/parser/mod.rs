@@ -1,6 +1,6 @@
 use crate::{
 	PResult,
-	error::{UnexpectedToken, UnexpectedTokenInsteadOfKeyword},
+	error::{ExpectedDelimiter, ExpectedIdent, UnexpectedToken, UnexpectedTokenInsteadOfKeyword},
 };
 use dapic_lexer::rich::{Delimiter, Enricher, Token, TokenKind};
 use dapic_session::{Ident, ParseSession, SourceFile, Symbol};
@@ -38,6 +38,8 @@ pub struct Parser<'a> {
 
 	expected_tokens: Vec<TokenKind>,
 	cursor: Enricher<'a>,
+	/// The token after the current one, when it was looked at.
+	next_token: Option<(Token, bool)>,
 }
 
 impl<'a> Parser<'a> {
@@ -56,6 +58,7 @@ impl<'a> Parser<'a> {
 			prev_token: Token::DUMMY,
 			expected_tokens: Vec::default(),
 			cursor,
+			next_token: None,
 		};
 
 		parser.bump();
@@ -66,20 +69,20 @@ impl<'a> Parser<'a> {
 	/// Expects and consumes the token `t`. Signals an error if the next token is not `t`.
 	#[track_caller]
 	#[instrument(level = "TRACE", skip(self))]
-	fn expect(&mut self, tok: &TokenKind) -> PResult</* recovered */ bool> {
+	fn expect(&mut self, tok: TokenKind) -> PResult</* recovered */ bool> {
 		if self.expected_tokens.is_empty() {
-			if &self.token.kind == tok {
+			if self.token.kind == tok {
 				self.bump();
 				Ok(false)
 			} else {
 				// todo!("recover from unexpected token {}", self.token)
 				Err(UnexpectedToken {
 					parsed: self.token.clone(),
-					expected: *tok,
+					expected: tok,
 				}
 				.into())
 			}
-		} else if &self.token.kind == tok {
+		} else if self.token.kind == tok {
 			self.bump();
 			Ok(false)
 		} else {
@@ -89,7 +92,7 @@ impl<'a> Parser<'a> {
 
 			Err(UnexpectedToken {
 				parsed: self.token.clone(),
-				expected: *tok,
+				expected: tok,
 			}
 			.into())
 		}
@@ -97,9 +100,9 @@ impl<'a> Parser<'a> {
 
 	#[track_caller]
 	fn expect_braced<T>(&mut self, mut p: impl FnMut(&mut Self) -> PResult<T>) -> PResult<T> {
-		self.expect(&TokenKind::OpenDelim(Delimiter::Brace))?;
+		self.expect(TokenKind::OpenDelim(Delimiter::Brace))?;
 		let parsed = p(self)?;
-		self.expect(&TokenKind::CloseDelim(Delimiter::Brace))?;
+		self.expect(TokenKind::CloseDelim(Delimiter::Brace))?;
 		Ok(parsed)
 	}
 
@@ -119,10 +122,20 @@ impl<'a> Parser<'a> {
 		}
 	}
 
+	/// The token after the current one, without consuming anything.
+	#[instrument(level = "TRACE", skip(self))]
+	fn look_ahead(&mut self) -> &Token {
+		let cursor = &mut self.cursor;
+		&self.next_token.get_or_insert_with(|| cursor.next_token()).0
+	}
+
 	#[instrument(level = "TRACE", skip(self))]
 	fn bump(&mut self) {
 		use Spacing::*;
-		let (next_token, has_space_before) = self.cursor.next_token();
+		let (next_token, has_space_before) = self
+			.next_token
+			.take()
+			.unwrap_or_else(|| self.cursor.next_token());
 
 		self.prev_token = mem::replace(&mut self.token, next_token);
 		self.token_spacing = if has_space_before { Alone } else { Joint };
@@ -131,11 +144,11 @@ impl<'a> Parser<'a> {
 	}
 
 	#[instrument(level = "TRACE", skip(self))]
-	fn check(&mut self, tok: &TokenKind) -> bool {
-		let is_present = &self.token.kind == tok;
+	fn check(&mut self, tok: TokenKind) -> bool {
+		let is_present = self.token.kind == tok;
 
 		if !is_present {
-			self.expected_tokens.push(*tok);
+			self.expected_tokens.push(tok);
 		}
 
 		is_present
@@ -143,7 +156,7 @@ impl<'a> Parser<'a> {
 
 	/// Consumes a token 'tok' if it exists. Returns whether the given token was present.
 	#[instrument(level = "TRACE", skip(self))]
-	fn eat(&mut self, tok: &TokenKind) -> bool {
+	fn eat(&mut self, tok: TokenKind) -> bool {
 		let is_present = self.check(tok);
 		if is_present {
 			self.bump();
@@ -174,7 +187,7 @@ impl<'a> Parser<'a> {
 	/// If the next token is the given keyword, returns `true` without eating it.
 	/// An expectation is also added for diagnostics purposes.
 	#[instrument(level = "TRACE", skip(self))]
-	fn check_ident(&mut self) -> bool {
+	fn check_ident(&self) -> bool {
 		// self.expected_tokens.push(TokenKind::Ident(kw));
 		self.token.ident().is_some()
 	}
@@ -187,13 +200,18 @@ impl<'a> Parser<'a> {
 	#[instrument(level = "TRACE", skip(self))]
 	fn parse_ident(&mut self) -> PResult<Ident> {
 		let Some(ident) = self.token.ident() else {
-			todo!("recover")
+			return Err(ExpectedIdent {
+				parsed: self.token.clone(),
+			}
+			.into());
 		};
 
 		self.bump();
 		Ok(ident)
 	}
 
+	/// Parse the tokens between a delimiter and its closing one, both
+	/// delimiters are consumed.
 	#[instrument(level = "TRACE", skip(self))]
 	fn parse_delimited(&mut self) -> PResult<(Delimiter, ThinVec<Token>)> {
 		let mut tokens = ThinVec::default();
@@ -203,7 +221,12 @@ impl<'a> Parser<'a> {
 				self.bump();
 				delim
 			}
-			_ => todo!("recover"),
+			_ => {
+				return Err(ExpectedDelimiter {
+					parsed: self.token.clone(),
+				}
+				.into());
+			}
 		};
 
 		let mut nesting = 0;
@@ -216,6 +239,7 @@ impl<'a> Parser<'a> {
 
 				TokenKind::CloseDelim(delim) if delim == delim_kind => {
 					if nesting == 0 {
+						self.bump();
 						break Ok((delim_kind, tokens));
 					}
 
@@ -269,4 +293,17 @@ mod tests {
 	}
 
 	assert_tokenize!(parse_delimited, "(bar, baz)");
+
+	#[test]
+	fn parse_delimited_consumes_close_delim() -> Result<(), dapic_session::Diagnostic> {
+		parser!(p; "(bar (baz)) qux");
+		let (_, tokens) = p.parse_delimited()?;
+
+		assert_eq!(tokens.len(), 4);
+		assert_eq!(
+			p.token.ident().map(|ident| ident.to_string()),
+			Some("qux".into())
+		);
+		Ok(())
+	}
 }
@@ -1,36 +1,105 @@
 use crate::{PResult, Parser, error::ExpectedType};
-use dapic_ast::types::{P, Ty, TyKind};
-use dapic_lexer::rich::{Delimiter, TokenKind};
+use dapic_ast::types::{P, Path, Ty, TyKind};
+use dapic_lexer::rich::{Delimiter, OpKind, TokenKind};
 use thin_vec::thin_vec;
 use tracing::instrument;
 
 impl Parser<'_> {
+	/// Parse a type, or a union of types separated by `|`
 	#[instrument(level = "DEBUG", skip(self))]
 	pub(super) fn parse_ty(&mut self) -> PResult<P<Ty>> {
 		let lo = self.token.span;
 
-		let kind = if self.check(&TokenKind::OpenDelim(Delimiter::Parenthesis)) {
+		let ty = self.parse_ty_no_union()?;
+		if !self.check_union_bar() {
+			return Ok(ty);
+		}
+
+		let mut tys = thin_vec![ty];
+		while self.check_union_bar() {
+			self.bump();
+			tys.push(self.parse_ty_no_union()?);
+		}
+
+		Ok(Self::make_ty(TyKind::Union(tys), self.span(lo)))
+	}
+
+	/// A `|` separates the variants of a union when it is followed by a type,
+	/// otherwise it opens inline attributes (e.g. `id int |@min: 1|`).
+	fn check_union_bar(&mut self) -> bool {
+		self.check(TokenKind::Op(OpKind::Or))
+			&& matches!(
+				self.look_ahead().kind,
+				TokenKind::Ident(_) | TokenKind::OpenDelim(_)
+			)
+	}
+
+	#[instrument(level = "DEBUG", skip(self))]
+	fn parse_ty_no_union(&mut self) -> PResult<P<Ty>> {
+		let lo = self.token.span;
+
+		let kind = if self.check(TokenKind::OpenDelim(Delimiter::Parenthesis)) {
 			self.parse_ty_tuple_or_paren()?
-		} else if self.check(&TokenKind::OpenDelim(Delimiter::Bracket)) {
+		} else if self.check(TokenKind::OpenDelim(Delimiter::Bracket)) {
 			self.parse_ty_array()?
-		} else if self.check(&TokenKind::OpenDelim(Delimiter::Brace)) {
+		} else if self.check(TokenKind::OpenDelim(Delimiter::Brace)) {
 			self.parse_ty_inline_model()?
-		} else if let Some(ident) = self.eat_ident() {
-			Self::make_ty_kind_single(ident, self.token.span)
+		} else if self.check_ident() {
+			TyKind::Path(self.parse_path()?)
 		} else {
 			return Err(ExpectedType { span: lo }.into());
 		};
 
+		if self.eat(TokenKind::Question) {
+			let ty = Self::make_ty(kind, self.span(lo));
+			return Ok(Self::make_ty(TyKind::Nullable(ty), self.span(lo)));
+		}
+
 		Ok(Self::make_ty(kind, self.span(lo)))
 	}
 
+	/// Parse `<ident>(::<ident>)*(<<ty>(, <ty>)*>)?`
+	#[tracing::instrument(level = "DEBUG", skip(self))]
+	pub(super) fn parse_path(&mut self) -> PResult<Path> {
+		let lo = self.token.span;
+
+		let mut segments = thin_vec![Self::make_path_segment(self.parse_ident()?)];
+
+		// `::` is lexed as two distinct colons
+		while self.eat(TokenKind::Colon) {
+			self.expect(TokenKind::Colon)?;
+			if !self.check_ident() {
+				return Err(ExpectedType {
+					span: self.token.span,
+				}
+				.into());
+			}
+			segments.push(Self::make_path_segment(self.parse_ident()?));
+		}
+
+		if self.eat(TokenKind::Op(OpKind::Lt)) {
+			let last = segments
+				.last_mut()
+				.expect("paths have at least one segment");
+			loop {
+				last.args.push(self.parse_ty()?);
+				if !self.eat(TokenKind::Comma) {
+					break;
+				}
+			}
+			self.expect(TokenKind::Op(OpKind::Gt))?;
+		}
+
+		Ok(Self::make_path(segments, self.span(lo)))
+	}
+
 	#[tracing::instrument(level = "DEBUG", skip(self))]
 	fn parse_ty_tuple_or_paren(&mut self) -> PResult<TyKind> {
-		self.expect(&TokenKind::OpenDelim(Delimiter::Parenthesis))?;
+		self.expect(TokenKind::OpenDelim(Delimiter::Parenthesis))?;
 
 		let ty = self.parse_ty()?;
 
-		let kind = if self.eat(&TokenKind::Comma) {
+		let kind = if self.eat(TokenKind::Comma) {
 			let mut tys = thin_vec![ty];
 
 			loop {
@@ -39,7 +108,7 @@ impl Parser<'_> {
 					Err(_) => break,
 				}
 
-				if !self.eat(&TokenKind::Comma) {
+				if !self.eat(TokenKind::Comma) {
 					break;
 				}
 			}
@@ -49,22 +118,52 @@ impl Parser<'_> {
 			TyKind::Paren(ty)
 		};
 
-		self.expect(&TokenKind::CloseDelim(Delimiter::Parenthesis))?;
+		self.expect(TokenKind::CloseDelim(Delimiter::Parenthesis))?;
 
 		Ok(kind)
 	}
 
 	#[tracing::instrument(level = "DEBUG", skip(self))]
 	fn parse_ty_array(&mut self) -> PResult<TyKind> {
-		self.expect(&TokenKind::OpenDelim(Delimiter::Bracket))?;
+		self.expect(TokenKind::OpenDelim(Delimiter::Bracket))?;
 		let ty = self.parse_ty()?;
-		self.expect(&TokenKind::CloseDelim(Delimiter::Bracket))?;
+		self.expect(TokenKind::CloseDelim(Delimiter::Bracket))?;
 		Ok(TyKind::Array(ty))
 	}
 
+	/// Parse `{ <field_defs> }` or a map `{[<ty>]: <ty>}`
 	#[tracing::instrument(level = "DEBUG", skip(self))]
 	fn parse_ty_inline_model(&mut self) -> PResult<TyKind> {
-		let fields = self.expect_braced(Self::parse_field_defs)?;
-		Ok(TyKind::InlineModel(fields))
+		self.expect_braced(|this| {
+			if this.eat(TokenKind::OpenDelim(Delimiter::Bracket)) {
+				let key = this.parse_ty()?;
+				this.expect(TokenKind::CloseDelim(Delimiter::Bracket))?;
+				this.expect(TokenKind::Colon)?;
+				let value = this.parse_ty()?;
+
+				Ok(TyKind::Map(key, value))
+			} else {
+				Ok(TyKind::InlineModel(this.parse_field_defs()?))
+			}
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{assert_tokenize, parser};
+
+	assert_tokenize!(parse_ty, "qualified_path", "[pet::Status]");
+	assert_tokenize!(parse_ty, "nullable_array", "[string?]?");
+	assert_tokenize!(parse_ty, "union", "Pet | [Error]? | null");
+	assert_tokenize!(parse_ty, "map", "{[Status]: int}");
+	assert_tokenize!(parse_ty, "generic", "common::Page<Pet, [int]>");
+
+	#[test]
+	fn report_missing_path_segment() {
+		for src in ["pet::", "pet:: }", "pet::[string]"] {
+			parser!(p; src);
+			assert!(p.parse_ty().is_err(), "{src}");
+		}
 	}
 }
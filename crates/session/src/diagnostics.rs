@@ -18,6 +18,7 @@ impl DiagnosticsHandler {
 			error_count: 0,
 			warn_count: 0,
 			advice_count: 0,
+			codes: Vec::new(),
 		};
 
 		Self {
@@ -40,6 +41,16 @@ impl DiagnosticsHandler {
 		self.emit_diagnostic(&diag.into());
 	}
 
+	/// Whether at least one error was reported.
+	pub fn has_errors(&self) -> bool {
+		self.inner.lock().degraded()
+	}
+
+	/// Codes of the diagnostics reported so far, in order, e.g. `UnknownMetaKey`.
+	pub fn emitted_codes(&self) -> Vec<String> {
+		self.inner.lock().codes.clone()
+	}
+
 	/// Prints diagnostics statistics and exits if multiple errors were reported.
 	#[allow(clippy::significant_drop_tightening)]
 	pub fn check_degraded_and_exit(&self) {
@@ -59,6 +70,7 @@ impl DiagnosticsHandler {
 	///
 	/// # Panics
 	/// Must not be called in a degraded state
+	#[allow(clippy::significant_drop_tightening)]
 	pub fn print_final_stats(&self) {
 		let this = self.inner.lock();
 
@@ -83,6 +95,7 @@ struct InnerHandler {
 	error_count: u32,
 	warn_count: u32,
 	advice_count: u32,
+	codes: Vec<String>,
 }
 
 impl InnerHandler {
@@ -93,6 +106,8 @@ impl InnerHandler {
 			ReportKind::Advice => self.advice_count += 1,
 			ReportKind::Custom(_, _) => {}
 		}
+		self.codes
+			.push(diag.report.code.clone().unwrap_or_default());
 
 		if let Err(err) = diag.report.eprint(self.source_map.to_cache_hack()) {
 			tracing::error!("failed to print diagnostic: {}", err);
@@ -1,4 +1,9 @@
-use std::{fmt::Debug, hash::Hash, marker::PhantomData};
+use std::{
+	fmt::Debug,
+	hash::Hash,
+	marker::PhantomData,
+	ops::{Index, IndexMut},
+};
 
 pub trait Idx: Copy + 'static + Eq + PartialEq + Debug + Hash {
 	fn new(idx: usize) -> Self;
@@ -29,6 +34,68 @@ impl<I: Idx, T> IndexVec<I, T> {
 	pub const fn items(&self) -> &Vec<T> {
 		&self.inner
 	}
+
+	/// Appends an element and returns the index it was assigned.
+	pub fn push(&mut self, value: T) -> I {
+		let idx = I::new(self.inner.len());
+		self.inner.push(value);
+		idx
+	}
+
+	#[must_use]
+	pub const fn len(&self) -> usize {
+		self.inner.len()
+	}
+
+	#[must_use]
+	pub const fn is_empty(&self) -> bool {
+		self.inner.is_empty()
+	}
+
+	pub fn get(&self, idx: I) -> Option<&T> {
+		self.inner.get(idx.index())
+	}
+
+	pub fn iter(&self) -> impl Iterator<Item = &T> {
+		self.inner.iter()
+	}
+
+	pub fn iter_enumerated(&self) -> impl Iterator<Item = (I, &T)> {
+		self.inner
+			.iter()
+			.enumerate()
+			.map(|(idx, value)| (I::new(idx), value))
+	}
+
+	pub fn into_iter_enumerated(self) -> impl Iterator<Item = (I, T)> {
+		self.inner
+			.into_iter()
+			.enumerate()
+			.map(|(idx, value)| (I::new(idx), value))
+	}
+}
+
+impl<I: Idx, T> FromIterator<T> for IndexVec<I, T> {
+	fn from_iter<It: IntoIterator<Item = T>>(iter: It) -> Self {
+		Self {
+			inner: iter.into_iter().collect(),
+			marker: PhantomData,
+		}
+	}
+}
+
+impl<I: Idx, T> Index<I> for IndexVec<I, T> {
+	type Output = T;
+
+	fn index(&self, idx: I) -> &Self::Output {
+		&self.inner[idx.index()]
+	}
+}
+
+impl<I: Idx, T> IndexMut<I> for IndexVec<I, T> {
+	fn index_mut(&mut self, idx: I) -> &mut Self::Output {
+		&mut self.inner[idx.index()]
+	}
 }
 
 #[macro_export]
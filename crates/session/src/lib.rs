@@ -12,6 +12,7 @@ use std::{
 mod diagnostics;
 mod id;
 mod macros;
+mod prim;
 mod source_map;
 mod span;
 #[path = "symbols.rs"]
@@ -20,13 +21,14 @@ mod symbols_;
 pub use crate::{
 	diagnostics::{Diagnostic, DiagnosticsHandler},
 	id::{Idx, IndexVec},
+	prim::{JsonTy, PrimTy},
 	source_map::{BytePos, SourceFile, SourceFileHash, SourceFileId, SourceMap, with_source_map},
 	span::Span,
 	symbols_::{Ident, Symbol},
 };
 
 pub mod symbols {
-	pub use crate::symbols_::{attrs, kw, remarkable};
+	pub use crate::symbols_::{attrs, auth, kw, meta, prim, remarkable};
 }
 
 /// This is there to avoid having to add `ariadne` in crates that uses `IntoDiagnostic` macro
@@ -0,0 +1,215 @@
+//! Built-in primitive types
+//!
+//! Primitives can be used in any type position without being declared. They
+//! take precedence over user definitions during name resolution. A few
+//! aliases are also accepted, e.g. `long_string` for `string`.
+
+use crate::{Symbol, symbols::prim};
+use std::fmt;
+
+/// A built-in type, e.g. `int` or `date-time`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PrimTy {
+	/// Any JSON value
+	Any,
+	/// Raw bytes, e.g. file uploads
+	Binary,
+	Bool,
+	/// Full-date as defined by RFC 3339, e.g. `2017-07-21`
+	Date,
+	/// Date-time as defined by RFC 3339, e.g. `2017-07-21T17:32:28Z`
+	DateTime,
+	Double,
+	Float,
+	/// Integer of unspecified size
+	Int,
+	Int32,
+	Int64,
+	String,
+	Uuid,
+}
+
+/// Type keyword of a JSON Schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JsonTy {
+	Boolean,
+	Integer,
+	Number,
+	String,
+}
+
+impl PrimTy {
+	pub const ALL: &[Self] = &[
+		Self::Any,
+		Self::Binary,
+		Self::Bool,
+		Self::Date,
+		Self::DateTime,
+		Self::Double,
+		Self::Float,
+		Self::Int,
+		Self::Int32,
+		Self::Int64,
+		Self::String,
+		Self::Uuid,
+	];
+
+	/// Other names of primitives, they resolve to the primitive and are
+	/// emitted under its name.
+	pub const ALIASES: &[(Symbol, Self)] = &[
+		(prim::DateAlias, Self::Date),
+		// Strings have no length limit, it is only a hint for readers
+		(prim::LongString, Self::String),
+	];
+
+	#[must_use]
+	pub fn from_symbol(symbol: Symbol) -> Option<Self> {
+		Self::ALL
+			.iter()
+			.copied()
+			.find(|prim| prim.symbol() == symbol)
+			.or_else(|| {
+				Self::ALIASES
+					.iter()
+					.find(|(alias, _)| *alias == symbol)
+					.map(|&(_, prim)| prim)
+			})
+	}
+
+	#[must_use]
+	pub const fn symbol(self) -> Symbol {
+		match self {
+			Self::Any => prim::Any,
+			Self::Binary => prim::Binary,
+			Self::Bool => prim::Bool,
+			Self::Date => prim::Date,
+			Self::DateTime => prim::DateTime,
+			Self::Double => prim::Double,
+			Self::Float => prim::Float,
+			Self::Int => prim::Int,
+			Self::Int32 => prim::Int32,
+			Self::Int64 => prim::Int64,
+			Self::String => prim::String,
+			Self::Uuid => prim::Uuid,
+		}
+	}
+
+	/// JSON Schema `type` of the primitive, `None` for [`PrimTy::Any`] which
+	/// accepts every type.
+	#[must_use]
+	pub const fn json_ty(self) -> Option<JsonTy> {
+		match self {
+			Self::Any => None,
+			Self::Bool => Some(JsonTy::Boolean),
+			Self::Int | Self::Int32 | Self::Int64 => Some(JsonTy::Integer),
+			Self::Float | Self::Double => Some(JsonTy::Number),
+			Self::Binary | Self::Date | Self::DateTime | Self::String | Self::Uuid => {
+				Some(JsonTy::String)
+			}
+		}
+	}
+
+	/// `OpenAPI` `format` implied by the primitive itself.
+	#[must_use]
+	pub const fn format(self) -> Option<&'static str> {
+		match self {
+			Self::Any | Self::Bool | Self::Int | Self::String => None,
+			Self::Binary => Some("binary"),
+			Self::Date => Some("date"),
+			Self::DateTime => Some("date-time"),
+			Self::Double => Some("double"),
+			Self::Float => Some("float"),
+			Self::Int32 => Some("int32"),
+			Self::Int64 => Some("int64"),
+			Self::Uuid => Some("uuid"),
+		}
+	}
+
+	/// Values accepted by an `@format` attribute on a field of this type.
+	///
+	/// Primitives which already imply a format only accept that one.
+	#[must_use]
+	pub const fn allowed_formats(self) -> &'static [&'static str] {
+		match self {
+			Self::Any | Self::Bool => &[],
+			Self::Int => &["int32", "int64"],
+			Self::Int32 => &["int32"],
+			Self::Int64 => &["int64"],
+			Self::Float => &["float"],
+			Self::Double => &["double"],
+			Self::String => &[
+				"byte",
+				"binary",
+				"date",
+				"date-time",
+				"duration",
+				"email",
+				"hostname",
+				"ipv4",
+				"ipv6",
+				"password",
+				"regex",
+				"time",
+				"uri",
+				"uri-reference",
+				"uuid",
+			],
+			Self::Binary => &["binary"],
+			Self::Date => &["date"],
+			Self::DateTime => &["date-time"],
+			Self::Uuid => &["uuid"],
+		}
+	}
+}
+
+impl fmt::Display for PrimTy {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.symbol())
+	}
+}
+
+impl fmt::Display for JsonTy {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Boolean => write!(f, "boolean"),
+			Self::Integer => write!(f, "integer"),
+			Self::Number => write!(f, "number"),
+			Self::String => write!(f, "string"),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::PrimTy;
+	use crate::Symbol;
+
+	#[test]
+	fn prim_symbols_roundtrip() {
+		for prim in PrimTy::ALL {
+			assert_eq!(PrimTy::from_symbol(prim.symbol()), Some(*prim));
+		}
+		assert_eq!(
+			PrimTy::from_symbol(Symbol::intern("date-time")),
+			Some(PrimTy::DateTime)
+		);
+		assert_eq!(
+			PrimTy::from_symbol(Symbol::intern("Date")),
+			Some(PrimTy::Date)
+		);
+		assert_eq!(
+			PrimTy::from_symbol(Symbol::intern("long_string")),
+			Some(PrimTy::String)
+		);
+		assert_eq!(PrimTy::from_symbol(Symbol::intern("Uuid")), None);
+	}
+
+	#[test]
+	fn implied_format_is_allowed() {
+		for prim in PrimTy::ALL {
+			if let Some(format) = prim.format() {
+				assert!(prim.allowed_formats().contains(&format), "{prim}");
+			}
+		}
+	}
+}
@@ -489,14 +489,14 @@ mod pos {
 		/// # Panics
 		/// When used in a context where a source map is not available, this function will panic.
 		#[must_use]
-		pub(crate) fn to_char_pos(self) -> CharPos {
+		pub fn to_char_pos(self) -> CharPos {
 			self.try_to_char_pos()
 				.expect("this can only be called in a source context")
 		}
 
 		/// Translates to a [`CharPos`] if a [`SourceMap`](crate::SourceMap) context is available
 		#[must_use]
-		pub(crate) fn try_to_char_pos(self) -> Option<CharPos> {
+		pub fn try_to_char_pos(self) -> Option<CharPos> {
 			crate::with_source_map(|sm| sm.lookup_byte_pos(self))
 		}
 	}
@@ -23,27 +23,82 @@ symbols! { FRESH_SYMBOLS,
 		Auth: "auth",
 		Body: "body",
 		Code: "code",
+		Default: "default",
 		Enum: "enum",
 		Headers: "headers",
 		Meta: "meta",
 		Model: "model",
+		On: "on",
 		Params: "params",
 		Path: "path",
 		Query: "query",
+		Response: "response",
 		Scope: "scope",
+		Type: "type",
+		Union: "union",
 		Verb: "verb",
 
 		- // Bool literals
 		False: "false",
-		True: "true"
+		True: "true",
+
+		- // Type of the `null` value, e.g. `string | null`
+		Null: "null"
 	}
 
 	attrs {
 		deprecated,
 		description,
 		doc,
+		example,
 		format,
-		r#type: "type",
+		inline,
+		max,
+		maxItems,
+		maxLength,
+		min,
+		minItems,
+		minLength,
+		multipleOf,
+		pattern,
+		unique,
+	}
+
+	auth {
+		authorizationUrl,
+		bearerFormat,
+		flow,
+		r#in: "in",
+		refreshUrl,
+		scheme,
+		scopes,
+		tokenUrl,
+		url,
+	}
+
+	meta {
+		contact,
+		license,
+		name,
+		urls,
+		version,
+	}
+
+	prim {
+		Any: "any",
+		Binary: "binary",
+		Bool: "bool",
+		Date: "date",
+		DateAlias: "Date",
+		DateTime: "date-time",
+		Double: "double",
+		Float: "float",
+		Int: "int",
+		Int32: "int32",
+		Int64: "int64",
+		LongString: "long_string",
+		String: "string",
+		Uuid: "uuid",
 	}
 
 	remarkable {
@@ -53,13 +108,14 @@ symbols! { FRESH_SYMBOLS,
 		Get: "GET",
 		Head: "HEAD",
 		Options: "OPTIONS",
+		Patch: "PATCH",
 		Post: "POST",
 		Put: "PUT",
 		Trace: "TRACE",
 	}
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Symbol(u32);
 
 impl fmt::Debug for Symbol {